@@ -0,0 +1,143 @@
+//! # funlog
+//!
+//! Reads the output of functions instrumented with `#[funlog]` and shows it as a call
+//! tree or as per-function statistics.
+//!
+//! The input is read from a file or standard input, one line at a time, in either
+//! form funlog writes:
+//!
+//! - Text log lines such as `add [in ]: x:1, y:2` and `add [out]: return:3`, as
+//!   printed by `print` or written through `log`; logger prefixes are skipped and
+//!   other lines are ignored
+//! - JSON lines from `JsonLinesSink` or `FUNLOG_JSON=calls.jsonl`, which add the
+//!   module, thread, call id and elapsed time of every call
+//!
+//! Entries are paired with their exits into an indented, colourised tree. Calls can be
+//! filtered by function, thread and duration, and `--summary` prints the calls,
+//! errors and times of each function instead of the tree. Threads and durations are
+//! only known for JSON lines.
+//!
+//! ## Examples
+//!
+//! ```text
+//! $ FUNLOG_JSON=calls.jsonl cargo run
+//! $ funlog calls.jsonl --fn parse --min-duration 1ms
+//! $ cargo run 2>&1 | funlog --summary
+//! ```
+
+mod parse;
+mod render;
+mod summary;
+mod tree;
+
+use clap::{Parser, ValueEnum};
+use render::Style;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+use tree::Filter;
+
+/// Pretty-print, filter and summarise funlog output as call trees.
+#[derive(Debug, Parser)]
+#[command(name = "funlog", version, about)]
+struct Args {
+    /// The funlog output to read, text log lines or JSON lines; standard input if
+    /// omitted or `-`
+    file: Option<PathBuf>,
+
+    /// Only show calls of this function, by name or `module::name`, with the calls
+    /// made inside them; may be repeated
+    #[arg(short, long = "fn", value_name = "NAME")]
+    function: Vec<String>,
+
+    /// Only show calls on this funlog thread id; may be repeated
+    #[arg(short, long, value_name = "ID")]
+    thread: Vec<u64>,
+
+    /// Only show calls that took at least this long, e.g. `500us` or `1.5ms`
+    #[arg(long, value_name = "DURATION", value_parser = parse::parse_duration)]
+    min_duration: Option<Duration>,
+
+    /// Only show calls that took at most this long
+    #[arg(long, value_name = "DURATION", value_parser = parse::parse_duration)]
+    max_duration: Option<Duration>,
+
+    /// Print the calls, errors and times of each function instead of the tree
+    #[arg(short, long)]
+    summary: bool,
+
+    /// When to colour the tree
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = Color::Auto)]
+    color: Color,
+}
+
+/// When to colour the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Color {
+    /// When writing to a terminal and `NO_COLOR` is not set
+    Auto,
+    /// Always
+    Always,
+    /// Never
+    Never,
+}
+
+impl Color {
+    fn enabled(self) -> bool {
+        match self {
+            Color::Auto => io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            Color::Always => true,
+            Color::Never => false,
+        }
+    }
+}
+
+/// Reads the events from the input, skipping lines funlog did not write.
+fn read(file: Option<&PathBuf>) -> io::Result<Vec<parse::Event>> {
+    let input: Box<dyn BufRead> = match file {
+        Some(path) if path.as_os_str() != "-" => Box::new(BufReader::new(File::open(path)?)),
+        _ => Box::new(io::stdin().lock()),
+    };
+    let mut events = Vec::new();
+    for line in input.lines() {
+        events.extend(parse::parse_line(&line?));
+    }
+    Ok(events)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let events = match read(args.file.as_ref()) {
+        Ok(events) => events,
+        Err(e) => {
+            let source = match &args.file {
+                Some(path) => path.display().to_string(),
+                None => "standard input".to_string(),
+            };
+            eprintln!("funlog: cannot read {source}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let filter = Filter {
+        functions: args.function,
+        threads: args.thread,
+        min_duration: args.min_duration,
+        max_duration: args.max_duration,
+    };
+    let calls = filter.apply(tree::build(events));
+    let output = if args.summary {
+        summary::table(&summary::summarize(&calls))
+    } else {
+        render::tree(
+            &calls,
+            Style {
+                color: args.color.enabled(),
+            },
+        )
+    };
+    // A closed pipe, as with `funlog calls.jsonl | head`, is not an error
+    let _ = io::stdout().lock().write_all(output.as_bytes());
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,229 @@
+use serde_json::Value;
+use std::time::Duration;
+
+/// Whether a line logged the entry to or the exit from a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A `[in ]` line
+    Enter,
+    /// A `[out]` line
+    Exit,
+}
+
+/// One entry or exit read from funlog output.
+///
+/// Text lines only carry the function name and the logged values; lines written by
+/// `JsonLinesSink` also carry the module, thread, call id and elapsed time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// Entry or exit
+    pub kind: Kind,
+    /// The name of the function
+    pub fn_name: String,
+    /// The module path of the function, if known
+    pub module: Option<String>,
+    /// funlog's id for the thread the call ran on, if known
+    pub thread: Option<u64>,
+    /// Identifies the call, if known; the entry and exit of one call share it
+    pub call_id: Option<u64>,
+    /// The logged values as written in the log line, e.g. `x:1, y:2`
+    pub values: String,
+    /// The logged return value
+    pub ret: Option<String>,
+    /// The time the call took, on exits
+    pub elapsed: Option<Duration>,
+}
+
+/// Parses one line of funlog output.
+///
+/// Lines starting with `{` are read as JSON lines; other lines are searched for a
+/// funlog log line, so prefixes added by loggers such as `env_logger` are skipped.
+///
+/// # Arguments
+///
+/// * `line` - The line, without its line ending
+///
+/// # Returns
+///
+/// Returns the event, or `None` for lines that funlog did not write
+pub fn parse_line(line: &str) -> Option<Event> {
+    let line = line.trim();
+    if line.starts_with('{') {
+        parse_json(line)
+    } else {
+        parse_text(line)
+    }
+}
+
+/// Parses a line written by `JsonLinesSink`.
+fn parse_json(line: &str) -> Option<Event> {
+    let value = serde_json::from_str::<Value>(line).ok()?;
+    let kind = match value["kind"].as_str()? {
+        "enter" => Kind::Enter,
+        "exit" => Kind::Exit,
+        _ => return None,
+    };
+    let text = |key: &str| value[key].as_str().map(str::to_string);
+    // The message keeps the values in the order they were logged
+    let values = match text("message").as_deref().and_then(parse_text) {
+        Some(event) => event.values,
+        None => String::new(),
+    };
+    Some(Event {
+        kind,
+        fn_name: text("fn")?,
+        module: text("module"),
+        thread: value["thread"].as_u64(),
+        call_id: value["call_id"].as_u64(),
+        values,
+        ret: text("ret"),
+        elapsed: value["elapsed_ns"].as_u64().map(Duration::from_nanos),
+    })
+}
+
+/// Parses a log line such as `add [in ]: x:1, y:2` or `add [out]: return:3`.
+fn parse_text(line: &str) -> Option<Event> {
+    let (index, kind, marker) = [(Kind::Enter, " [in ]"), (Kind::Exit, " [out]")]
+        .into_iter()
+        .filter_map(|(kind, marker)| Some((line.find(marker)?, kind, marker)))
+        .min_by_key(|(index, _, _)| *index)?;
+    let before = &line[..index];
+    let fn_name = before
+        .rsplit(|c: char| c.is_whitespace() || c == ']' || c == ':')
+        .next()?;
+    if fn_name.is_empty() || !fn_name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let after = &line[index + marker.len()..];
+    let detail = match after.strip_prefix(": ") {
+        Some(detail) => detail,
+        None if after.is_empty() => "",
+        None => return None,
+    };
+    let (values, ret) = match kind {
+        Kind::Enter => (detail, None),
+        Kind::Exit => match detail.strip_prefix("return:") {
+            Some(ret) => ("", Some(ret)),
+            None => match detail.find(", return:") {
+                Some(at) => (&detail[..at], Some(&detail[at + ", return:".len()..])),
+                None => (detail, None),
+            },
+        },
+    };
+    Some(Event {
+        kind,
+        fn_name: fn_name.to_string(),
+        module: None,
+        thread: None,
+        call_id: None,
+        values: values.to_string(),
+        ret: ret.map(str::to_string),
+        elapsed: None,
+    })
+}
+
+/// Parses a duration such as `250us`, `1.5ms` or `2s`, as written by `Duration`'s
+/// `Debug`; `µs` is accepted for `us`.
+///
+/// # Arguments
+///
+/// * `text` - The duration
+///
+/// # Returns
+///
+/// Returns the duration, or a message saying what is wrong with it
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .ok_or_else(|| format!("'{text}' has no unit, use ns, us, ms or s"))?;
+    let (number, unit) = text.split_at(split);
+    let number = number
+        .parse::<f64>()
+        .map_err(|_| format!("'{text}' does not start with a number"))?;
+    let nanos = match unit.trim() {
+        "ns" => 1.0,
+        "us" | "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        unit => return Err(format!("unknown unit '{unit}', use ns, us, ms or s")),
+    };
+    Ok(Duration::from_nanos((number * nanos).round() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: Kind, fn_name: &str, values: &str, ret: Option<&str>) -> Event {
+        Event {
+            kind,
+            fn_name: fn_name.to_string(),
+            module: None,
+            thread: None,
+            call_id: None,
+            values: values.to_string(),
+            ret: ret.map(str::to_string),
+            elapsed: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_text() {
+        assert_eq!(
+            parse_line("add [in ]: x:1, y:2"),
+            Some(event(Kind::Enter, "add", "x:1, y:2", None))
+        );
+        assert_eq!(
+            parse_line("add [out]: return:3"),
+            Some(event(Kind::Exit, "add", "", Some("3")))
+        );
+        assert_eq!(
+            parse_line("add [out]: x:1, return:Err(\"bad\")"),
+            Some(event(Kind::Exit, "add", "x:1", Some("Err(\"bad\")")))
+        );
+        assert_eq!(
+            parse_line("[2025-01-09T10:00:00Z DEBUG app] tick [out]"),
+            Some(event(Kind::Exit, "tick", "", None))
+        );
+        assert_eq!(
+            parse_line("DEBUG app: tick [in ]"),
+            Some(event(Kind::Enter, "tick", "", None))
+        );
+        assert_eq!(parse_line("starting server"), None);
+        assert_eq!(parse_line("a b [in ]x"), None);
+        assert_eq!(parse_line(" [in ]: x:1"), None);
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let line = r#"{"kind":"exit","level":"DEBUG","fn":"add","module":"app::math","file":"src/math.rs","line":3,"thread":2,"call_id":7,"params":{"y":"2","x":"1"},"ret":"3","elapsed_ns":1500,"message":"add [out]: x:1, y:2, return:3"}"#;
+        assert_eq!(
+            parse_line(line),
+            Some(Event {
+                kind: Kind::Exit,
+                fn_name: "add".to_string(),
+                module: Some("app::math".to_string()),
+                thread: Some(2),
+                call_id: Some(7),
+                values: "x:1, y:2".to_string(),
+                ret: Some("3".to_string()),
+                elapsed: Some(Duration::from_nanos(1500)),
+            })
+        );
+        assert_eq!(parse_line(r#"{"kind":"start","fn":"add"}"#), None);
+        assert_eq!(parse_line("{not json"), None);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("15ns"), Ok(Duration::from_nanos(15)));
+        assert_eq!(parse_duration("250us"), Ok(Duration::from_micros(250)));
+        assert_eq!(parse_duration("2µs"), Ok(Duration::from_micros(2)));
+        assert_eq!(parse_duration("1.5ms"), Ok(Duration::from_micros(1500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("3min").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::tree::Call;
+use std::fmt::Write;
+use std::time::Duration;
+
+const BOLD: &str = "1";
+const DIM: &str = "2";
+const RED: &str = "31";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const CYAN: &str = "36";
+
+/// Whether to colour the output with ANSI escape codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    /// Colour the output
+    pub color: bool,
+}
+
+impl Style {
+    /// Returns `text` in the colour or style given by the SGR `code`, or unchanged
+    /// when colour is off.
+    fn paint(self, code: &str, text: &str) -> String {
+        if self.color && !text.is_empty() {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Formats a duration with one decimal, e.g. `1.5ms`, as funlog's reports do.
+pub fn duration(duration: Duration) -> String {
+    format!("{duration:.1?}")
+}
+
+/// Renders calls as an indented tree, one line per call.
+///
+/// Each line shows the function with its logged values, the return value after `->`
+/// (red for an `Err`) and the elapsed time in brackets. Calls whose exit was not
+/// logged are marked `(no exit)`. When the calls ran on more than one thread, each
+/// thread's calls are grouped under a `thread N` heading.
+///
+/// # Arguments
+///
+/// * `calls` - The outermost calls to show
+/// * `style` - Whether to colour the output
+///
+/// # Returns
+///
+/// Returns the tree, with a newline after every line
+pub fn tree(calls: &[Call], style: Style) -> String {
+    let mut threads: Vec<(Option<u64>, Vec<&Call>)> = Vec::new();
+    for call in calls {
+        match threads
+            .iter_mut()
+            .find(|(thread, _)| *thread == call.thread)
+        {
+            Some((_, calls)) => calls.push(call),
+            None => threads.push((call.thread, vec![call])),
+        }
+    }
+    let mut out = String::new();
+    let headings = threads.len() > 1;
+    for (index, (thread, calls)) in threads.iter().enumerate() {
+        if headings {
+            if index > 0 {
+                out.push('\n');
+            }
+            let heading = match thread {
+                Some(thread) => format!("thread {thread}"),
+                None => "thread unknown".to_string(),
+            };
+            let _ = writeln!(out, "{}", style.paint(BOLD, &heading));
+        }
+        for call in calls {
+            line(&mut out, call, "", "", style);
+        }
+    }
+    out
+}
+
+/// Writes the line of `call` and the lines of its children.
+///
+/// # Arguments
+///
+/// * `out` - The buffer to append to
+/// * `call` - The call
+/// * `lead` - The connector in front of this call, e.g. `├── `
+/// * `indent` - The prefix for the lines of the children
+/// * `style` - Whether to colour the output
+fn line(out: &mut String, call: &Call, lead: &str, indent: &str, style: Style) {
+    let _ = write!(
+        out,
+        "{}{}({})",
+        style.paint(DIM, lead),
+        style.paint(&format!("{BOLD};{CYAN}"), &call.fn_name),
+        call.values
+    );
+    if let Some(ret) = &call.ret {
+        let color = if call.is_err() { RED } else { GREEN };
+        let _ = write!(out, " -> {}", style.paint(color, ret));
+    }
+    if let Some(elapsed) = call.elapsed {
+        let _ = write!(
+            out,
+            " {}",
+            style.paint(YELLOW, &format!("[{}]", duration(elapsed)))
+        );
+    }
+    if !call.exited {
+        let _ = write!(out, " {}", style.paint(DIM, "(no exit)"));
+    }
+    out.push('\n');
+    for (index, child) in call.children.iter().enumerate() {
+        let last = index + 1 == call.children.len();
+        let (lead, more) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        line(
+            out,
+            child,
+            &format!("{indent}{lead}"),
+            &format!("{indent}{more}"),
+            style,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_line;
+    use crate::tree::build;
+
+    fn calls(lines: &[&str]) -> Vec<Call> {
+        build(lines.iter().filter_map(|line| parse_line(line)))
+    }
+
+    #[test]
+    fn test_tree() {
+        let mut calls = calls(&[
+            "load [in ]: path:\"a.toml\"",
+            "read [in ]",
+            "read [out]: return:Ok(12)",
+            "parse [in ]: n:12",
+            "check [in ]",
+            "parse [out]: return:Err(\"eof\")",
+            "load [out]",
+            "main [in ]",
+        ]);
+        calls[0].elapsed = Some(Duration::from_micros(1500));
+        let plain = Style { color: false };
+        assert_eq!(
+            tree(&calls, plain),
+            concat!(
+                "load(path:\"a.toml\") [1.5ms]\n",
+                "├── read() -> Ok(12)\n",
+                "└── parse(n:12) -> Err(\"eof\")\n",
+                "    └── check() (no exit)\n",
+                "main() (no exit)\n",
+            )
+        );
+
+        let colored = tree(&calls[1..], Style { color: true });
+        assert_eq!(colored, "\x1b[1;36mmain\x1b[0m() \x1b[2m(no exit)\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_tree_threads() {
+        let line = |kind: &str, id: u64, thread: u64| {
+            format!(
+                r#"{{"kind":"{kind}","fn":"work","thread":{thread},"call_id":{id},"message":"work [in ]: id:{id}"}}"#
+            )
+        };
+        let lines = [
+            line("enter", 1, 1),
+            line("enter", 2, 2),
+            line("exit", 2, 2),
+            line("exit", 1, 1),
+            line("enter", 3, 1),
+            line("exit", 3, 1),
+        ];
+        let calls = build(lines.iter().filter_map(|line| parse_line(line)));
+        assert_eq!(
+            tree(&calls, Style { color: false }),
+            "thread 1\nwork(id:1)\nwork(id:3)\n\nthread 2\nwork(id:2)\n"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::render::duration;
+use crate::tree::Call;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// The calls of one function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// The function's path, or its name if the module is not known
+    pub function: String,
+    /// The number of calls
+    pub calls: u64,
+    /// The number of calls that returned an `Err`
+    pub errors: u64,
+    /// The number of calls whose elapsed time is known
+    pub timed: u64,
+    /// The total time of the timed calls
+    pub total: Duration,
+    /// The shortest timed call
+    pub min: Duration,
+    /// The longest timed call
+    pub max: Duration,
+}
+
+impl Row {
+    /// Returns the mean time of the timed calls.
+    pub fn mean(&self) -> Duration {
+        match u32::try_from(self.timed) {
+            Ok(timed) if timed > 0 => self.total / timed,
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Counts the calls of each function, including nested calls.
+///
+/// # Arguments
+///
+/// * `calls` - The outermost calls
+///
+/// # Returns
+///
+/// Returns a row per function, by total time, then number of calls, longest first
+pub fn summarize(calls: &[Call]) -> Vec<Row> {
+    let mut rows: Vec<Row> = Vec::new();
+    for call in calls {
+        call.walk(&mut |call| {
+            let function = call.path();
+            let index = match rows.iter().position(|row| row.function == function) {
+                Some(index) => index,
+                None => {
+                    rows.push(Row {
+                        function,
+                        calls: 0,
+                        errors: 0,
+                        timed: 0,
+                        total: Duration::ZERO,
+                        min: Duration::MAX,
+                        max: Duration::ZERO,
+                    });
+                    rows.len() - 1
+                }
+            };
+            let row = &mut rows[index];
+            row.calls += 1;
+            row.errors += u64::from(call.is_err());
+            if let Some(elapsed) = call.elapsed {
+                row.timed += 1;
+                row.total += elapsed;
+                row.min = row.min.min(elapsed);
+                row.max = row.max.max(elapsed);
+            }
+        });
+    }
+    rows.sort_by(|a, b| b.total.cmp(&a.total).then(b.calls.cmp(&a.calls)));
+    rows
+}
+
+/// Renders rows as a table in the layout of `funlog::report()`.
+///
+/// Times are shown as `-` for functions without timed calls, as in text input.
+///
+/// # Arguments
+///
+/// * `rows` - The rows, as returned by [`summarize`]
+///
+/// # Returns
+///
+/// Returns the table, with a newline after every line
+pub fn table(rows: &[Row]) -> String {
+    let width = rows
+        .iter()
+        .map(|row| row.function.len())
+        .max()
+        .unwrap_or(0)
+        .max(8);
+    let mut out = format!(
+        "{:<width$} {:>8} {:>8} {:>7} {:>10} {:>10} {:>10} {:>10}\n",
+        "function", "calls", "errors", "err%", "total", "mean", "min", "max"
+    );
+    for row in rows {
+        let time = |value: Duration| {
+            if row.timed > 0 {
+                duration(value)
+            } else {
+                "-".to_string()
+            }
+        };
+        let _ = writeln!(
+            out,
+            "{:<width$} {:>8} {:>8} {:>6.1}% {:>10} {:>10} {:>10} {:>10}",
+            row.function,
+            row.calls,
+            row.errors,
+            row.errors as f64 / row.calls as f64 * 100.0,
+            time(row.total),
+            time(row.mean()),
+            time(row.min),
+            time(row.max),
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_line;
+    use crate::tree::build;
+
+    #[test]
+    fn test_summarize() {
+        let mut calls = build(
+            [
+                "load [in ]",
+                "parse [in ]",
+                "parse [out]: return:Err(1)",
+                "parse [in ]",
+                "parse [out]: return:Ok(2)",
+                "load [out]",
+                "parse [in ]",
+                "parse [out]: return:Ok(3)",
+            ]
+            .iter()
+            .filter_map(|line| parse_line(line)),
+        );
+        calls[0].children[0].elapsed = Some(Duration::from_millis(4));
+        calls[0].children[1].elapsed = Some(Duration::from_millis(1));
+
+        let rows = summarize(&calls);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].function, "parse");
+        assert_eq!((rows[0].calls, rows[0].errors, rows[0].timed), (3, 1, 2));
+        assert_eq!(rows[0].total, Duration::from_millis(5));
+        assert_eq!(rows[0].mean(), Duration::from_micros(2500));
+        assert_eq!(rows[0].min, Duration::from_millis(1));
+        assert_eq!(rows[0].max, Duration::from_millis(4));
+        assert_eq!(rows[1].function, "load");
+        assert_eq!(rows[1].mean(), Duration::ZERO);
+
+        assert_eq!(
+            table(&rows),
+            concat!(
+                "function    calls   errors    err%      total       mean        min        max\n",
+                "parse           3        1   33.3%      5.0ms      2.5ms      1.0ms      4.0ms\n",
+                "load            1        0    0.0%          -          -          -          -\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use crate::parse::{Event, Kind};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// One call of an instrumented function, with the calls made inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    /// The name of the function
+    pub fn_name: String,
+    /// The module path of the function, if known
+    pub module: Option<String>,
+    /// The thread the call ran on, if known
+    pub thread: Option<u64>,
+    /// The logged values, from the entry or else from the exit
+    pub values: String,
+    /// The logged return value
+    pub ret: Option<String>,
+    /// The time the call took, if known
+    pub elapsed: Option<Duration>,
+    /// Whether the entry was logged
+    pub entered: bool,
+    /// Whether the exit was logged
+    pub exited: bool,
+    /// The instrumented calls made inside this one, in order
+    pub children: Vec<Call>,
+}
+
+impl Call {
+    fn new(event: Event) -> Self {
+        Self {
+            entered: event.kind == Kind::Enter,
+            exited: event.kind == Kind::Exit,
+            fn_name: event.fn_name,
+            module: event.module,
+            thread: event.thread,
+            values: event.values,
+            ret: event.ret,
+            elapsed: event.elapsed,
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the function's path, `module::name`, or its name if the module is not
+    /// known.
+    pub fn path(&self) -> String {
+        match &self.module {
+            Some(module) => format!("{module}::{}", self.fn_name),
+            None => self.fn_name.clone(),
+        }
+    }
+
+    /// Returns whether the call returned an `Err`.
+    pub fn is_err(&self) -> bool {
+        self.ret
+            .as_deref()
+            .is_some_and(|ret| ret.starts_with("Err("))
+    }
+
+    /// Calls `f` with this call and every call nested in it, parents first.
+    pub fn walk<'a>(&'a self, f: &mut impl FnMut(&'a Call)) {
+        f(self);
+        for child in &self.children {
+            child.walk(f);
+        }
+    }
+}
+
+/// A call whose exit has not been read yet.
+struct Open {
+    call: Call,
+    call_id: Option<u64>,
+    /// The index in the roots reserved for an outermost call
+    slot: Option<usize>,
+}
+
+/// Pairs entries with exits and nests calls in the calls they were made in.
+///
+/// Calls on different threads are paired separately. An exit is paired with the
+/// innermost open call of the same id, or for text lines of the same function; open
+/// calls inside it are closed without an exit. An exit without an entry, as logged by
+/// `onEnd`, becomes a call nested in the innermost open call.
+///
+/// # Arguments
+///
+/// * `events` - The events in the order they were logged
+///
+/// # Returns
+///
+/// Returns the outermost calls in the order they started
+pub fn build(events: impl IntoIterator<Item = Event>) -> Vec<Call> {
+    let mut roots: Vec<Option<Call>> = Vec::new();
+    let mut stacks: HashMap<Option<u64>, Vec<Open>> = HashMap::new();
+    for event in events {
+        let stack = stacks.entry(event.thread).or_default();
+        match event.kind {
+            Kind::Enter => {
+                let slot = stack.is_empty().then(|| {
+                    roots.push(None);
+                    roots.len() - 1
+                });
+                stack.push(Open {
+                    call_id: event.call_id,
+                    call: Call::new(event),
+                    slot,
+                });
+            }
+            Kind::Exit => {
+                let matching = stack.iter().rposition(|open| match event.call_id {
+                    Some(id) => open.call_id == Some(id),
+                    None => open.call.fn_name == event.fn_name,
+                });
+                let Some(index) = matching else {
+                    attach(stack, &mut roots, Call::new(event));
+                    continue;
+                };
+                while stack.len() > index + 1 {
+                    close(stack, &mut roots);
+                }
+                let call = &mut stack[index].call;
+                call.exited = true;
+                call.ret = event.ret;
+                call.elapsed = event.elapsed;
+                if call.values.is_empty() {
+                    call.values = event.values;
+                }
+                close(stack, &mut roots);
+            }
+        }
+    }
+    for stack in stacks.values_mut() {
+        while !stack.is_empty() {
+            close(stack, &mut roots);
+        }
+    }
+    roots.into_iter().flatten().collect()
+}
+
+/// Pops the innermost open call and adds it to its parent or to the roots.
+fn close(stack: &mut Vec<Open>, roots: &mut Vec<Option<Call>>) {
+    if let Some(open) = stack.pop() {
+        match open.slot {
+            Some(slot) => roots[slot] = Some(open.call),
+            None => attach(stack, roots, open.call),
+        }
+    }
+}
+
+fn attach(stack: &mut [Open], roots: &mut Vec<Option<Call>>, call: Call) {
+    match stack.last_mut() {
+        Some(parent) => parent.call.children.push(call),
+        None => roots.push(Some(call)),
+    }
+}
+
+/// Selects the calls to show.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    /// Function names or `module::name` paths; any of them matches
+    pub functions: Vec<String>,
+    /// Thread ids; any of them matches
+    pub threads: Vec<u64>,
+    /// The shortest call to show
+    pub min_duration: Option<Duration>,
+    /// The longest call to show
+    pub max_duration: Option<Duration>,
+}
+
+impl Filter {
+    /// Returns whether `call` itself passes every condition of the filter.
+    pub fn matches(&self, call: &Call) -> bool {
+        let function = self.functions.is_empty()
+            || self
+                .functions
+                .iter()
+                .any(|name| *name == call.fn_name || *name == call.path());
+        let thread = self.threads.is_empty()
+            || call
+                .thread
+                .is_some_and(|thread| self.threads.contains(&thread));
+        let min = self
+            .min_duration
+            .is_none_or(|min| call.elapsed.is_some_and(|elapsed| elapsed >= min));
+        let max = self
+            .max_duration
+            .is_none_or(|max| call.elapsed.is_some_and(|elapsed| elapsed <= max));
+        function && thread && min && max
+    }
+
+    /// Keeps the outermost calls that match, with everything nested in them.
+    ///
+    /// Calls that do not match are dropped, and the matching calls inside them take
+    /// their place.
+    ///
+    /// # Arguments
+    ///
+    /// * `calls` - The calls, as returned by [`build`]
+    ///
+    /// # Returns
+    ///
+    /// Returns the matching calls in order
+    pub fn apply(&self, calls: Vec<Call>) -> Vec<Call> {
+        let mut kept = Vec::new();
+        for call in calls {
+            if self.matches(&call) {
+                kept.push(call);
+            } else {
+                kept.extend(self.apply(call.children));
+            }
+        }
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_line;
+
+    fn build_lines(lines: &[&str]) -> Vec<Call> {
+        build(lines.iter().filter_map(|line| parse_line(line)))
+    }
+
+    /// Renders calls as `name(values)=ret` with children in brackets, `!` marking a
+    /// missing exit and `?` a missing entry.
+    fn shape(calls: &[Call]) -> String {
+        calls
+            .iter()
+            .map(|call| {
+                let mut out = format!("{}({})", call.fn_name, call.values);
+                if let Some(ret) = &call.ret {
+                    out.push_str(&format!("={ret}"));
+                }
+                if !call.exited {
+                    out.push('!');
+                }
+                if !call.entered {
+                    out.push('?');
+                }
+                if !call.children.is_empty() {
+                    out.push_str(&format!("[{}]", shape(&call.children)));
+                }
+                out
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_build_text() {
+        let calls = build_lines(&[
+            "fib [in ]: n:2",
+            "fib [in ]: n:1",
+            "fib [out]: return:1",
+            "noise",
+            "log [out]: msg:\"x\"",
+            "fib [in ]: n:0",
+            "fib [out]: return:0",
+            "fib [out]: return:1",
+            "start [in ]",
+            "spawn [in ]",
+            "start [out]",
+            "open [in ]: n:1",
+        ]);
+        assert_eq!(
+            shape(&calls),
+            "fib(n:2)=1[fib(n:1)=1 log(msg:\"x\")? fib(n:0)=0] start()[spawn()!] open(n:1)!"
+        );
+    }
+
+    #[test]
+    fn test_build_json_threads() {
+        let line = |kind: &str, id: u64, thread: u64, extra: &str| {
+            format!(
+                r#"{{"kind":"{kind}","fn":"f{id}","module":"app","thread":{thread},"call_id":{id}{extra},"message":""}}"#
+            )
+        };
+        let lines = [
+            line("enter", 1, 1, ""),
+            line("enter", 2, 2, ""),
+            line("enter", 3, 1, ""),
+            line("exit", 2, 2, r#","elapsed_ns":5"#),
+            line("exit", 1, 1, r#","elapsed_ns":9"#),
+        ];
+        let calls = build(lines.iter().filter_map(|line| parse_line(line)));
+        assert_eq!(shape(&calls), "f1()[f3()!] f2()");
+        assert_eq!(calls[0].thread, Some(1));
+        assert_eq!(calls[0].elapsed, Some(Duration::from_nanos(9)));
+        assert_eq!(calls[0].path(), "app::f1");
+        assert_eq!(calls[1].thread, Some(2));
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut calls = build_lines(&[
+            "outer [in ]",
+            "inner [in ]: x:1",
+            "leaf [in ]",
+            "leaf [out]",
+            "inner [out]: return:Err(1)",
+            "outer [out]",
+            "inner [in ]: x:2",
+            "inner [out]: return:Ok(2)",
+        ]);
+        assert!(calls[0].children[0].is_err());
+        assert!(!calls[1].is_err());
+
+        let by_name = Filter {
+            functions: vec!["inner".to_string()],
+            ..Filter::default()
+        };
+        assert_eq!(
+            shape(&by_name.apply(calls.clone())),
+            "inner(x:1)=Err(1)[leaf()] inner(x:2)=Ok(2)"
+        );
+
+        calls[0].children[0].elapsed = Some(Duration::from_millis(5));
+        calls[1].elapsed = Some(Duration::from_millis(1));
+        let slow = Filter {
+            min_duration: Some(Duration::from_millis(2)),
+            ..Filter::default()
+        };
+        assert_eq!(
+            shape(&slow.apply(calls.clone())),
+            "inner(x:1)=Err(1)[leaf()]"
+        );
+        let fast = Filter {
+            max_duration: Some(Duration::from_millis(2)),
+            ..Filter::default()
+        };
+        assert_eq!(shape(&fast.apply(calls.clone())), "inner(x:2)=Ok(2)");
+
+        let by_thread = Filter {
+            threads: vec![1],
+            ..Filter::default()
+        };
+        assert_eq!(shape(&by_thread.apply(calls.clone())), "");
+        assert_eq!(Filter::default().apply(calls.clone()), calls);
+    }
+}
@@ -0,0 +1,117 @@
+use funlog::{clear_sinks, funlog, register_sink, JsonLinesSink};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+#[funlog(debug, retVal)]
+fn parse(text: &str) -> Result<u32, std::num::ParseIntError> {
+    text.parse()
+}
+
+#[funlog(debug, all, retVal)]
+fn total(texts: &[&str]) -> u32 {
+    texts.iter().filter_map(|text| parse(text).ok()).sum()
+}
+
+fn funlog_cli(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_funlog"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Replaces the `[1.5ms]` times in a tree with `[time]`.
+fn without_times(tree: &str) -> String {
+    tree.lines()
+        .map(|line| match line.rfind(" [") {
+            Some(at) if line.ends_with(']') => format!("{} [time]", &line[..at]),
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_json_lines_tree_and_summary() {
+    let path = std::env::temp_dir().join(format!("funlog-cli-{}.jsonl", std::process::id()));
+    register_sink(JsonLinesSink::create(&path).unwrap());
+    total(&["1", "x", "2"]);
+    clear_sinks();
+    let path = path.to_str().unwrap();
+
+    let output = funlog_cli(&[path, "--color", "never"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        without_times(&String::from_utf8(output.stdout).unwrap()),
+        concat!(
+            "total(texts:[\"1\", \"x\", \"2\"]) -> 3 [time]\n",
+            "├── parse(text:\"1\") -> Ok(1) [time]\n",
+            "├── parse(text:\"x\") -> Err(ParseIntError { kind: InvalidDigit }) [time]\n",
+            "└── parse(text:\"2\") -> Ok(2) [time]",
+        )
+    );
+
+    let output = funlog_cli(&[path, "--fn", "parse", "--color", "never"], "");
+    let tree = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(tree.lines().count(), 3);
+    assert!(tree.lines().all(|line| line.starts_with("parse(")));
+
+    let output = funlog_cli(&[path, "--min-duration", "1h"], "");
+    assert_eq!(output.stdout, b"");
+
+    let output = funlog_cli(&[path, "--summary"], "");
+    let summary = String::from_utf8(output.stdout).unwrap();
+    let lines = summary.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("function "));
+    assert!(lines[1].starts_with("raw_test_cli::total "));
+    let parse = lines[2].split_whitespace().collect::<Vec<_>>();
+    assert_eq!(&parse[..4], ["raw_test_cli::parse", "3", "1", "33.3%"]);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_text_from_stdin() {
+    let log = "\
+[2025-01-09T10:00:00Z DEBUG app] load [in ]: path:\"a.toml\"
+starting
+[2025-01-09T10:00:00Z DEBUG app] read [in ]
+[2025-01-09T10:00:00Z DEBUG app] read [out]: return:Ok(12)
+[2025-01-09T10:00:00Z DEBUG app] load [out]
+";
+    let output = funlog_cli(&["--color", "never"], log);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "load(path:\"a.toml\")\n└── read() -> Ok(12)\n"
+    );
+
+    let output = funlog_cli(&["-", "--summary"], log);
+    let summary = String::from_utf8(output.stdout).unwrap();
+    assert!(summary.contains("\nload "));
+    assert!(summary.lines().all(|line| !line.contains("ms")));
+}
+
+#[test]
+fn test_errors() {
+    let output = funlog_cli(&["/nonexistent/calls.jsonl"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("funlog: cannot read /nonexistent/calls.jsonl: "));
+
+    let output = funlog_cli(&["--min-duration", "5"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("'5' has no unit, use ns, us, ms or s"));
+}
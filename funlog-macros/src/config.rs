@@ -0,0 +1,982 @@
+use crate::expr_check::{break_from_body, collect_pat_idents, contains_impl_trait};
+use crate::expr_check::{deref_idents, mentions_self_type};
+use crate::file_config::FormatOptions;
+use crate::log_template::LogTemplate;
+use crate::output::Output;
+use proc_macro2::Span;
+use quote::quote;
+use std::path::PathBuf;
+use syn::Token;
+use syn::{punctuated::Punctuated, token::Comma, Ident};
+use syn::{Abi, Attribute, Block, Expr, FnArg, GenericParam, Generics, Lifetime, Pat, ReturnType};
+
+/// Specifies when logging output should occur during function execution.
+///
+/// # Examples
+///
+/// ```ignore
+/// use funlog::config::OutputPosition;
+///
+/// let position = OutputPosition::OnStart;
+/// // This will log only at the start of function execution
+/// ```
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum OutputPosition {
+    /// Log only at the start of function execution
+    OnStart,
+    /// Log only at the end of function execution
+    OnEnd,
+    /// Log at both start and end of function execution
+    OnStartAndEnd,
+}
+
+/// Specifies the type of logging output to use.
+///
+/// # Examples
+///
+/// ```ignore
+/// use funlog::config::OutputType;
+///
+/// let output_type = OutputType::Debug;
+/// // This will use log::debug! for output
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputType {
+    /// Use println! for output
+    Print,
+    /// Use log::error! for output
+    Error,
+    /// Use log::warn! for output
+    Warn,
+    /// Use log::info! for output
+    Info,
+    /// Use log::debug! for output
+    Debug,
+    /// Use log::trace! for output
+    Trace,
+}
+
+/// Configuration struct that holds all the settings for function logging.
+///
+/// This struct contains all the necessary information to generate the appropriate
+/// logging code for a function, including output position, type, parameters, and
+/// function metadata.
+///
+/// # Examples
+///
+/// ```ignore
+/// use funlog::config::{Config, OutputPosition, OutputType};
+/// use syn::{parse_quote, Visibility, Block, Ident, ReturnType};
+/// use syn::punctuated::Punctuated;
+///
+/// // This would typically be created by ConfigBuilder
+/// ```
+#[derive(Debug)]
+pub struct Config {
+    pub output_position: OutputPosition,
+    pub start_output_type: OutputType,
+    pub end_output_type: OutputType,
+    pub output_ret_value: bool,
+    pub stats: bool,
+    pub record: bool,
+    pub format: FormatOptions,
+    pub redact: Vec<String>,
+    pub config_file: Option<PathBuf>,
+    pub when: Option<Expr>,
+    pub when_ret: Option<Expr>,
+    pub fields: Vec<(Ident, Expr)>,
+    pub func_attrs_outer: Vec<Attribute>,
+    pub func_attrs_inner: Vec<Attribute>,
+    pub func_vis: syn::Visibility,
+    pub func_asyncness: Option<Token![async]>,
+    pub func_unsafety: Option<Token![unsafe]>,
+    pub func_abi: Option<Abi>,
+    pub func_generics: Generics,
+    pub func_block: Block,
+    pub func_name: syn::Ident,
+    pub func_params_for_output: Vec<Ident>,
+    pub func_params_for_invoke: Vec<Ident>,
+    pub func_params_destructure: Vec<(Pat, Expr)>,
+    pub func_params_rebind: Vec<(Pat, Ident)>,
+    pub func_params_for_declare: Punctuated<FnArg, Comma>,
+    pub func_params_for_wrapper: Punctuated<FnArg, Comma>,
+    pub func_return_type: syn::ReturnType,
+}
+
+impl Config {
+    /// Converts the configuration into an Output struct containing the generated code.
+    ///
+    /// This method takes all the configuration settings and generates the appropriate
+    /// TokenStream code for the logging functionality.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `Output` struct containing all the generated code tokens
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // This is typically called internally by the macro
+    /// // let output = config.to_output();
+    /// ```
+    pub(crate) fn to_output(&self) -> Output {
+        let Config {
+            func_vis,
+            func_name,
+            func_params_for_output,
+            func_params_for_invoke,
+            func_params_destructure,
+            func_params_for_wrapper,
+            func_return_type,
+            output_position,
+            start_output_type,
+            end_output_type,
+            output_ret_value,
+            stats,
+            record,
+            format,
+            redact,
+            config_file,
+            when,
+            when_ret,
+            fields,
+            func_attrs_outer,
+            func_attrs_inner,
+            func_asyncness,
+            func_unsafety,
+            func_abi,
+            func_generics,
+            ..
+        } = self;
+
+        let where_clause = &func_generics.where_clause;
+
+        // The ABI only belongs on the public symbol; the body runs as plain Rust inside it
+        let func_declare_start = quote! {
+            #(#func_attrs_outer)*
+            #[allow(clippy::too_many_arguments)]
+            #func_vis #func_asyncness #func_unsafety #func_abi fn #func_name #func_generics (#func_params_for_wrapper) #func_return_type #where_clause
+        };
+
+        let (let_output, invoke) = if self.needs_inner_item() {
+            (
+                quote! {
+                    #[allow(clippy::redundant_closure_call)]
+                    let output
+                },
+                self.invoke_inner_item(),
+            )
+        } else if self.needs_body_in_wrapper() {
+            self.invoke_in_wrapper()
+        } else {
+            (
+                quote! {
+                    #[allow(clippy::redundant_closure_call)]
+                    let output
+                },
+                self.invoke_inline_body(),
+            )
+        };
+
+        // Patterned arguments are bound to a single name in the wrapper; borrow their
+        // sub-identifiers from it so they can be logged like parameters
+        let param_destructures = func_params_destructure.iter().map(|(pat, place)| {
+            quote! {
+                #[allow(unused_variables)]
+                let #pat = &#place;
+            }
+        });
+        // Guards and fields see the borrowed sub-identifiers dereferenced, as places of
+        // the same types as plain parameters
+        let mut borrowed = Vec::new();
+        for (pat, _) in func_params_destructure {
+            collect_pat_idents(pat, &mut borrowed);
+        }
+        let deref_borrowed = |expr: &Expr| {
+            let mut expr = expr.clone();
+            deref_idents(&mut expr, &borrowed);
+            expr
+        };
+        let when = when.as_ref().map(deref_borrowed);
+        let when_ret = when_ret.as_ref().map(deref_borrowed);
+        let fields = fields
+            .iter()
+            .map(|(label, expr)| (label.clone(), deref_borrowed(expr)))
+            .collect::<Vec<_>>();
+
+        // Parameters are formatted once on entry, so the end log can reuse the values
+        // after the parameters have been moved into the original body, and only when
+        // the call is logged at all
+        let param_values = func_params_for_output.iter().map(|param| {
+            let value_name = LogTemplate::param_value_name(param);
+            let format_param = LogTemplate::format_param(param);
+            quote! {
+                let #value_name = if __funlog_log__ { #format_param } else { ::std::string::String::new() };
+            }
+        });
+        let param_value_names = func_params_for_output
+            .iter()
+            .map(LogTemplate::param_value_name)
+            .collect::<Vec<_>>();
+
+        // Use the new LogTemplate to generate log statements
+        let field_labels = fields
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect::<Vec<_>>();
+
+        let template = LogTemplate::new(
+            &func_name.to_string(),
+            func_params_for_output,
+            func_return_type,
+            *output_ret_value,
+        )
+        .with_fields(&field_labels)
+        .with_format(format, redact);
+        let debug_checks = template.debug_checks(func_params_for_output, output_position);
+
+        // Recompile when the configuration file changes; a file created later is not
+        // tracked, which the `funlog.toml` docs state
+        let config_file = config_file
+            .as_ref()
+            .and_then(|path| path.to_str())
+            .map(|path| quote! { const _: &[u8] = include_bytes!(#path); });
+
+        let func_declare_body = quote! {
+            #config_file
+            #debug_checks
+            #(#func_attrs_inner)*
+            #let_output = #invoke;
+        };
+
+        let func_declare_end = quote! {
+            output
+        };
+
+        // Fields are formatted once on entry so the end log can reuse them after the
+        // parameters have been moved into the inner function
+        let field_values = fields.iter().map(|(label, expr)| {
+            let value_name = LogTemplate::field_value_name(label);
+            let format_value = template.format_value(quote! { #expr });
+            quote! {
+                let #value_name = if __funlog_log__ { #format_value } else { ::std::string::String::new() };
+            }
+        });
+
+        let (func_output_start, func_output_end) = template.generate_log_statements_with_context(
+            output_position,
+            start_output_type,
+            end_output_type,
+            &param_value_names,
+        );
+
+        // The call is started first so its elapsed time covers the whole wrapper
+        let call_start = template.call_start();
+        let call_start = if *stats {
+            quote! {
+                #call_start
+                static __FUNLOG_STATS__: ::funlog::__private::CallStats =
+                    ::funlog::__private::CallStats::new(&__FUNLOG_CALLSITE__);
+            }
+        } else {
+            call_start
+        };
+        // Arguments are serialized before the body can move or change them
+        let call_start = match (*record, func_params_for_invoke.is_empty()) {
+            (false, _) => call_start,
+            (true, true) => quote! {
+                #call_start
+                ::funlog::__private::require_record!("#[funlog(record)]");
+                let __funlog_record__ = ::funlog::__private::Recording::start(&__funlog_call__);
+            },
+            (true, false) => quote! {
+                #call_start
+                ::funlog::__private::require_record!("#[funlog(record)]");
+                let mut __funlog_record__ = ::funlog::__private::Recording::start(&__funlog_call__);
+                if let ::core::option::Option::Some(__funlog_record__) = &mut __funlog_record__ {
+                    #(__funlog_record__.arg(&#func_params_for_invoke);)*
+                }
+            },
+        };
+        // Whether the call is logged is decided once, before the parameters are moved
+        // into the body: something has to receive its events, and the entry guard, if
+        // any, has to hold
+        let is_active = template.is_active(output_position, start_output_type, end_output_type);
+        let log_guard = match when {
+            Some(when) => quote! { #is_active && { #when } },
+            None => is_active,
+        };
+        let func_output_start = if func_output_start.is_empty() {
+            func_output_start
+        } else {
+            quote! {
+                if __funlog_log__ {
+                    #func_output_start
+                }
+            }
+        };
+        let func_output_start = quote! {
+            #call_start
+            #(#param_destructures)*
+            let __funlog_log__: bool = #log_guard;
+            #(#param_values)*
+            #(#field_values)*
+            #func_output_start
+        };
+
+        // Statistics and metrics count every call, whatever the guards decide about
+        // logging it
+        let stats_ref = if *stats {
+            quote! { ::core::option::Option::Some(&__FUNLOG_STATS__) }
+        } else {
+            quote! { ::core::option::Option::None }
+        };
+        let is_err = if returns_result(func_return_type) {
+            quote! { output.is_err() }
+        } else {
+            quote! { false }
+        };
+        let finish_call = quote! {
+            ::funlog::__private::finish(&__funlog_call__, #stats_ref, #is_err);
+        };
+
+        // The exit guard sees the return value as `ret`, borrowed from `output`
+        let func_output_end = match when_ret {
+            Some(when_ret) => quote! {
+                if __funlog_log__ && {
+                    let ret = &output;
+                    #when_ret
+                } {
+                    #func_output_end
+                }
+            },
+            None if func_output_end.is_empty() => func_output_end,
+            None => quote! {
+                if __funlog_log__ {
+                    #func_output_end
+                }
+            },
+        };
+        let record_call = record.then(|| {
+            quote! {
+                if let ::core::option::Option::Some(__funlog_record__) = __funlog_record__ {
+                    __funlog_record__.finish(&output);
+                }
+            }
+        });
+
+        // Finished after the exit log, so its return value reaches the call's span
+        let func_output_end = quote! {
+            #func_output_end
+            #record_call
+            #finish_call
+        };
+
+        Output {
+            func_declare_start,
+            func_declare_body,
+            func_declare_end,
+            func_output_start,
+            func_output_end,
+        }
+    }
+
+    /// Returns `true` if the function is a synchronous `#[track_caller]` function.
+    ///
+    /// `#[track_caller]` cannot be applied to closures on stable Rust, so the body of
+    /// such a function runs in a nested item or directly in the wrapper instead of the
+    /// inline closure. On async functions the attribute has no effect on stable Rust,
+    /// and rustc warns about it on the wrapper as it would on the original function.
+    fn is_sync_track_caller(&self) -> bool {
+        self.func_asyncness.is_none()
+            && self
+                .func_attrs_outer
+                .iter()
+                .any(|attr| attr.path().is_ident("track_caller"))
+    }
+
+    /// Returns `true` if the body of a `#[track_caller]` function can run in a nested
+    /// `fn` item that is marked `#[track_caller]` as well.
+    ///
+    /// Nested items cannot see the receiver or `Self` of an enclosing impl, so this
+    /// only holds for free functions and associated functions that do not mention
+    /// `Self`.
+    fn needs_inner_item(&self) -> bool {
+        let has_receiver = self
+            .func_params_for_declare
+            .iter()
+            .any(|arg| matches!(arg, FnArg::Receiver(_)));
+        let Config {
+            func_generics,
+            func_params_for_declare,
+            func_return_type,
+            func_block,
+            ..
+        } = self;
+        self.is_sync_track_caller()
+            && !has_receiver
+            && !mentions_self_type(quote! {
+                #func_generics (#func_params_for_declare) #func_return_type #func_block
+            })
+    }
+
+    /// Returns `true` if the body of a `#[track_caller]` function has to run directly
+    /// in the wrapper, because it is a method or mentions `Self`.
+    fn needs_body_in_wrapper(&self) -> bool {
+        self.is_sync_track_caller() && !self.needs_inner_item()
+    }
+
+    /// Generates a labeled block running the original body directly in the wrapper,
+    /// for `#[track_caller]` methods, so the caller's location reaches the body.
+    ///
+    /// The body's `return` and `?` are rewritten to leave the block, see
+    /// [`break_from_body`]; a `return` or `?` inside a macro invocation still leaves
+    /// the wrapper, skipping the end log.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `let output` binding, typed with the return type so `?` can
+    /// convert its residual, and the block expression whose value is the original
+    /// function's return value
+    fn invoke_in_wrapper(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let Config {
+            func_block,
+            func_params_rebind,
+            func_return_type,
+            ..
+        } = self;
+
+        let label = Lifetime::new("'__funlog_body__", Span::call_site());
+        let mut block = func_block.clone();
+        break_from_body(&mut block, &label);
+        let rebinds = func_params_rebind.iter().map(|(pat, binding)| {
+            quote! { let #pat = #binding; }
+        });
+        let output_type = match func_return_type {
+            ReturnType::Type(_, ty) if !contains_impl_trait(ty) => quote! { : #ty },
+            _ => quote! {},
+        };
+        let stmts = &block.stmts;
+        (
+            quote! {
+                #[allow(unused_labels)]
+                let output #output_type
+            },
+            quote! {
+                #label: {
+                    let __funlog_entered__ = __funlog_call__.enter();
+                    #(#rebinds)*
+                    #(#stmts)*
+                }
+            },
+        )
+    }
+
+    /// Generates the expression that runs the original body inline.
+    ///
+    /// Synchronous bodies become an immediately called `move` closure and async bodies
+    /// an awaited `async move` block, so `return` and `?` leave the body rather than
+    /// the wrapper and the end log still runs. Arguments declared with `mut`, `ref` or
+    /// a destructuring pattern are rebound with their original pattern first. The call
+    /// is entered while the body runs, on every poll for an async body, so the calls
+    /// it makes record it as their parent.
+    ///
+    /// # Returns
+    ///
+    /// Returns the expression whose value is the original function's return value
+    fn invoke_inline_body(&self) -> proc_macro2::TokenStream {
+        let Config {
+            func_block,
+            func_params_rebind,
+            func_return_type,
+            func_asyncness,
+            ..
+        } = self;
+
+        let rebinds = func_params_rebind.iter().map(|(pat, binding)| {
+            quote! { let #pat = #binding; }
+        });
+
+        // `impl Trait` cannot be named in a closure signature, so it is left to inference
+        let return_type = match func_return_type {
+            ReturnType::Type(_, ty) if contains_impl_trait(ty) => quote! {},
+            other => quote! { #other },
+        };
+
+        // Splice the statements rather than the block, so a body that is a single
+        // expression does not trip `unused_braces` in the caller's crate
+        let stmts = &func_block.stmts;
+        if func_asyncness.is_some() {
+            quote! {
+                ::funlog::__private::in_call(__funlog_call__, async move {
+                    #(#rebinds)*
+                    #(#stmts)*
+                })
+                .await
+            }
+        } else {
+            quote! {
+                (move || #return_type {
+                    let __funlog_entered__ = __funlog_call__.enter();
+                    #(#rebinds)*
+                    #(#stmts)*
+                })()
+            }
+        }
+    }
+
+    /// Generates a nested `fn __funlog_inner__` holding the original body, followed by
+    /// a call to it, for functions that must stay `#[track_caller]`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a block expression whose value is the original function's return value
+    fn invoke_inner_item(&self) -> proc_macro2::TokenStream {
+        let Config {
+            func_attrs_inner,
+            func_unsafety,
+            func_generics,
+            func_params_for_declare,
+            func_params_for_invoke,
+            func_return_type,
+            func_block,
+            ..
+        } = self;
+        let where_clause = &func_generics.where_clause;
+
+        // Type and const parameters are passed explicitly in case they only appear in
+        // the return type; lifetimes are left to inference
+        let generic_args = func_generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(ty) => Some(&ty.ident),
+                GenericParam::Const(konst) => Some(&konst.ident),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let turbofish = if generic_args.is_empty() {
+            quote! {}
+        } else {
+            quote! { ::<#(#generic_args),*> }
+        };
+
+        let mut call = quote! { __funlog_inner__ #turbofish(#(#func_params_for_invoke,) *) };
+        if func_unsafety.is_some() {
+            call = quote! { unsafe { #call } };
+        }
+
+        quote! {
+            {
+                let __funlog_entered__ = __funlog_call__.enter();
+                #(#func_attrs_inner)*
+                #[track_caller]
+                #[allow(clippy::too_many_arguments)]
+                #func_unsafety fn __funlog_inner__ #func_generics (#func_params_for_declare) #func_return_type #where_clause #func_block
+                #call
+            }
+        }
+    }
+}
+
+/// Returns `true` if the return type is written as a `Result`, including aliases such
+/// as `io::Result<T>`, so statistics and metrics can count the calls that return an
+/// `Err`.
+///
+/// # Arguments
+///
+/// * `return_type` - The return type of the function
+fn returns_result(return_type: &ReturnType) -> bool {
+    match return_type {
+        ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::format_ident;
+    use syn::{parse_quote, ItemFn};
+
+    fn create_test_config() -> Config {
+        let func: ItemFn = parse_quote! {
+            pub fn test_func(x: i32, y: String) -> i32 {
+                x + 1
+            }
+        };
+
+        Config {
+            output_position: OutputPosition::OnStartAndEnd,
+            start_output_type: OutputType::Debug,
+            end_output_type: OutputType::Debug,
+            output_ret_value: true,
+            stats: false,
+            record: false,
+            format: FormatOptions::default(),
+            redact: Vec::new(),
+            config_file: None,
+            when: None,
+            when_ret: None,
+            fields: vec![],
+            func_attrs_outer: vec![],
+            func_attrs_inner: vec![],
+            func_vis: func.vis,
+            func_asyncness: func.sig.asyncness,
+            func_unsafety: func.sig.unsafety,
+            func_abi: func.sig.abi,
+            func_generics: func.sig.generics,
+            func_block: *func.block,
+            func_name: func.sig.ident,
+            func_params_for_output: vec![format_ident!("x"), format_ident!("y")],
+            func_params_for_invoke: vec![format_ident!("x"), format_ident!("y")],
+            func_params_destructure: vec![],
+            func_params_rebind: vec![],
+            func_params_for_declare: func.sig.inputs.clone(),
+            func_params_for_wrapper: func.sig.inputs,
+            func_return_type: func.sig.output,
+        }
+    }
+
+    #[test]
+    fn test_output_position_debug() {
+        let pos = OutputPosition::OnStart;
+        assert!(matches!(pos, OutputPosition::OnStart));
+
+        let pos = OutputPosition::OnEnd;
+        assert!(matches!(pos, OutputPosition::OnEnd));
+
+        let pos = OutputPosition::OnStartAndEnd;
+        assert!(matches!(pos, OutputPosition::OnStartAndEnd));
+    }
+
+    #[test]
+    fn test_output_type_debug() {
+        let output_type = OutputType::Debug;
+        assert!(matches!(output_type, OutputType::Debug));
+
+        let output_type = OutputType::Info;
+        assert!(matches!(output_type, OutputType::Info));
+
+        let output_type = OutputType::Print;
+        assert!(matches!(output_type, OutputType::Print));
+    }
+
+    #[test]
+    fn test_config_to_output() {
+        let config = create_test_config();
+        let output = config.to_output();
+
+        // Verify that output contains the expected components
+        // We can't easily test the exact token content, but we can verify
+        // that the method completes without panicking
+        assert!(!output.func_declare_body.is_empty());
+        assert!(!output.func_declare_start.is_empty());
+    }
+
+    #[test]
+    fn test_config_with_different_positions() {
+        let mut config = create_test_config();
+
+        // Test OnStart position
+        config.output_position = OutputPosition::OnStart;
+        let output = config.to_output();
+        assert!(!output.func_output_start.is_empty());
+
+        // Test OnEnd position
+        config.output_position = OutputPosition::OnEnd;
+        let output = config.to_output();
+        assert!(!output.func_output_end.is_empty());
+
+        // Test OnStartAndEnd position
+        config.output_position = OutputPosition::OnStartAndEnd;
+        let output = config.to_output();
+        assert!(!output.func_output_start.is_empty());
+        assert!(!output.func_output_end.is_empty());
+    }
+
+    #[test]
+    fn test_config_with_different_output_types() {
+        let mut config = create_test_config();
+
+        // Test different output types
+        config.start_output_type = OutputType::Print;
+        config.end_output_type = OutputType::Print;
+        let _output = config.to_output();
+
+        config.start_output_type = OutputType::Error;
+        config.end_output_type = OutputType::Error;
+        let _output = config.to_output();
+
+        config.start_output_type = OutputType::Warn;
+        config.end_output_type = OutputType::Warn;
+        let _output = config.to_output();
+
+        config.start_output_type = OutputType::Info;
+        config.end_output_type = OutputType::Info;
+        let _output = config.to_output();
+
+        config.start_output_type = OutputType::Trace;
+        config.end_output_type = OutputType::Trace;
+        let _output = config.to_output();
+
+        // All should complete without panicking
+
+        // Entry and exit levels are independent
+        config.start_output_type = OutputType::Trace;
+        config.end_output_type = OutputType::Info;
+        let output = config.to_output();
+        assert!(output
+            .func_output_start
+            .to_string()
+            .contains("Level :: Trace"));
+        assert!(output.func_output_end.to_string().contains("Level :: Info"));
+    }
+
+    #[test]
+    fn test_config_with_guards() {
+        let mut config = create_test_config();
+        config.when = Some(parse_quote! { x > 10 });
+        config.when_ret = Some(parse_quote! { *ret != 0 });
+
+        let output = config.to_output();
+        let start = output.func_output_start.to_string();
+        let end = output.func_output_end.to_string();
+
+        assert!(start.contains(
+            "let __funlog_log__ : bool = :: funlog :: __private :: is_active (& __funlog_call__ , & [:: funlog :: __private :: Level :: Debug]) && { x > 10 } ;"
+        ));
+        assert!(start.contains("if __funlog_log__"));
+        assert!(end.contains("let ret = & output"));
+        assert!(end.contains("* ret != 0"));
+    }
+
+    #[test]
+    fn test_config_with_stats() {
+        let mut config = create_test_config();
+        let output = config.to_output();
+        assert!(!output
+            .func_output_start
+            .to_string()
+            .contains("__FUNLOG_STATS__"));
+        // Calls are finished without statistics for the `metrics` feature
+        assert!(output.func_output_end.to_string().ends_with(
+            ":: funlog :: __private :: finish (& __funlog_call__ , :: core :: option :: Option :: None , false) ;"
+        ));
+
+        config.stats = true;
+        config.when = Some(parse_quote! { x > 10 });
+        let output = config.to_output();
+        let start = output.func_output_start.to_string();
+        let end = output.func_output_end.to_string();
+        assert!(start.contains(
+            "static __FUNLOG_STATS__ : :: funlog :: __private :: CallStats = :: funlog :: __private :: CallStats :: new (& __FUNLOG_CALLSITE__)"
+        ));
+        // Recorded after, and outside, the exit guard
+        assert!(end.starts_with("if __funlog_log__"));
+        assert!(end.ends_with(
+            "} :: funlog :: __private :: finish (& __funlog_call__ , :: core :: option :: Option :: Some (& __FUNLOG_STATS__) , false) ;"
+        ));
+
+        config.func_return_type = parse_quote! { -> std::io::Result<i32> };
+        let end = config.to_output().func_output_end.to_string();
+        assert!(end.contains("Some (& __FUNLOG_STATS__) , output . is_err ())"));
+    }
+
+    #[test]
+    fn test_config_with_record() {
+        let mut config = create_test_config();
+        config.record = true;
+        let output = config.to_output();
+        let start = output.func_output_start.to_string();
+        let end = output.func_output_end.to_string();
+        assert!(
+            start.contains(":: funlog :: __private :: require_record ! (\"#[funlog(record)]\") ;")
+        );
+        assert!(start.contains(
+            "let mut __funlog_record__ = :: funlog :: __private :: Recording :: start (& __funlog_call__) ;"
+        ));
+        assert!(start.contains("__funlog_record__ . arg (& x) ;"));
+        assert!(end.contains("__funlog_record__ . finish (& output) ;"));
+
+        config.func_params_for_invoke.clear();
+        let start = config.to_output().func_output_start.to_string();
+        assert!(start.contains("let __funlog_record__ ="));
+        assert!(!start.contains(". arg ("));
+    }
+
+    #[test]
+    fn test_returns_result() {
+        assert!(returns_result(&parse_quote! { -> Result<(), String> }));
+        assert!(returns_result(&parse_quote! { -> io::Result<usize> }));
+        assert!(!returns_result(&parse_quote! { -> Option<u8> }));
+        assert!(!returns_result(&parse_quote! {}));
+    }
+
+    #[test]
+    fn test_config_with_fields() {
+        let mut config = create_test_config();
+        config.fields = vec![(format_ident!("len"), parse_quote! { y.len() })];
+
+        let output = config.to_output();
+        let start = output.func_output_start.to_string();
+
+        assert!(start.contains("let __funlog_field_len__ = if __funlog_log__ { format !"));
+        assert!(start.contains("x:{}, y:{}, len:{}"));
+    }
+
+    #[test]
+    fn test_config_with_qualifiers_and_attributes() {
+        let func: ItemFn = parse_quote! {
+            /// Docs
+            #[must_use]
+            #[allow(unused_variables)]
+            pub async unsafe extern "C" fn test_func<'a, T: Copy, const N: usize>(x: &'a T) -> T
+            where
+                T: Default
+            {
+                *x
+            }
+        };
+        let generics_fn = crate::generics_item_fn::GenericsFn::from(func.clone());
+        let (func_attrs_outer, func_attrs_inner) = generics_fn.split_attrs();
+
+        let mut config = create_test_config();
+        config.func_attrs_outer = func_attrs_outer;
+        config.func_attrs_inner = func_attrs_inner;
+        config.func_asyncness = func.sig.asyncness;
+        config.func_unsafety = func.sig.unsafety;
+        config.func_abi = func.sig.abi;
+        config.func_generics = func.sig.generics;
+
+        let output = config.to_output();
+        let declare = output.func_declare_start.to_string();
+        let body = output.func_declare_body.to_string();
+
+        assert!(
+            declare.starts_with("# [doc = r\" Docs\"] # [must_use] # [allow (unused_variables)]")
+        );
+        assert!(declare.contains(
+            "pub async unsafe extern \"C\" fn test_func < 'a , T : Copy , const N : usize >"
+        ));
+        assert!(declare.contains("where T : Default"));
+        assert!(body.contains("# [allow (unused_variables)] # [allow (clippy :: redundant_closure_call)] let output = :: funlog :: __private :: in_call (__funlog_call__ , async move"));
+        assert!(body.contains(". await ;"));
+        assert!(!body.contains("must_use"));
+        assert!(!body.contains("__test_func__"));
+    }
+
+    #[test]
+    fn test_config_inline_body_rebinds_arguments() {
+        let mut config = create_test_config();
+        config.func_params_rebind = vec![(parse_quote! { mut x }, format_ident!("x"))];
+
+        let body = config.to_output().func_declare_body.to_string();
+        assert!(body.contains("let output = (move || -> i32 { let __funlog_entered__ = __funlog_call__ . enter () ; let mut x = x ; x + 1 }) ()"));
+    }
+
+    #[test]
+    fn test_config_track_caller_uses_nested_item() {
+        let mut config = create_test_config();
+        config.func_attrs_outer = vec![parse_quote! { #[track_caller] }];
+
+        let body = config.to_output().func_declare_body.to_string();
+        assert!(body.contains("# [track_caller] # [allow (clippy :: too_many_arguments)] fn __funlog_inner__ (x : i32 , y : String) -> i32"));
+        assert!(body.contains("__funlog_inner__ (x , y ,)"));
+        assert!(!body.contains("move ||"));
+    }
+
+    #[test]
+    fn test_config_with_no_parameters() {
+        let func: ItemFn = parse_quote! {
+            pub fn test_func() -> i32 {
+                42
+            }
+        };
+
+        let config = Config {
+            output_position: OutputPosition::OnStartAndEnd,
+            start_output_type: OutputType::Debug,
+            end_output_type: OutputType::Debug,
+            output_ret_value: true,
+            stats: false,
+            record: false,
+            format: FormatOptions::default(),
+            redact: Vec::new(),
+            config_file: None,
+            when: None,
+            when_ret: None,
+            fields: vec![],
+            func_attrs_outer: vec![],
+            func_attrs_inner: vec![],
+            func_vis: func.vis,
+            func_asyncness: func.sig.asyncness,
+            func_unsafety: func.sig.unsafety,
+            func_abi: func.sig.abi,
+            func_generics: func.sig.generics,
+            func_block: *func.block,
+            func_name: func.sig.ident,
+            func_params_for_output: vec![],
+            func_params_for_invoke: vec![],
+            func_params_destructure: vec![],
+            func_params_rebind: vec![],
+            func_params_for_declare: func.sig.inputs.clone(),
+            func_params_for_wrapper: func.sig.inputs,
+            func_return_type: func.sig.output,
+        };
+
+        let output = config.to_output();
+        assert!(!output.func_declare_body.is_empty());
+    }
+
+    #[test]
+    fn test_config_with_no_return_value() {
+        let func: ItemFn = parse_quote! {
+            pub fn test_func(x: i32) {
+                println!("{}", x);
+            }
+        };
+
+        let config = Config {
+            output_position: OutputPosition::OnStartAndEnd,
+            start_output_type: OutputType::Debug,
+            end_output_type: OutputType::Debug,
+            output_ret_value: false,
+            stats: false,
+            record: false,
+            format: FormatOptions::default(),
+            redact: Vec::new(),
+            config_file: None,
+            when: None,
+            when_ret: None,
+            fields: vec![],
+            func_attrs_outer: vec![],
+            func_attrs_inner: vec![],
+            func_vis: func.vis,
+            func_asyncness: func.sig.asyncness,
+            func_unsafety: func.sig.unsafety,
+            func_abi: func.sig.abi,
+            func_generics: func.sig.generics,
+            func_block: *func.block,
+            func_name: func.sig.ident,
+            func_params_for_output: vec![format_ident!("x")],
+            func_params_for_invoke: vec![format_ident!("x")],
+            func_params_destructure: vec![],
+            func_params_rebind: vec![],
+            func_params_for_declare: func.sig.inputs.clone(),
+            func_params_for_wrapper: func.sig.inputs,
+            func_return_type: func.sig.output,
+        };
+
+        let output = config.to_output();
+        assert!(!output.func_declare_body.is_empty());
+    }
+}
@@ -0,0 +1,2189 @@
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::format_ident;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use syn::parse::Parser;
+use syn::spanned::Spanned;
+use syn::{punctuated::Punctuated, token::Comma, Ident, Meta};
+use syn::{Abi, Attribute, Block, Expr, ExprLit, FnArg, Generics, Lit, MetaList, MetaNameValue};
+use syn::{Pat, PatIdent, PatType};
+use syn::{ReturnType, Token, Visibility};
+
+use crate::config::{Config, OutputPosition, OutputType};
+use crate::error::ConfigError;
+use crate::expr_check::unknown_identifiers;
+use crate::expr_check::{collect_pat_idents, pattern_places, strip_binding_modes};
+use crate::file_config::{Defaults, FileConfig, FormatOptions};
+use crate::generics_item_fn::GenericsFn;
+
+#[derive(Debug)]
+pub enum ParameterEnum {
+    NoneParameter,
+    AllParameters,
+    Specified,
+}
+
+/// The options seen while parsing an attribute list, with the span of each occurrence.
+#[derive(Debug, Default)]
+struct SeenOptions {
+    params: Vec<(&'static str, Span)>,
+    levels: Vec<(&'static str, Span)>,
+    start_levels: Vec<(&'static str, Span)>,
+    end_levels: Vec<(&'static str, Span)>,
+    positions: Vec<(&'static str, Span)>,
+    when_ret: Option<Span>,
+}
+
+#[derive(Debug, Default)]
+pub struct ConfigBuilder {
+    output_position: Option<OutputPosition>,
+    param_config: Option<ParameterEnum>,
+    output_ret_value: Option<bool>,
+    stats: Option<bool>,
+    record: Option<bool>,
+    output_type: Option<OutputType>,
+    start_output_type: Option<OutputType>,
+    end_output_type: Option<OutputType>,
+    when: Option<Expr>,
+    when_ret: Option<Expr>,
+    fields: Vec<(Ident, Expr)>,
+    file_defaults: Defaults,
+    format: FormatOptions,
+    config_file: Option<PathBuf>,
+    presets: BTreeMap<String, String>,
+    func_attrs_outer: Vec<Attribute>,
+    func_attrs_inner: Vec<Attribute>,
+    func_vis: Option<Visibility>,
+    func_constness: Option<Token![const]>,
+    func_asyncness: Option<Token![async]>,
+    func_unsafety: Option<Token![unsafe]>,
+    func_abi: Option<Abi>,
+    func_generics: Generics,
+    func_block: Option<Block>,
+    func_name: Option<Ident>,
+    func_params_for_output: Vec<Ident>,
+    func_params_for_invoke: Vec<Ident>,
+    func_params_bound: Vec<Ident>,
+    func_params_destructure: Vec<(Pat, Expr)>,
+    func_params_rebind: Vec<(Pat, Ident)>,
+    func_params_for_declare: Punctuated<FnArg, Comma>,
+    func_params_for_wrapper: Punctuated<FnArg, Comma>,
+    func_return_type: Option<ReturnType>,
+}
+
+impl ConfigBuilder {
+    /// Sets the parameter configuration for the function logging.
+    ///
+    /// # Arguments
+    ///
+    /// * `param_config` - The parameter configuration enum specifying how parameters should be handled
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError` if there's an issue
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::config_builder::{ConfigBuilder, ParameterEnum};
+    ///
+    /// let mut builder = ConfigBuilder::default();
+    /// assert!(builder.param_config(ParameterEnum::AllParameters).is_ok());
+    /// ```
+    pub fn param_config(&mut self, param_config: ParameterEnum) -> Result<(), ConfigError> {
+        // Don't check conflicts here, check them uniformly in parse_meta_list
+        self.param_config = Some(param_config);
+        Ok(())
+    }
+
+    /// Sets the output type for logging (print, debug, info, etc.).
+    ///
+    /// # Arguments
+    ///
+    /// * `output_type` - The type of output to use for logging
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError` if there's an issue
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::config_builder::ConfigBuilder;
+    /// use funlog::config::OutputType;
+    ///
+    /// let mut builder = ConfigBuilder::default();
+    /// assert!(builder.output_type(OutputType::Debug).is_ok());
+    /// ```
+    pub fn output_type(&mut self, output_type: OutputType) -> Result<(), ConfigError> {
+        // Don't check conflicts here, check them uniformly in parse_meta_list
+        self.output_type = Some(output_type);
+        Ok(())
+    }
+
+    /// Sets the output type used for the entry log only, overriding `output_type`.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_type` - The type of output to use for the entry log
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError` if there's an issue
+    pub fn start_output_type(&mut self, output_type: OutputType) -> Result<(), ConfigError> {
+        // Don't check conflicts here, check them uniformly in parse_meta_list
+        self.start_output_type = Some(output_type);
+        Ok(())
+    }
+
+    /// Sets the output type used for the exit log only, overriding `output_type`.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_type` - The type of output to use for the exit log
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError` if there's an issue
+    pub fn end_output_type(&mut self, output_type: OutputType) -> Result<(), ConfigError> {
+        // Don't check conflicts here, check them uniformly in parse_meta_list
+        self.end_output_type = Some(output_type);
+        Ok(())
+    }
+
+    /// Applies the settings from `funlog.toml` for the module the function is in.
+    ///
+    /// The file's defaults are only used for options the attribute leaves unset, so
+    /// they never conflict with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_config` - The configuration read from `funlog.toml`
+    /// * `module` - The module path of the function relative to the crate root, if known
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError` if there's an issue
+    pub fn file_config(
+        &mut self,
+        file_config: &FileConfig,
+        module: Option<&str>,
+    ) -> Result<(), ConfigError> {
+        self.file_defaults = file_config.resolve(module);
+        self.format = file_config.format.clone();
+        self.config_file = file_config.path.clone();
+        self.presets = file_config.presets.clone();
+        Ok(())
+    }
+
+    /// Sets the position where logging output should occur.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_position` - The position for logging (OnStart, OnEnd, or OnStartAndEnd)
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError` if there's an issue
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::config_builder::ConfigBuilder;
+    /// use funlog::config::OutputPosition;
+    ///
+    /// let mut builder = ConfigBuilder::default();
+    /// assert!(builder.output_position(OutputPosition::OnStart).is_ok());
+    /// ```
+    pub fn output_position(&mut self, output_position: OutputPosition) -> Result<(), ConfigError> {
+        // Don't check conflicts here, check them uniformly in parse_meta_list
+        self.output_position = Some(output_position);
+        Ok(())
+    }
+
+    /// Sets whether to output the return value in logging.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_ret_value` - Whether to include return value in the log output
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError::AlreadySet` if already configured
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::config_builder::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::default();
+    /// assert!(builder.output_ret_value(true).is_ok());
+    /// // Setting it again should fail
+    /// assert!(builder.output_ret_value(false).is_err());
+    /// ```
+    pub fn output_ret_value(&mut self, output_ret_value: bool) -> Result<(), ConfigError> {
+        if self.output_ret_value.is_some() {
+            return Err(ConfigError::AlreadySet(
+                "return value configuration",
+                Span::call_site(),
+            ));
+        }
+        self.output_ret_value = Some(output_ret_value);
+        Ok(())
+    }
+
+    /// Sets whether to record call statistics for the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - Whether each call updates the function's counters
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError::AlreadySet` if already configured
+    pub fn stats(&mut self, stats: bool) -> Result<(), ConfigError> {
+        if self.stats.is_some() {
+            return Err(ConfigError::AlreadySet(
+                "statistics configuration",
+                Span::call_site(),
+            ));
+        }
+        self.stats = Some(stats);
+        Ok(())
+    }
+
+    /// Sets whether to record the arguments and return value of each call.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - Whether calls are written to the `FUNLOG_RECORD` cassette
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError::AlreadySet` if already configured
+    pub fn record(&mut self, record: bool) -> Result<(), ConfigError> {
+        if self.record.is_some() {
+            return Err(ConfigError::AlreadySet(
+                "record configuration",
+                Span::call_site(),
+            ));
+        }
+        self.record = Some(record);
+        Ok(())
+    }
+
+    /// Sets the guard expression evaluated against the parameters on function entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `when` - The expression that must evaluate to `true` for the call to be logged
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError::AlreadySet` if already configured
+    pub fn when(&mut self, when: Expr) -> Result<(), ConfigError> {
+        if self.when.is_some() {
+            return Err(ConfigError::AlreadySet("when", Span::call_site()));
+        }
+        self.when = Some(when);
+        Ok(())
+    }
+
+    /// Sets the guard expression evaluated against the return value on function exit.
+    ///
+    /// # Arguments
+    ///
+    /// * `when_ret` - The expression, referring to the return value as `ret`, that must
+    ///   evaluate to `true` for the exit to be logged
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError::AlreadySet` if already configured
+    pub fn when_ret(&mut self, when_ret: Expr) -> Result<(), ConfigError> {
+        if self.when_ret.is_some() {
+            return Err(ConfigError::AlreadySet("when_ret", Span::call_site()));
+        }
+        self.when_ret = Some(when_ret);
+        Ok(())
+    }
+
+    /// Adds a labelled expression that is evaluated on entry and logged as `label:value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The name the value is logged under
+    /// * `expr` - The expression to evaluate, e.g. `req.id` or `items.len()`
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or `ConfigError::InvalidParameterSyntax` if the label
+    /// is already used by another field
+    pub fn field(&mut self, label: Ident, expr: Expr) -> Result<(), ConfigError> {
+        if self.fields.iter().any(|(existing, _)| *existing == label) {
+            return Err(ConfigError::InvalidParameterSyntax {
+                param: label.to_string(),
+                expected: "unique names in fields(name = expression, ...)".to_string(),
+                span: label.span(),
+            });
+        }
+        self.fields.push((label, expr));
+        Ok(())
+    }
+
+    /// Builds the final configuration from the builder.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Config)` with the built configuration, or `ConfigError::MissingFunction`
+    /// if required function fields are missing
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::config_builder::ConfigBuilder;
+    /// use syn::{parse_quote, ItemFn};
+    /// use funlog::generics_item_fn::GenericsFn;
+    ///
+    /// let func: ItemFn = parse_quote! {
+    ///     fn test_func(x: i32) -> i32 { x + 1 }
+    /// };
+    /// let mut builder = ConfigBuilder::default();
+    /// builder.set_function_fields(GenericsFn::from(func));
+    /// assert!(builder.build().is_ok());
+    /// ```
+    pub fn build(self) -> Result<Config, ConfigError> {
+        let func_vis = self.func_vis.ok_or(ConfigError::MissingFunction)?;
+        let func_block = self.func_block.ok_or(ConfigError::MissingFunction)?;
+        let func_name = self.func_name.ok_or(ConfigError::MissingFunction)?;
+        let func_return_type = self.func_return_type.ok_or(ConfigError::MissingFunction)?;
+
+        if let Some(constness) = self.func_constness {
+            return Err(ConfigError::UnsupportedQualifier {
+                qualifier: "const fn".to_string(),
+                reason: "logging is not available in const contexts, remove #[funlog] or make the function non-const".to_string(),
+                span: constness.span,
+            });
+        }
+
+        let Defaults {
+            level: default_level,
+            position: default_position,
+            ret: default_ret,
+            stats: default_stats,
+            params: default_params,
+            redact,
+        } = self.file_defaults;
+        let default_level = default_level.map(OutputType::from);
+
+        // Default to the file's choice or all parameters, unless fields were chosen to
+        // stand in for them
+        let param_config = match self.param_config {
+            Some(param_config) => Some(param_config),
+            None if self.fields.is_empty() => default_params.map(ParameterEnum::from),
+            None => Some(ParameterEnum::NoneParameter),
+        };
+        let func_params_for_output = match param_config {
+            Some(ParameterEnum::AllParameters) | None => self.func_params_bound.clone(),
+            Some(ParameterEnum::NoneParameter) => Vec::new(),
+            Some(ParameterEnum::Specified) => self.func_params_for_output,
+        };
+
+        Ok(Config {
+            output_position: self
+                .output_position
+                .or_else(|| default_position.map(OutputPosition::from))
+                .unwrap_or(OutputPosition::OnStartAndEnd),
+            start_output_type: self
+                .start_output_type
+                .or(self.output_type)
+                .or(default_level)
+                .unwrap_or(OutputType::Print),
+            end_output_type: self
+                .end_output_type
+                .or(self.output_type)
+                .or(default_level)
+                .unwrap_or(OutputType::Print),
+            output_ret_value: self.output_ret_value.or(default_ret).unwrap_or(false),
+            stats: self.stats.or(default_stats).unwrap_or(false),
+            record: self.record.unwrap_or(false),
+            format: self.format,
+            redact,
+            config_file: self.config_file,
+            when: self.when,
+            when_ret: self.when_ret,
+            fields: self.fields,
+            func_attrs_outer: self.func_attrs_outer,
+            func_attrs_inner: self.func_attrs_inner,
+            func_vis,
+            func_asyncness: self.func_asyncness,
+            func_unsafety: self.func_unsafety,
+            func_abi: self.func_abi,
+            func_generics: self.func_generics,
+            func_block,
+            func_name,
+            func_params_for_output,
+            func_params_for_invoke: self.func_params_for_invoke,
+            func_params_destructure: self.func_params_destructure,
+            func_params_rebind: self.func_params_rebind,
+            func_params_for_declare: self.func_params_for_declare,
+            func_params_for_wrapper: self.func_params_for_wrapper,
+            func_return_type,
+        })
+    }
+
+    /// Creates a ConfigBuilder from metadata and function information, without a
+    /// configuration file.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta_list` - Punctuated list of metadata from the macro attributes
+    /// * `func` - Generic function information extracted from the ItemFn
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(ConfigBuilder)` on success, or `ConfigError` if parsing fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use funlog::config_builder::ConfigBuilder;
+    /// use syn::{parse_quote, ItemFn, Meta, punctuated::Punctuated, token::Comma};
+    /// use funlog::generics_item_fn::GenericsFn;
+    ///
+    /// let func: ItemFn = parse_quote! {
+    ///     fn test_func(x: i32) -> i32 { x + 1 }
+    /// };
+    /// let meta_list: Punctuated<Meta, Comma> = Punctuated::new();
+    /// let result = ConfigBuilder::from(meta_list, GenericsFn::from(func));
+    /// assert!(result.is_ok());
+    /// ```
+    #[cfg(test)]
+    pub fn from(meta_list: Punctuated<Meta, Comma>, func: GenericsFn) -> Result<Self, ConfigError> {
+        Self::from_with_file_config(meta_list, func, &FileConfig::default(), None)
+    }
+
+    /// Creates a ConfigBuilder using the defaults and presets from `funlog.toml`.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta_list` - Punctuated list of metadata from the macro attributes
+    /// * `func` - Generic function information extracted from the ItemFn
+    /// * `file_config` - The configuration read from `funlog.toml`
+    /// * `module` - The module path of the function relative to the crate root, if known
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(ConfigBuilder)` on success, or `ConfigError` if parsing fails
+    pub fn from_with_file_config(
+        meta_list: Punctuated<Meta, Comma>,
+        func: GenericsFn,
+        file_config: &FileConfig,
+        module: Option<&str>,
+    ) -> Result<Self, ConfigError> {
+        let mut builder = ConfigBuilder::default();
+        builder.file_config(file_config, module)?;
+        builder.set_function_fields(func);
+        builder.parse_meta_list(meta_list)?;
+        Ok(builder)
+    }
+
+    /// Sets the function-related fields from the GenericsFn.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The generic function information to extract fields from
+    fn set_function_fields(&mut self, func: GenericsFn) {
+        let (attrs_outer, attrs_inner) = func.split_attrs();
+        self.func_attrs_outer = attrs_outer;
+        self.func_attrs_inner = attrs_inner;
+        self.func_vis = Some(func.vis);
+        self.func_block = Some(func.block);
+        let func_decl = func.sig;
+        self.func_name = Some(func_decl.ident);
+        self.func_constness = func_decl.constness;
+        self.func_asyncness = func_decl.asyncness;
+        self.func_unsafety = func_decl.unsafety;
+        self.func_abi = func_decl.abi;
+        self.func_generics = func_decl.generics;
+        self.set_parameters(&func_decl.inputs);
+        self.func_params_for_declare = func_decl.inputs;
+        self.func_return_type = Some(func_decl.output);
+    }
+
+    /// Binds every function argument to a plain identifier for the wrapper.
+    ///
+    /// Arguments declared with a simple identifier (`x: i32`, `mut x: i32`) keep their
+    /// name. Any other pattern (`(a, b): (i32, i32)`, `Point { x, y }: Point`, `_: Marker`)
+    /// is bound to a generated `__funlog_arg_{index}__` name. Arguments that are not a
+    /// plain immutable identifier are rebound with their original pattern right before
+    /// the original body runs. The identifiers bound inside patterns remain available
+    /// for logging as references to their place in the generated binding, such as
+    /// `let a = &__funlog_arg_1__.0;`, or, when a place cannot be written, by
+    /// destructuring a reference to the whole binding.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The function arguments to extract parameter names from
+    fn set_parameters(&mut self, inputs: &Punctuated<FnArg, Comma>) {
+        for (index, input) in inputs.iter().enumerate() {
+            let mut wrapper_input = input.clone();
+            if let FnArg::Typed(PatType { pat, ty, .. }) = &mut wrapper_input {
+                let binding = match pat.as_ref() {
+                    Pat::Ident(PatIdent {
+                        ident,
+                        subpat: None,
+                        by_ref,
+                        mutability,
+                        ..
+                    }) => {
+                        if by_ref.is_some() || mutability.is_some() {
+                            self.func_params_rebind
+                                .push((pat.as_ref().clone(), ident.clone()));
+                        }
+                        self.func_params_bound.push(ident.clone());
+                        ident.clone()
+                    }
+                    other => {
+                        let binding = format_ident!("__funlog_arg_{}__", index);
+                        let mut bound = Vec::new();
+                        collect_pat_idents(other, &mut bound);
+                        match pattern_places(other, ty, syn::parse_quote! { #binding }) {
+                            Some(places) => self.func_params_destructure.extend(
+                                places.into_iter().map(|(ident, place)| {
+                                    let pat = Pat::Ident(PatIdent {
+                                        attrs: Vec::new(),
+                                        by_ref: None,
+                                        mutability: None,
+                                        ident,
+                                        subpat: None,
+                                    });
+                                    (pat, place)
+                                }),
+                            ),
+                            None if !bound.is_empty() => self
+                                .func_params_destructure
+                                .push((strip_binding_modes(other), syn::parse_quote! { #binding })),
+                            None => {}
+                        }
+                        self.func_params_bound.extend(bound);
+                        self.func_params_rebind
+                            .push((other.clone(), binding.clone()));
+                        binding
+                    }
+                };
+                **pat = Pat::Ident(PatIdent {
+                    attrs: Vec::new(),
+                    by_ref: None,
+                    mutability: None,
+                    ident: binding.clone(),
+                    subpat: None,
+                });
+                self.func_params_for_invoke.push(binding);
+            }
+            self.func_params_for_wrapper.push(wrapper_input);
+        }
+    }
+    /// Parses the metadata list from macro attributes and configures the builder.
+    ///
+    /// Every option is checked even after an error has been found, so that all the
+    /// problems in an attribute list are reported in a single compile.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta_list` - The punctuated list of metadata to parse
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, the error if exactly one problem was found, or
+    /// `ConfigError::Multiple` listing every problem in source order
+    fn parse_meta_list(&mut self, meta_list: Punctuated<Meta, Comma>) -> Result<(), ConfigError> {
+        let mut seen = SeenOptions::default();
+        let mut errors = Vec::new();
+        let meta_list = self.expand_preset(meta_list, &mut errors);
+
+        for meta in meta_list.iter() {
+            if let Err(error) = self.parse_meta(meta, &mut seen) {
+                errors.push(error);
+            }
+        }
+
+        // Check for conflicting configurations, pointing at every option after the first
+        for options in [
+            &seen.params,
+            &seen.levels,
+            &seen.start_levels,
+            &seen.end_levels,
+            &seen.positions,
+        ] {
+            if let [(option1, _), rest @ ..] = options.as_slice() {
+                errors.extend(
+                    rest.iter()
+                        .map(|(option2, span)| ConfigError::ConflictingOptions {
+                            option1: option1.to_string(),
+                            option2: option2.to_string(),
+                            span: *span,
+                        }),
+                );
+            }
+        }
+
+        // The exit guard can never fire when only the entry is logged
+        if let (Some(span), [("onStart", _)]) = (seen.when_ret, seen.positions.as_slice()) {
+            errors.push(ConfigError::ConflictingOptions {
+                option1: "when_ret".to_string(),
+                option2: "onStart".to_string(),
+                span,
+            });
+        }
+
+        ConfigError::from_many(errors)
+    }
+
+    /// Replaces `preset = name` with the options of the preset.
+    ///
+    /// Options in the preset that belong to the same group as an option written in the
+    /// attribute (for example `info` and `level = "debug"`) are dropped, so explicit
+    /// options override the preset instead of conflicting with it. The remaining
+    /// options are spanned on the preset name, so problems in the preset are reported
+    /// there, and go through the same validation as the rest of the attribute.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta_list` - The options written in the attribute
+    /// * `errors` - Collects problems with the preset itself
+    ///
+    /// # Returns
+    ///
+    /// Returns the preset's remaining options followed by the explicit options
+    fn expand_preset(
+        &self,
+        meta_list: Punctuated<Meta, Comma>,
+        errors: &mut Vec<ConfigError>,
+    ) -> Punctuated<Meta, Comma> {
+        let (presets, explicit): (Vec<Meta>, Vec<Meta>) = meta_list
+            .into_iter()
+            .partition(|meta| matches!(meta, Meta::NameValue(nv) if nv.path.is_ident("preset")));
+
+        let mut expanded = Punctuated::new();
+        for (index, meta) in presets.iter().enumerate() {
+            let Meta::NameValue(MetaNameValue { path, value, .. }) = meta else {
+                continue;
+            };
+            if index > 0 {
+                errors.push(ConfigError::AlreadySet("preset", path.span()));
+                continue;
+            }
+            match self.preset_options(value) {
+                Ok(options) => expanded.extend(options.into_iter().filter(|option| {
+                    Self::option_group(option).is_none_or(|group| {
+                        !explicit
+                            .iter()
+                            .any(|meta| Self::option_group(meta) == Some(group))
+                    })
+                })),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        expanded.extend(explicit);
+        expanded
+    }
+
+    /// Looks up and parses the options of the preset named by `preset = ...`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value assigned to `preset` in the attribute
+    ///
+    /// # Returns
+    ///
+    /// Returns the preset's options spanned on `value`, or a `ConfigError` if the
+    /// preset is not defined or its options cannot be parsed
+    fn preset_options(&self, value: &Expr) -> Result<Punctuated<Meta, Comma>, ConfigError> {
+        let name = self.parse_word_value("preset", value, "preset = name")?;
+        let Some(options) = self.presets.get(&name) else {
+            return Err(ConfigError::UnknownPreset {
+                preset: name,
+                available: self.presets.keys().cloned().collect(),
+                span: value.span(),
+            });
+        };
+
+        let span = value.span();
+        let invalid = |message: String| ConfigError::InvalidParameterSyntax {
+            param: format!("preset {name} = \"{options}\""),
+            expected: format!(
+                "a list of funlog options, such as \"info, all, retVal\" ({message})"
+            ),
+            span,
+        };
+        let tokens = options
+            .parse::<TokenStream>()
+            .map_err(|e| invalid(e.to_string()))?;
+        let options = Punctuated::<Meta, Comma>::parse_terminated
+            .parse2(respan(tokens, span))
+            .map_err(|e| invalid(e.to_string()))?;
+        if options.iter().any(|meta| meta.path().is_ident("preset")) {
+            return Err(invalid("presets cannot use other presets".to_string()));
+        }
+        Ok(options)
+    }
+
+    /// Returns the group an option belongs to, for overriding preset options.
+    ///
+    /// Options in the same group set the same thing, e.g. `debug` and `level = "info"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The option
+    ///
+    /// # Returns
+    ///
+    /// Returns the group name, or `None` for options funlog does not know
+    fn option_group(meta: &Meta) -> Option<&'static str> {
+        let name = meta.path().get_ident()?.to_string();
+        let group = match (meta, name.as_str()) {
+            (Meta::Path(_), "all" | "none") | (Meta::List(_), "params") => "params",
+            (Meta::Path(_), "print" | "trace" | "debug" | "info" | "warn" | "error")
+            | (Meta::NameValue(_), "level") => "level",
+            (Meta::Path(_), "onStart" | "onEnd" | "onStartEnd")
+            | (Meta::NameValue(_), "position") => "position",
+            (Meta::Path(_), "retVal") | (Meta::NameValue(_), "ret") => "ret",
+            (Meta::Path(_), "stats") | (Meta::NameValue(_), "stats") => "stats",
+            (Meta::Path(_), "record") | (Meta::NameValue(_), "record") => "record",
+            (Meta::List(_), "fields") => "fields",
+            (Meta::List(_), "start") => "start",
+            (Meta::List(_), "end") => "end",
+            (Meta::NameValue(_), "when") => "when",
+            (Meta::NameValue(_), "when_ret") => "when_ret",
+            _ => return None,
+        };
+        Some(group)
+    }
+
+    /// Parses a single option from the attribute list.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The option to parse
+    /// * `seen` - The options seen so far, used for conflict detection
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or the `ConfigError`s found in this option
+    fn parse_meta(&mut self, meta: &Meta, seen: &mut SeenOptions) -> Result<(), ConfigError> {
+        match meta {
+            Meta::Path(path) => {
+                let span = path.span();
+                let ident_str = path
+                    .get_ident()
+                    .map_or("unknown".to_string(), |i| i.to_string());
+
+                match ident_str.as_str() {
+                    "all" => {
+                        seen.params.push(("all", span));
+                        self.param_config(ParameterEnum::AllParameters)?;
+                    }
+                    "none" => {
+                        seen.params.push(("none", span));
+                        self.param_config(ParameterEnum::NoneParameter)?;
+                    }
+                    "print" => {
+                        seen.levels.push(("print", span));
+                        self.output_type(OutputType::Print)?;
+                    }
+                    "trace" => {
+                        seen.levels.push(("trace", span));
+                        self.output_type(OutputType::Trace)?;
+                    }
+                    "debug" => {
+                        seen.levels.push(("debug", span));
+                        self.output_type(OutputType::Debug)?;
+                    }
+                    "info" => {
+                        seen.levels.push(("info", span));
+                        self.output_type(OutputType::Info)?;
+                    }
+                    "warn" => {
+                        seen.levels.push(("warn", span));
+                        self.output_type(OutputType::Warn)?;
+                    }
+                    "error" => {
+                        seen.levels.push(("error", span));
+                        self.output_type(OutputType::Error)?;
+                    }
+                    "onStart" => {
+                        seen.positions.push(("onStart", span));
+                        self.output_position(OutputPosition::OnStart)?;
+                    }
+                    "onEnd" => {
+                        seen.positions.push(("onEnd", span));
+                        self.output_position(OutputPosition::OnEnd)?;
+                    }
+                    "onStartEnd" => {
+                        seen.positions.push(("onStartEnd", span));
+                        self.output_position(OutputPosition::OnStartAndEnd)?;
+                    }
+                    "retVal" => {
+                        self.output_ret_value(true).map_err(|e| e.with_span(span))?;
+                    }
+                    "stats" => {
+                        self.stats(true).map_err(|e| e.with_span(span))?;
+                    }
+                    "record" => {
+                        self.record(true).map_err(|e| e.with_span(span))?;
+                    }
+                    _ => {
+                        let suggestion = self.suggest_similar_attribute(&ident_str);
+                        return Err(ConfigError::InvalidAttribute {
+                            attr: ident_str,
+                            suggestion,
+                            span,
+                        });
+                    }
+                }
+            }
+            Meta::List(MetaList { path, tokens, .. }) => {
+                let span = path.span();
+                let list_name = path
+                    .get_ident()
+                    .map_or("unknown".to_string(), |i| i.to_string());
+
+                if path.is_ident("params") {
+                    seen.params.push(("params", span));
+
+                    let parser = Punctuated::<Ident, Comma>::parse_terminated;
+                    let idents = parser.parse2(tokens.clone())
+                        .map_err(|e| ConfigError::ParseError(format!("Parameter list parsing failed: {e}\n💡 Correct format: params(param1, param2)"), e.span()))?;
+
+                    let available_params: Vec<String> = self
+                        .func_params_bound
+                        .iter()
+                        .map(|i| i.to_string())
+                        .collect();
+
+                    let (params, unknown): (Vec<_>, Vec<_>) = idents
+                        .into_iter()
+                        .partition(|ident| self.func_params_bound.contains(ident));
+                    ConfigError::from_many(
+                        unknown
+                            .into_iter()
+                            .map(|ident| ConfigError::InvalidParameter {
+                                param: ident.to_string(),
+                                available: available_params.clone(),
+                                suggestion: self.suggest_similar_parameter(&ident.to_string()),
+                                span: ident.span(),
+                            })
+                            .collect(),
+                    )?;
+
+                    // Use the identifiers from the signature so diagnostics about the
+                    // logged values point at the parameter declarations
+                    self.param_config(ParameterEnum::Specified)?;
+                    self.func_params_for_output = params
+                        .iter()
+                        .filter_map(|param| self.func_params_bound.iter().find(|b| *b == param))
+                        .cloned()
+                        .collect();
+                } else if path.is_ident("fields") {
+                    let parser = Punctuated::<MetaNameValue, Comma>::parse_terminated;
+                    let entries = parser.parse2(tokens.clone())
+                        .map_err(|e| ConfigError::ParseError(format!("Field list parsing failed: {e}\n💡 Correct format: fields(name = expression, ...)"), e.span()))?;
+
+                    let available = self.func_params_bound.clone();
+                    let mut errors = Vec::new();
+                    for MetaNameValue { path, value, .. } in entries {
+                        let result = path
+                            .get_ident()
+                            .cloned()
+                            .ok_or_else(|| ConfigError::InvalidParameterSyntax {
+                                param: quote::quote!(#path).to_string(),
+                                expected: "fields(name = expression, ...)".to_string(),
+                                span: path.span(),
+                            })
+                            .and_then(|label| {
+                                self.check_identifiers("fields", &value, &available)?;
+                                self.field(label, value)
+                            });
+                        errors.extend(result.err());
+                    }
+                    ConfigError::from_many(errors)?;
+                } else if path.is_ident("start") || path.is_ident("end") {
+                    let is_start = path.is_ident("start");
+                    let parser = Punctuated::<Meta, Comma>::parse_terminated;
+                    let entries = parser.parse2(tokens.clone()).map_err(|e| {
+                        ConfigError::ParseError(
+                            format!("{list_name}(...) parsing failed: {e}\n💡 Correct format: {list_name}(level = \"debug\")"),
+                            e.span(),
+                        )
+                    })?;
+
+                    let mut errors = Vec::new();
+                    for entry in entries {
+                        let result = match &entry {
+                            Meta::NameValue(MetaNameValue { path, value, .. })
+                                if path.is_ident("level") =>
+                            {
+                                self.parse_level(value).and_then(|level| {
+                                    if is_start {
+                                        seen.start_levels.push(("start(level)", path.span()));
+                                        self.start_output_type(level)
+                                    } else {
+                                        seen.end_levels.push(("end(level)", path.span()));
+                                        self.end_output_type(level)
+                                    }
+                                })
+                            }
+                            other => Err(ConfigError::InvalidAttribute {
+                                attr: format!("{list_name}({})", quote::quote!(#other)),
+                                suggestion: None,
+                                span: other.span(),
+                            }),
+                        };
+                        errors.extend(result.err());
+                    }
+                    ConfigError::from_many(errors)?;
+                } else {
+                    let suggestion = if list_name == "param" {
+                        Some("params".to_string())
+                    } else {
+                        None
+                    };
+                    return Err(ConfigError::InvalidAttribute {
+                        attr: format!("{list_name}(...)"),
+                        suggestion,
+                        span,
+                    });
+                }
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. }) => {
+                let span = path.span();
+                let name = path
+                    .get_ident()
+                    .map_or("unknown".to_string(), |i| i.to_string());
+
+                match name.as_str() {
+                    "when" => {
+                        let available = self.func_params_bound.clone();
+                        let expr = self.parse_guard_expression("when", value, &available)?;
+                        self.when(expr).map_err(|e| e.with_span(span))?;
+                    }
+                    "when_ret" => {
+                        let available = vec![format_ident!("ret")];
+                        let expr = self.parse_guard_expression("when_ret", value, &available)?;
+                        self.when_ret(expr).map_err(|e| e.with_span(span))?;
+                        seen.when_ret = Some(span);
+                    }
+                    "level" => {
+                        let level = self.parse_level(value)?;
+                        seen.levels.push(("level", span));
+                        self.output_type(level)?;
+                    }
+                    "position" => {
+                        let position = self.parse_position(value)?;
+                        seen.positions.push(("position", span));
+                        self.output_position(position)?;
+                    }
+                    "ret" => {
+                        let ret = Self::parse_bool("ret", value)?;
+                        self.output_ret_value(ret).map_err(|e| e.with_span(span))?;
+                    }
+                    "stats" => {
+                        let stats = Self::parse_bool("stats", value)?;
+                        self.stats(stats).map_err(|e| e.with_span(span))?;
+                    }
+                    "record" => {
+                        let record = Self::parse_bool("record", value)?;
+                        self.record(record).map_err(|e| e.with_span(span))?;
+                    }
+                    _ => {
+                        let suggestion = self.suggest_similar_attribute(&name);
+                        return Err(ConfigError::InvalidAttribute {
+                            attr: format!("{name} = ..."),
+                            suggestion,
+                            span,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the value of a `key = value` option written as a string or a bare word,
+    /// e.g. `level = "debug"` or `level = debug`.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The name of the option, used in error messages
+    /// * `value` - The value assigned to the option in the attribute
+    /// * `expected` - The accepted values, used in error messages
+    ///
+    /// # Returns
+    ///
+    /// Returns the value as a string, or `ConfigError::InvalidParameterSyntax` spanned
+    /// on the value if it is neither a string literal nor an identifier
+    fn parse_word_value(
+        &self,
+        option: &str,
+        value: &Expr,
+        expected: &str,
+    ) -> Result<String, ConfigError> {
+        match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Ok(lit.value()),
+            Expr::Path(path) if path.path.get_ident().is_some() => {
+                Ok(path.path.get_ident().unwrap().to_string())
+            }
+            _ => Err(ConfigError::InvalidParameterSyntax {
+                param: option.to_string(),
+                expected: expected.to_string(),
+                span: value.span(),
+            }),
+        }
+    }
+
+    /// Parses the value of a boolean option such as `ret = true`.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The name of the option, used in error messages
+    /// * `value` - The value assigned to the option in the attribute
+    ///
+    /// # Returns
+    ///
+    /// Returns the value, or `ConfigError::InvalidParameterSyntax` spanned on the value
+    /// if it is not `true` or `false`
+    fn parse_bool(option: &str, value: &Expr) -> Result<bool, ConfigError> {
+        match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Bool(value),
+                ..
+            }) => Ok(value.value),
+            _ => Err(ConfigError::InvalidParameterSyntax {
+                param: option.to_string(),
+                expected: format!("{option} = true or {option} = false"),
+                span: value.span(),
+            }),
+        }
+    }
+
+    /// Parses the value of `level = ...`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value assigned to the option in the attribute
+    ///
+    /// # Returns
+    ///
+    /// Returns the log level, or `ConfigError::InvalidParameterSyntax` spanned on the
+    /// value if it does not name one
+    fn parse_level(&self, value: &Expr) -> Result<OutputType, ConfigError> {
+        let expected =
+            "level = \"print\" | \"trace\" | \"debug\" | \"info\" | \"warn\" | \"error\"";
+        let level = match self.parse_word_value("level", value, expected)?.as_str() {
+            "print" => OutputType::Print,
+            "trace" => OutputType::Trace,
+            "debug" => OutputType::Debug,
+            "info" => OutputType::Info,
+            "warn" => OutputType::Warn,
+            "error" => OutputType::Error,
+            other => {
+                return Err(ConfigError::InvalidParameterSyntax {
+                    param: format!("level = {other}"),
+                    expected: expected.to_string(),
+                    span: value.span(),
+                })
+            }
+        };
+        Ok(level)
+    }
+
+    /// Parses the value of `position = ...`.
+    ///
+    /// Accepts `start`, `end` and `start_end`, as well as the flag names `onStart`,
+    /// `onEnd` and `onStartEnd`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value assigned to the option in the attribute
+    ///
+    /// # Returns
+    ///
+    /// Returns the position, or `ConfigError::InvalidParameterSyntax` spanned on the
+    /// value if it does not name one
+    fn parse_position(&self, value: &Expr) -> Result<OutputPosition, ConfigError> {
+        let expected = "position = \"start\" | \"end\" | \"start_end\"";
+        let position = match self.parse_word_value("position", value, expected)?.as_str() {
+            "start" | "onStart" => OutputPosition::OnStart,
+            "end" | "onEnd" => OutputPosition::OnEnd,
+            "start_end" | "onStartEnd" => OutputPosition::OnStartAndEnd,
+            other => {
+                return Err(ConfigError::InvalidParameterSyntax {
+                    param: format!("position = {other}"),
+                    expected: expected.to_string(),
+                    span: value.span(),
+                })
+            }
+        };
+        Ok(position)
+    }
+
+    /// Parses a string literal option such as `when = "amount > 10_000"` into an expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The name of the option being parsed, used in error messages
+    /// * `value` - The value assigned to the option in the attribute
+    /// * `available` - The identifiers the expression is allowed to reference
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed expression, or a `ConfigError` spanned on the literal if it is
+    /// not a string, does not parse, or references an identifier that is not available
+    fn parse_guard_expression(
+        &self,
+        option: &str,
+        value: &Expr,
+        available: &[Ident],
+    ) -> Result<Expr, ConfigError> {
+        let Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) = value
+        else {
+            return Err(ConfigError::InvalidParameterSyntax {
+                param: option.to_string(),
+                expected: format!("{option} = \"<expression>\""),
+                span: value.span(),
+            });
+        };
+
+        let expr: Expr = lit.parse().map_err(|e| ConfigError::InvalidExpression {
+            option: option.to_string(),
+            message: e.to_string(),
+            span: lit.span(),
+        })?;
+
+        self.check_identifiers(option, &expr, available)?;
+        Ok(expr)
+    }
+
+    /// Checks that an expression only references identifiers that are in scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The name of the option the expression belongs to, used in error messages
+    /// * `expr` - The expression to check
+    /// * `available` - The identifiers the expression is allowed to reference
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or a `ConfigError::UnknownIdentifier` spanned on each
+    /// unknown identifier
+    fn check_identifiers(
+        &self,
+        option: &str,
+        expr: &Expr,
+        available: &[Ident],
+    ) -> Result<(), ConfigError> {
+        let available_names: Vec<String> = available.iter().map(|i| i.to_string()).collect();
+        ConfigError::from_many(
+            unknown_identifiers(expr, available)
+                .into_iter()
+                .map(|ident| ConfigError::UnknownIdentifier {
+                    ident: ident.to_string(),
+                    option: option.to_string(),
+                    available: available_names.clone(),
+                    span: ident.span(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Provides suggestions for misspelled attributes using similarity matching.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input attribute string to find suggestions for
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(String)` with a suggested attribute, or `None` if no similar attribute found
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::config_builder::ConfigBuilder;
+    ///
+    /// let builder = ConfigBuilder::default();
+    /// assert_eq!(builder.suggest_similar_attribute("debg"), Some("debug".to_string()));
+    /// assert_eq!(builder.suggest_similar_attribute("xyz"), None);
+    /// ```
+    fn suggest_similar_attribute(&self, input: &str) -> Option<String> {
+        let valid_attributes = [
+            "all",
+            "none",
+            "print",
+            "trace",
+            "debug",
+            "info",
+            "warn",
+            "error",
+            "onStart",
+            "onEnd",
+            "onStartEnd",
+            "retVal",
+            "params",
+            "when",
+            "when_ret",
+            "fields",
+            "level",
+            "position",
+            "ret",
+            "stats",
+            "record",
+            "start",
+            "end",
+            "preset",
+        ];
+
+        // Simple similarity matching
+        for attr in &valid_attributes {
+            if self.is_similar(input, attr) {
+                return Some(attr.to_string());
+            }
+        }
+
+        None
+    }
+
+    /// Finds the function parameter closest to a misspelled name in `params(...)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The parameter name as written in the attribute
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(String)` with the most similar real parameter, or `None` if no
+    /// parameter is similar enough
+    fn suggest_similar_parameter(&self, input: &str) -> Option<String> {
+        self.func_params_bound
+            .iter()
+            .map(|param| param.to_string())
+            .filter(|param| self.is_similar(input, param))
+            .min_by_key(|param| self.levenshtein_distance(input, param))
+    }
+
+    /// Performs a simple string similarity check using substring matching and edit distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First string to compare
+    /// * `b` - Second string to compare
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if strings are considered similar, `false` otherwise
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        let a_lower = a.to_lowercase();
+        let b_lower = b.to_lowercase();
+
+        // Check if they contain the same substring
+        if a_lower.contains(&b_lower) || b_lower.contains(&a_lower) {
+            return true;
+        }
+
+        // Check edit distance
+        self.levenshtein_distance(&a_lower, &b_lower) <= 2
+    }
+
+    /// Calculates the Levenshtein edit distance between two strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First string
+    /// * `b` - Second string
+    ///
+    /// # Returns
+    ///
+    /// Returns the minimum number of single-character edits required to transform one string into another
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::config_builder::ConfigBuilder;
+    ///
+    /// let builder = ConfigBuilder::default();
+    /// assert_eq!(builder.levenshtein_distance("debug", "debg"), 1);
+    /// assert_eq!(builder.levenshtein_distance("hello", "world"), 4);
+    /// ```
+    fn levenshtein_distance(&self, a: &str, b: &str) -> usize {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        let a_len = a_chars.len();
+        let b_len = b_chars.len();
+
+        if a_len == 0 {
+            return b_len;
+        }
+        if b_len == 0 {
+            return a_len;
+        }
+
+        let mut matrix = vec![vec![0; b_len + 1]; a_len + 1];
+
+        for (i, row) in matrix.iter_mut().enumerate().take(a_len + 1) {
+            row[0] = i;
+        }
+        for (j, cell) in matrix[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+
+        for i in 1..=a_len {
+            for j in 1..=b_len {
+                let cost = if a_chars[i - 1] == b_chars[j - 1] {
+                    0
+                } else {
+                    1
+                };
+                matrix[i][j] = std::cmp::min(
+                    std::cmp::min(
+                        matrix[i - 1][j] + 1, // deletion
+                        matrix[i][j - 1] + 1, // insertion
+                    ),
+                    matrix[i - 1][j - 1] + cost, // substitution
+                );
+            }
+        }
+
+        matrix[a_len][b_len]
+    }
+}
+
+/// Sets the span of every token in `tokens`, including those inside groups.
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                token = TokenTree::Group(respanned);
+            }
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{parse_quote, ItemFn};
+
+    fn create_test_function() -> GenericsFn {
+        let func: ItemFn = parse_quote! {
+            pub fn test_func(x: i32, y: String) -> i32 {
+                x + 1
+            }
+        };
+        GenericsFn::from(func)
+    }
+
+    #[test]
+    fn test_param_config() {
+        let mut builder = ConfigBuilder::default();
+
+        // Test setting parameter config
+        assert!(builder.param_config(ParameterEnum::AllParameters).is_ok());
+        assert!(matches!(
+            builder.param_config,
+            Some(ParameterEnum::AllParameters)
+        ));
+
+        // Test overwriting (should be allowed)
+        assert!(builder.param_config(ParameterEnum::NoneParameter).is_ok());
+        assert!(matches!(
+            builder.param_config,
+            Some(ParameterEnum::NoneParameter)
+        ));
+    }
+
+    #[test]
+    fn test_output_type() {
+        let mut builder = ConfigBuilder::default();
+
+        // Test setting output type
+        assert!(builder.output_type(OutputType::Debug).is_ok());
+        assert!(matches!(builder.output_type, Some(OutputType::Debug)));
+
+        // Test overwriting (should be allowed)
+        assert!(builder.output_type(OutputType::Info).is_ok());
+        assert!(matches!(builder.output_type, Some(OutputType::Info)));
+    }
+
+    #[test]
+    fn test_output_position() {
+        let mut builder = ConfigBuilder::default();
+
+        // Test setting output position
+        assert!(builder.output_position(OutputPosition::OnStart).is_ok());
+        assert!(matches!(
+            builder.output_position,
+            Some(OutputPosition::OnStart)
+        ));
+
+        // Test overwriting (should be allowed)
+        assert!(builder.output_position(OutputPosition::OnEnd).is_ok());
+        assert!(matches!(
+            builder.output_position,
+            Some(OutputPosition::OnEnd)
+        ));
+    }
+
+    #[test]
+    fn test_stats_option() {
+        let build = |meta_list: Punctuated<Meta, Comma>| {
+            let func: ItemFn = parse_quote! { fn test_func(x: i32) -> i32 { x } };
+            ConfigBuilder::from(meta_list, GenericsFn::from(func)).and_then(ConfigBuilder::build)
+        };
+        assert!(!build(parse_quote! { debug }).unwrap().stats);
+        assert!(build(parse_quote! { debug, stats }).unwrap().stats);
+        assert!(build(parse_quote! { stats = true }).unwrap().stats);
+        assert!(!build(parse_quote! { stats = false }).unwrap().stats);
+        assert!(matches!(
+            build(parse_quote! { stats, stats = false }),
+            Err(ConfigError::AlreadySet("statistics configuration", _))
+        ));
+        assert!(matches!(
+            build(parse_quote! { stats = "yes" }),
+            Err(ConfigError::InvalidParameterSyntax { .. })
+        ));
+    }
+
+    #[test]
+    fn test_record_option() {
+        let build = |meta_list: Punctuated<Meta, Comma>| {
+            let func: ItemFn = parse_quote! { fn test_func(x: i32) -> i32 { x } };
+            ConfigBuilder::from(meta_list, GenericsFn::from(func)).and_then(ConfigBuilder::build)
+        };
+        assert!(!build(parse_quote! { debug }).unwrap().record);
+        assert!(build(parse_quote! { debug, record }).unwrap().record);
+        assert!(!build(parse_quote! { record = false }).unwrap().record);
+        assert!(matches!(
+            build(parse_quote! { record, record = true }),
+            Err(ConfigError::AlreadySet("record configuration", _))
+        ));
+    }
+
+    #[test]
+    fn test_output_ret_value() {
+        let mut builder = ConfigBuilder::default();
+
+        // Test setting return value config
+        assert!(builder.output_ret_value(true).is_ok());
+        assert_eq!(builder.output_ret_value, Some(true));
+
+        // Test setting again should fail
+        let result = builder.output_ret_value(false);
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), ConfigError::AlreadySet(..)));
+    }
+
+    #[test]
+    fn test_build_success() {
+        let mut builder = ConfigBuilder::default();
+        let func = create_test_function();
+        builder.set_function_fields(func);
+
+        let config = builder.build();
+        assert!(config.is_ok());
+
+        let config = config.unwrap();
+        assert_eq!(config.func_name.to_string(), "test_func");
+        assert_eq!(config.func_params_for_invoke.len(), 2);
+    }
+
+    #[test]
+    fn test_build_missing_function() {
+        let builder = ConfigBuilder::default();
+
+        let result = builder.build();
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), ConfigError::MissingFunction));
+    }
+
+    #[test]
+    fn test_from_success() {
+        let func = create_test_function();
+        let meta_list: Punctuated<Meta, Comma> = Punctuated::new();
+
+        let result = ConfigBuilder::from(meta_list, func);
+        assert!(result.is_ok());
+
+        let builder = result.unwrap();
+        assert!(builder.func_name.is_some());
+        assert_eq!(builder.func_params_for_invoke.len(), 2);
+    }
+
+    #[test]
+    fn test_set_function_fields() {
+        let mut builder = ConfigBuilder::default();
+        let func = create_test_function();
+
+        builder.set_function_fields(func);
+
+        assert!(builder.func_name.is_some());
+        assert_eq!(builder.func_name.as_ref().unwrap().to_string(), "test_func");
+        assert_eq!(builder.func_params_for_invoke.len(), 2);
+        assert_eq!(builder.func_params_for_invoke[0].to_string(), "x");
+        assert_eq!(builder.func_params_for_invoke[1].to_string(), "y");
+    }
+
+    #[test]
+    fn test_set_parameters() {
+        let mut builder = ConfigBuilder::default();
+        let func: ItemFn = parse_quote! {
+            fn test(a: i32, b: String, c: bool) {}
+        };
+
+        builder.set_parameters(&func.sig.inputs);
+
+        assert_eq!(builder.func_params_for_invoke.len(), 3);
+        assert_eq!(builder.func_params_for_invoke[0].to_string(), "a");
+        assert_eq!(builder.func_params_for_invoke[1].to_string(), "b");
+        assert_eq!(builder.func_params_for_invoke[2].to_string(), "c");
+    }
+
+    #[test]
+    fn test_set_function_fields_qualifiers() {
+        let mut builder = ConfigBuilder::default();
+        let func: ItemFn = parse_quote! {
+            /// Docs
+            #[must_use]
+            pub async unsafe extern "C" fn test<T: Clone>(x: T) -> T where T: Copy { x }
+        };
+
+        builder.set_function_fields(GenericsFn::from(func));
+
+        assert_eq!(builder.func_attrs_outer.len(), 2);
+        assert!(builder.func_attrs_inner.is_empty());
+        assert!(builder.func_asyncness.is_some());
+        assert!(builder.func_unsafety.is_some());
+        assert!(builder.func_abi.is_some());
+        assert_eq!(builder.func_generics.params.len(), 1);
+        assert!(builder.func_generics.where_clause.is_some());
+    }
+
+    #[test]
+    fn test_build_uses_file_defaults() {
+        let file_config = FileConfig::parse(
+            r#"
+            [defaults]
+            level = "info"
+            position = "end"
+            ret = true
+            params = "none"
+            redact = ["y"]
+
+            [modules.db]
+            level = "trace"
+            "#,
+        )
+        .unwrap();
+
+        let mut builder = ConfigBuilder::from(Punctuated::new(), create_test_function()).unwrap();
+        builder.file_config(&file_config, Some("db")).unwrap();
+        let config = builder.build().unwrap();
+
+        assert!(matches!(config.start_output_type, OutputType::Trace));
+        assert!(matches!(config.end_output_type, OutputType::Trace));
+        assert!(matches!(config.output_position, OutputPosition::OnEnd));
+        assert!(config.output_ret_value);
+        assert!(config.func_params_for_output.is_empty());
+        assert_eq!(config.redact, vec!["y"]);
+    }
+
+    #[test]
+    fn test_attribute_overrides_file_defaults() {
+        let file_config = FileConfig::parse(
+            r#"
+            [defaults]
+            level = "info"
+            position = "end"
+            ret = true
+            params = "none"
+            "#,
+        )
+        .unwrap();
+
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { debug, onStart, ret = false, all };
+        let mut builder = ConfigBuilder::from(meta_list, create_test_function()).unwrap();
+        builder.file_config(&file_config, None).unwrap();
+        let config = builder.build().unwrap();
+
+        assert!(matches!(config.start_output_type, OutputType::Debug));
+        assert!(matches!(config.output_position, OutputPosition::OnStart));
+        assert!(!config.output_ret_value);
+        assert_eq!(config.func_params_for_output.len(), 2);
+
+        // Fields stand in for the parameters even when the file selects all of them
+        let file_config = FileConfig::parse("[defaults]\nparams = \"all\"").unwrap();
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { fields(n = x + 1) };
+        let mut builder = ConfigBuilder::from(meta_list, create_test_function()).unwrap();
+        builder.file_config(&file_config, None).unwrap();
+        assert!(builder.build().unwrap().func_params_for_output.is_empty());
+    }
+
+    fn preset_config() -> FileConfig {
+        FileConfig::parse(
+            r#"
+            [presets]
+            audit = "info, all, retVal"
+            quiet = "trace, none, onEnd"
+            broken = "info, debug, pamars(x)"
+            nested = "preset = audit"
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn build_with_presets(meta_list: Punctuated<Meta, Comma>) -> Result<Config, ConfigError> {
+        ConfigBuilder::from_with_file_config(
+            meta_list,
+            create_test_function(),
+            &preset_config(),
+            None,
+        )
+        .and_then(ConfigBuilder::build)
+    }
+
+    #[test]
+    fn test_preset_options_are_applied() {
+        let config = build_with_presets(parse_quote! { preset = audit }).unwrap();
+        assert!(matches!(config.start_output_type, OutputType::Info));
+        assert!(config.output_ret_value);
+        assert_eq!(config.func_params_for_output.len(), 2);
+
+        let config = build_with_presets(parse_quote! { preset = "quiet" }).unwrap();
+        assert!(matches!(config.end_output_type, OutputType::Trace));
+        assert!(matches!(config.output_position, OutputPosition::OnEnd));
+        assert!(config.func_params_for_output.is_empty());
+    }
+
+    #[test]
+    fn test_explicit_options_override_preset() {
+        let config = build_with_presets(
+            parse_quote! { preset = audit, level = "debug", params(x), ret = false },
+        )
+        .unwrap();
+        assert!(matches!(config.start_output_type, OutputType::Debug));
+        assert!(!config.output_ret_value);
+        assert_eq!(config.func_params_for_output.len(), 1);
+
+        // Options of the preset the attribute does not touch are kept
+        let config = build_with_presets(parse_quote! { debug, preset = quiet }).unwrap();
+        assert!(matches!(config.start_output_type, OutputType::Debug));
+        assert!(matches!(config.output_position, OutputPosition::OnEnd));
+
+        // Conflicts among the explicit options are still reported
+        assert!(matches!(
+            build_with_presets(parse_quote! { preset = audit, debug, warn }).unwrap_err(),
+            ConfigError::ConflictingOptions { .. }
+        ));
+    }
+
+    #[test]
+    fn test_preset_errors() {
+        match build_with_presets(parse_quote! { preset = audt }).unwrap_err() {
+            ConfigError::UnknownPreset {
+                preset, available, ..
+            } => {
+                assert_eq!(preset, "audt");
+                assert_eq!(available, vec!["audit", "broken", "nested", "quiet"]);
+            }
+            other => panic!("Expected UnknownPreset, got {other:?}"),
+        }
+
+        // Presets go through the same validation as the attribute
+        let errors = build_with_presets(parse_quote! { preset = broken })
+            .unwrap_err()
+            .into_errors();
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ConfigError::ConflictingOptions { .. })));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ConfigError::InvalidAttribute { .. })));
+
+        assert!(matches!(
+            build_with_presets(parse_quote! { preset = nested }).unwrap_err(),
+            ConfigError::InvalidParameterSyntax { .. }
+        ));
+        assert!(matches!(
+            build_with_presets(parse_quote! { preset = audit, preset = quiet }).unwrap_err(),
+            ConfigError::AlreadySet("preset", _)
+        ));
+    }
+
+    #[test]
+    fn test_build_const_fn() {
+        let mut builder = ConfigBuilder::default();
+        let func: ItemFn = parse_quote! {
+            const fn test(x: i32) -> i32 { x }
+        };
+        builder.set_function_fields(GenericsFn::from(func));
+
+        assert!(matches!(
+            builder.build().unwrap_err(),
+            ConfigError::UnsupportedQualifier { .. }
+        ));
+    }
+
+    #[test]
+    fn test_set_parameters_with_patterns() {
+        let mut builder = ConfigBuilder::default();
+        let func: ItemFn = parse_quote! {
+            fn test(mut a: i32, (b, c): (i32, i32), Point { x, .. }: Point, _: Marker) {}
+        };
+
+        builder.set_parameters(&func.sig.inputs);
+
+        let names = |idents: &[Ident]| idents.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            names(&builder.func_params_for_invoke),
+            vec![
+                "a",
+                "__funlog_arg_1__",
+                "__funlog_arg_2__",
+                "__funlog_arg_3__"
+            ]
+        );
+        assert_eq!(names(&builder.func_params_bound), vec!["a", "b", "c", "x"]);
+        let destructure = builder
+            .func_params_destructure
+            .iter()
+            .map(|(pat, place)| quote::quote!(let #pat = &#place;).to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            destructure,
+            [
+                "let b = & __funlog_arg_1__ . 0 ;",
+                "let c = & __funlog_arg_1__ . 1 ;",
+                "let x = & __funlog_arg_2__ . x ;"
+            ]
+        );
+        assert_eq!(builder.func_params_rebind.len(), 4);
+        assert_eq!(builder.func_params_rebind[0].1.to_string(), "a");
+        assert_eq!(
+            builder.func_params_rebind[3].1.to_string(),
+            "__funlog_arg_3__"
+        );
+
+        let wrapper_inputs = &builder.func_params_for_wrapper;
+        let expected: Punctuated<FnArg, Comma> = parse_quote! {
+            a: i32, __funlog_arg_1__: (i32, i32), __funlog_arg_2__: Point, __funlog_arg_3__: Marker
+        };
+        assert_eq!(*wrapper_inputs, expected);
+    }
+
+    #[test]
+    fn test_suggest_similar_attribute() {
+        let builder = ConfigBuilder::default();
+
+        // Test exact matches
+        assert_eq!(
+            builder.suggest_similar_attribute("debug"),
+            Some("debug".to_string())
+        );
+
+        // Test similar matches
+        assert_eq!(
+            builder.suggest_similar_attribute("debg"),
+            Some("debug".to_string())
+        );
+        assert_eq!(
+            builder.suggest_similar_attribute("infoo"),
+            Some("info".to_string())
+        );
+        assert_eq!(
+            builder.suggest_similar_attribute("al"),
+            Some("all".to_string())
+        );
+
+        // Test no matches
+        assert_eq!(builder.suggest_similar_attribute("xyz"), None);
+        assert_eq!(
+            builder.suggest_similar_attribute("completely_different"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_similar() {
+        let builder = ConfigBuilder::default();
+
+        // Test substring matching
+        assert!(builder.is_similar("debug", "debug"));
+        assert!(builder.is_similar("debug", "deb"));
+        assert!(builder.is_similar("info", "inf"));
+
+        // Test edit distance
+        assert!(builder.is_similar("debug", "debg"));
+        assert!(builder.is_similar("trace", "trac"));
+
+        // Test case insensitive
+        assert!(builder.is_similar("DEBUG", "debug"));
+        assert!(builder.is_similar("Info", "info"));
+
+        // Test not similar
+        assert!(!builder.is_similar("debug", "xyz"));
+        assert!(!builder.is_similar("completely", "different"));
+    }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        let builder = ConfigBuilder::default();
+
+        // Test identical strings
+        assert_eq!(builder.levenshtein_distance("hello", "hello"), 0);
+
+        // Test single character differences
+        assert_eq!(builder.levenshtein_distance("debug", "debg"), 1);
+        assert_eq!(builder.levenshtein_distance("info", "infoo"), 1);
+
+        // Test multiple character differences
+        assert_eq!(builder.levenshtein_distance("hello", "world"), 4);
+
+        // Test empty strings
+        assert_eq!(builder.levenshtein_distance("", "hello"), 5);
+        assert_eq!(builder.levenshtein_distance("hello", ""), 5);
+        assert_eq!(builder.levenshtein_distance("", ""), 0);
+    }
+
+    #[test]
+    fn test_parse_meta_list_conflicting_params() {
+        let mut builder = ConfigBuilder::default();
+        let func = create_test_function();
+        builder.set_function_fields(func);
+
+        // Create conflicting parameter configurations
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { all, none };
+
+        let result = builder.parse_meta_list(meta_list);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            ConfigError::ConflictingOptions { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_meta_list_conflicting_log_levels() {
+        let mut builder = ConfigBuilder::default();
+        let func = create_test_function();
+        builder.set_function_fields(func);
+
+        // Create conflicting log level configurations
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { debug, info };
+
+        let result = builder.parse_meta_list(meta_list);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            ConfigError::ConflictingOptions { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_meta_list_invalid_attribute() {
+        let mut builder = ConfigBuilder::default();
+        let func = create_test_function();
+        builder.set_function_fields(func);
+
+        // Create invalid attribute
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { invalid_attr };
+
+        let result = builder.parse_meta_list(meta_list);
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            ConfigError::InvalidAttribute { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_meta_list_guards() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+
+        let meta_list: Punctuated<Meta, Comma> =
+            parse_quote! { debug, when = "x > 10 && !y.is_empty()", when_ret = "*ret > 0" };
+
+        assert!(builder.parse_meta_list(meta_list).is_ok());
+        assert!(builder.when.is_some());
+        assert!(builder.when_ret.is_some());
+    }
+
+    #[test]
+    fn test_parse_meta_list_guard_unknown_identifier() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { when = "z > 10" };
+
+        match builder.parse_meta_list(meta_list).unwrap_err() {
+            ConfigError::UnknownIdentifier {
+                ident, available, ..
+            } => {
+                assert_eq!(ident, "z");
+                assert_eq!(available, vec!["x", "y"]);
+            }
+            other => panic!("Expected UnknownIdentifier error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_meta_list_when_ret_only_sees_ret() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { when_ret = "x > 0" };
+
+        assert!(matches!(
+            builder.parse_meta_list(meta_list).unwrap_err(),
+            ConfigError::UnknownIdentifier { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_meta_list_guard_errors() {
+        let func = create_test_function;
+
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(func());
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { when = 42 };
+        assert!(matches!(
+            builder.parse_meta_list(meta_list).unwrap_err(),
+            ConfigError::InvalidParameterSyntax { .. }
+        ));
+
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(func());
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { when = "x >" };
+        assert!(matches!(
+            builder.parse_meta_list(meta_list).unwrap_err(),
+            ConfigError::InvalidExpression { .. }
+        ));
+
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(func());
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { when = "x > 1", when = "x > 2" };
+        assert!(matches!(
+            builder.parse_meta_list(meta_list).unwrap_err(),
+            ConfigError::AlreadySet("when", _)
+        ));
+
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(func());
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { onStart, when_ret = "*ret > 0" };
+        assert!(matches!(
+            builder.parse_meta_list(meta_list).unwrap_err(),
+            ConfigError::ConflictingOptions { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_meta_list_reports_every_error() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! {
+            debg, debug, info, warn, params(nmae, x, z), retVal, retVal, when = "w > 1"
+        };
+        let errors = match builder.parse_meta_list(meta_list).unwrap_err() {
+            ConfigError::Multiple(errors) => errors,
+            other => panic!("Expected Multiple, got {other:?}"),
+        };
+
+        let kinds = errors
+            .iter()
+            .map(|error| match error {
+                ConfigError::InvalidAttribute { attr, .. } => format!("attribute {attr}"),
+                ConfigError::InvalidParameter { param, .. } => format!("parameter {param}"),
+                ConfigError::AlreadySet(option, _) => format!("duplicate {option}"),
+                ConfigError::UnknownIdentifier { ident, .. } => format!("identifier {ident}"),
+                ConfigError::ConflictingOptions {
+                    option1, option2, ..
+                } => format!("conflict {option1}/{option2}"),
+                other => panic!("Unexpected error {other:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                "attribute debg",
+                "parameter nmae",
+                "parameter z",
+                "duplicate return value configuration",
+                "identifier w",
+                "conflict debug/info",
+                "conflict debug/warn",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_meta_list_key_value() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+        let meta_list: Punctuated<Meta, Comma> =
+            parse_quote! { level = "info", position = end, ret = true };
+        assert!(builder.parse_meta_list(meta_list).is_ok());
+
+        let config = builder.build().unwrap();
+        assert!(matches!(config.start_output_type, OutputType::Info));
+        assert!(matches!(config.end_output_type, OutputType::Info));
+        assert!(matches!(config.output_position, OutputPosition::OnEnd));
+        assert!(config.output_ret_value);
+
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+        let meta_list: Punctuated<Meta, Comma> =
+            parse_quote! { trace, position = "start_end", end(level = warn) };
+        assert!(builder.parse_meta_list(meta_list).is_ok());
+        let config = builder.build().unwrap();
+        assert!(matches!(config.start_output_type, OutputType::Trace));
+        assert!(matches!(config.end_output_type, OutputType::Warn));
+        assert!(matches!(
+            config.output_position,
+            OutputPosition::OnStartAndEnd
+        ));
+    }
+
+    #[test]
+    fn test_parse_meta_list_key_value_errors() {
+        let parse = |meta_list: Punctuated<Meta, Comma>| {
+            let mut builder = ConfigBuilder::default();
+            builder.set_function_fields(create_test_function());
+            builder.parse_meta_list(meta_list).unwrap_err()
+        };
+
+        // Flags and their key-value forms share conflict detection
+        assert!(matches!(
+            parse(parse_quote! { debug, level = "info" }),
+            ConfigError::ConflictingOptions { ref option1, ref option2, .. }
+                if option1 == "debug" && option2 == "level"
+        ));
+        assert!(matches!(
+            parse(parse_quote! { onStart, position = "end" }),
+            ConfigError::ConflictingOptions { .. }
+        ));
+        assert!(matches!(
+            parse(parse_quote! { retVal, ret = false }),
+            ConfigError::AlreadySet(..)
+        ));
+
+        // Invalid values
+        assert!(matches!(
+            parse(parse_quote! { level = "verbose" }),
+            ConfigError::InvalidParameterSyntax { ref param, .. } if param == "level = verbose"
+        ));
+        assert!(matches!(
+            parse(parse_quote! { position = 3 }),
+            ConfigError::InvalidParameterSyntax { .. }
+        ));
+        assert!(matches!(
+            parse(parse_quote! { ret = "yes" }),
+            ConfigError::InvalidParameterSyntax { .. }
+        ));
+        assert!(matches!(
+            parse(parse_quote! { end(level = info), end(level = debug) }),
+            ConfigError::ConflictingOptions { .. }
+        ));
+        assert!(matches!(
+            parse(parse_quote! { start(colour = red) }),
+            ConfigError::InvalidAttribute { .. }
+        ));
+    }
+
+    #[test]
+    fn test_params_typo_suggests_real_parameter() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { params(yy, unrelated) };
+        let errors = builder
+            .parse_meta_list(meta_list)
+            .unwrap_err()
+            .into_errors();
+        let suggestions = errors
+            .iter()
+            .map(|error| error.suggestion().map(|s| s.replacement))
+            .collect::<Vec<_>>();
+        assert_eq!(suggestions, vec![Some("y".to_string()), None]);
+    }
+
+    #[test]
+    fn test_parse_meta_list_fields() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+
+        let meta_list: Punctuated<Meta, Comma> =
+            parse_quote! { debug, fields(doubled = x * 2, len = y.len()) };
+
+        assert!(builder.parse_meta_list(meta_list).is_ok());
+        assert_eq!(builder.fields.len(), 2);
+        assert_eq!(builder.fields[0].0.to_string(), "doubled");
+        assert_eq!(builder.fields[1].0.to_string(), "len");
+
+        // Fields replace the default of logging every parameter
+        let config = builder.build().unwrap();
+        assert!(config.func_params_for_output.is_empty());
+        assert_eq!(config.fields.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_meta_list_fields_with_params() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { all, fields(len = y.len()) };
+
+        assert!(builder.parse_meta_list(meta_list).is_ok());
+        let config = builder.build().unwrap();
+        assert_eq!(config.func_params_for_output.len(), 2);
+        assert_eq!(config.fields.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_meta_list_fields_errors() {
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { fields(len = z.len()) };
+        assert!(matches!(
+            builder.parse_meta_list(meta_list).unwrap_err(),
+            ConfigError::UnknownIdentifier { .. }
+        ));
+
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { fields(n = x, n = y.len()) };
+        assert!(matches!(
+            builder.parse_meta_list(meta_list).unwrap_err(),
+            ConfigError::InvalidParameterSyntax { .. }
+        ));
+
+        let mut builder = ConfigBuilder::default();
+        builder.set_function_fields(create_test_function());
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { fields(x) };
+        assert!(matches!(
+            builder.parse_meta_list(meta_list).unwrap_err(),
+            ConfigError::ParseError(..)
+        ));
+    }
+}
@@ -0,0 +1,770 @@
+use proc_macro2::Span;
+use std::fmt;
+
+/// Represents various configuration errors that can occur during macro processing.
+///
+/// This enum covers all possible error conditions that can arise when parsing
+/// and validating the funlog macro attributes and function information. Every
+/// variant except `MissingFunction` and `InvalidConfigFile` carries the span of
+/// the attribute token it refers to, so the compiler underlines the offending
+/// word rather than the whole macro invocation.
+///
+/// # Examples
+///
+/// ```ignore
+/// use funlog::error::ConfigError;
+///
+/// use proc_macro2::Span;
+///
+/// let error = ConfigError::AlreadySet("return value configuration", Span::call_site());
+/// println!("{}", error);
+/// ```
+#[derive(Debug, Clone)]
+pub enum ConfigError {
+    /// A configuration option has already been set
+    AlreadySet(&'static str, Span),
+    /// An invalid parameter was specified
+    InvalidParameter {
+        param: String,
+        available: Vec<String>,
+        suggestion: Option<String>,
+        span: Span,
+    },
+    /// An invalid attribute was used
+    InvalidAttribute {
+        attr: String,
+        suggestion: Option<String>,
+        span: Span,
+    },
+    /// A parsing error occurred
+    ParseError(String, Span),
+    /// Conflicting configuration options were specified
+    ConflictingOptions {
+        option1: String,
+        option2: String,
+        span: Span,
+    },
+    /// The macro was not applied to a function
+    MissingFunction,
+    /// Invalid parameter syntax was used
+    InvalidParameterSyntax {
+        param: String,
+        expected: String,
+        span: Span,
+    },
+    /// The function uses a qualifier that cannot be instrumented
+    UnsupportedQualifier {
+        qualifier: String,
+        reason: String,
+        span: Span,
+    },
+    /// An expression option (such as `when`) could not be parsed
+    InvalidExpression {
+        option: String,
+        message: String,
+        span: Span,
+    },
+    /// An expression option referenced an identifier that is not in scope
+    UnknownIdentifier {
+        ident: String,
+        option: String,
+        available: Vec<String>,
+        span: Span,
+    },
+    /// A `preset = name` option named a preset that is not defined in `funlog.toml`
+    UnknownPreset {
+        preset: String,
+        available: Vec<String>,
+        span: Span,
+    },
+    /// The `funlog.toml` configuration file could not be read or parsed
+    InvalidConfigFile { path: String, message: String },
+    /// Several problems were found in one attribute list
+    Multiple(Vec<ConfigError>),
+}
+
+/// A replacement for the token an error is spanned on, e.g. `debug` for `debg`.
+///
+/// The suggestion is kept out of the error message. It is emitted as a `help` on its
+/// span when the `nightly` feature is enabled, and as a separate `help:` error on the
+/// same span otherwise.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    /// The span of the token to replace
+    pub span: Span,
+    /// The text to replace the token with
+    pub replacement: String,
+}
+
+impl fmt::Display for ConfigError {
+    /// Formats the error with helpful messages and suggestions.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The formatter to write to
+    ///
+    /// # Returns
+    ///
+    /// Returns `fmt::Result` indicating success or failure of formatting
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::error::ConfigError;
+    ///
+    /// use proc_macro2::Span;
+    ///
+    /// let error = ConfigError::AlreadySet("test", Span::call_site());
+    /// let formatted = format!("{}", error);
+    /// assert!(formatted.contains("already been set"));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::AlreadySet(field, _) => {
+                writeln!(
+                    f,
+                    "funlog configuration error: '{field}' option has already been set"
+                )?;
+                write!(f, "💡 Hint: Each configuration option can only be set once, please check for duplicate configurations")
+            }
+            ConfigError::InvalidParameter {
+                param, available, ..
+            } => {
+                writeln!(
+                    f,
+                    "funlog parameter error: parameter '{param}' does not exist"
+                )?;
+                if available.is_empty() {
+                    write!(f, "💡 Hint: This function has no parameters, please use 'none' or remove the params() configuration")
+                } else {
+                    writeln!(
+                        f,
+                        "💡 Hint: Available parameters are: {}",
+                        available.join(", ")
+                    )?;
+                    write!(
+                        f,
+                        "   Correct usage: #[funlog(params({}))]",
+                        available.join(", ")
+                    )
+                }
+            }
+            ConfigError::InvalidAttribute { attr, .. } => {
+                writeln!(
+                    f,
+                    "funlog configuration error: unknown configuration option '{attr}'"
+                )?;
+                writeln!(f, "📖 Available configuration options:")?;
+                writeln!(f, "   Log levels: print, trace, debug, info, warn, error")?;
+                writeln!(
+                    f,
+                    "   Parameter control: all, none, params(parameter_names...)"
+                )?;
+                writeln!(f, "   Position control: onStart, onEnd, onStartEnd")?;
+                writeln!(f, "   Return value: retVal")?;
+                writeln!(f, "   Call statistics: stats")?;
+                writeln!(f, "   Record and replay: record")?;
+                write!(
+                    f,
+                    "   Key-value: level = \"debug\", position = \"start\", ret = true, stats = true, record = true, start(level = ...), end(level = ...), preset = name"
+                )
+            }
+            ConfigError::ParseError(msg, _) => {
+                writeln!(f, "funlog parse error: {msg}")?;
+                write!(f, "💡 Hint: Please check if the macro syntax is correct, example: #[funlog(debug, all)]")
+            }
+            ConfigError::ConflictingOptions {
+                option1, option2, ..
+            } => {
+                writeln!(f, "funlog configuration conflict: '{option1}' and '{option2}' cannot be used together")?;
+                write!(f, "💡 Hint: Please choose one of the options")
+            }
+            ConfigError::MissingFunction => {
+                writeln!(f, "funlog error: can only be used on functions")?;
+                write!(f, "💡 Hint: funlog macro can only be applied to function definitions, not other items")
+            }
+            ConfigError::InvalidParameterSyntax {
+                param, expected, ..
+            } => {
+                writeln!(
+                    f,
+                    "funlog parameter syntax error: '{param}' format is incorrect"
+                )?;
+                write!(f, "💡 Hint: Expected format is {expected}")
+            }
+            ConfigError::UnsupportedQualifier {
+                qualifier, reason, ..
+            } => {
+                writeln!(
+                    f,
+                    "funlog error: '{qualifier}' functions cannot be instrumented"
+                )?;
+                write!(f, "💡 Hint: {reason}")
+            }
+            ConfigError::InvalidExpression {
+                option, message, ..
+            } => {
+                writeln!(
+                    f,
+                    "funlog expression error: '{option}' is not a valid expression: {message}"
+                )?;
+                write!(
+                    f,
+                    "💡 Hint: Expected format is {option} = \"<rust expression>\""
+                )
+            }
+            ConfigError::UnknownIdentifier {
+                ident,
+                option,
+                available,
+                ..
+            } => {
+                writeln!(
+                    f,
+                    "funlog expression error: unknown identifier '{ident}' in '{option}'"
+                )?;
+                if available.is_empty() {
+                    write!(
+                        f,
+                        "💡 Hint: No identifiers are available in this expression"
+                    )
+                } else {
+                    write!(
+                        f,
+                        "💡 Hint: Available identifiers are: {}",
+                        available.join(", ")
+                    )
+                }
+            }
+            ConfigError::UnknownPreset {
+                preset, available, ..
+            } => {
+                writeln!(f, "funlog preset error: preset '{preset}' is not defined")?;
+                if available.is_empty() {
+                    write!(
+                        f,
+                        "💡 Hint: Define presets in funlog.toml, e.g. [presets] {preset} = \"info, all, retVal\""
+                    )
+                } else {
+                    write!(
+                        f,
+                        "💡 Hint: Available presets are: {}",
+                        available.join(", ")
+                    )
+                }
+            }
+            ConfigError::InvalidConfigFile { path, message } => {
+                writeln!(
+                    f,
+                    "funlog configuration file error: '{path}' is invalid: {message}"
+                )?;
+                write!(f, "💡 Hint: Fix or remove the file; see the funlog documentation for the supported keys")
+            }
+            ConfigError::Multiple(errors) => {
+                for (index, error) in errors.iter().enumerate() {
+                    if index > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ConfigError {
+    /// Returns the span of the attribute token the error refers to.
+    ///
+    /// # Returns
+    ///
+    /// Returns the stored span, or `Span::call_site()` for errors that are not tied
+    /// to a single token
+    pub fn span(&self) -> Span {
+        match self {
+            ConfigError::AlreadySet(_, span)
+            | ConfigError::ParseError(_, span)
+            | ConfigError::InvalidParameter { span, .. }
+            | ConfigError::InvalidAttribute { span, .. }
+            | ConfigError::ConflictingOptions { span, .. }
+            | ConfigError::InvalidParameterSyntax { span, .. }
+            | ConfigError::UnsupportedQualifier { span, .. }
+            | ConfigError::InvalidExpression { span, .. }
+            | ConfigError::UnknownIdentifier { span, .. }
+            | ConfigError::UnknownPreset { span, .. } => *span,
+            ConfigError::Multiple(errors) => errors
+                .first()
+                .map_or_else(Span::call_site, ConfigError::span),
+            ConfigError::MissingFunction | ConfigError::InvalidConfigFile { .. } => {
+                Span::call_site()
+            }
+        }
+    }
+
+    /// Replaces the span of the error.
+    ///
+    /// Builder setters do not know where an option was written; the attribute parser
+    /// uses this to point their errors at the option's token.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_span` - The span of the offending token
+    ///
+    /// # Returns
+    ///
+    /// Returns the same error spanned on `new_span`
+    pub fn with_span(mut self, new_span: Span) -> Self {
+        match &mut self {
+            ConfigError::AlreadySet(_, span)
+            | ConfigError::ParseError(_, span)
+            | ConfigError::InvalidParameter { span, .. }
+            | ConfigError::InvalidAttribute { span, .. }
+            | ConfigError::ConflictingOptions { span, .. }
+            | ConfigError::InvalidParameterSyntax { span, .. }
+            | ConfigError::UnsupportedQualifier { span, .. }
+            | ConfigError::InvalidExpression { span, .. }
+            | ConfigError::UnknownIdentifier { span, .. }
+            | ConfigError::UnknownPreset { span, .. } => *span = new_span,
+            ConfigError::Multiple(_)
+            | ConfigError::MissingFunction
+            | ConfigError::InvalidConfigFile { .. } => {}
+        }
+        self
+    }
+
+    /// Returns the machine-readable fix for the error, if one is known.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(Suggestion)` for misspelled options and parameter names that
+    /// closely match a valid one, and `None` otherwise
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        match self {
+            ConfigError::InvalidAttribute {
+                suggestion: Some(replacement),
+                span,
+                ..
+            }
+            | ConfigError::InvalidParameter {
+                suggestion: Some(replacement),
+                span,
+                ..
+            } => Some(Suggestion {
+                span: *span,
+                replacement: replacement.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Flattens the error into the individual problems it reports.
+    ///
+    /// # Returns
+    ///
+    /// Returns the nested errors of `ConfigError::Multiple`, or the error itself
+    pub fn into_errors(self) -> Vec<ConfigError> {
+        match self {
+            ConfigError::Multiple(errors) => errors
+                .into_iter()
+                .flat_map(ConfigError::into_errors)
+                .collect(),
+            error => vec![error],
+        }
+    }
+
+    /// Converts the error into tokens that report it when the macro expands.
+    ///
+    /// This is a `compile_error!` per problem, spanned on the offending token, whose
+    /// message ends with a `help:` line for its suggestion, if any.
+    ///
+    /// # Returns
+    ///
+    /// Returns the tokens to place before the uninstrumented function
+    #[cfg(not(feature = "nightly"))]
+    pub fn into_compile_error(self) -> proc_macro2::TokenStream {
+        syn::Error::from(self).into_compile_error()
+    }
+
+    /// Reports the error when the macro expands.
+    ///
+    /// The problems are emitted directly as diagnostics, with suggestions attached as
+    /// structured `help` on the token to replace.
+    ///
+    /// # Returns
+    ///
+    /// Returns no tokens, since the diagnostics are already emitted
+    #[cfg(feature = "nightly")]
+    pub fn into_compile_error(self) -> proc_macro2::TokenStream {
+        use proc_macro::{Diagnostic, Level};
+
+        for error in self.into_errors() {
+            let mut diagnostic =
+                Diagnostic::spanned(error.span().unwrap(), Level::Error, error.to_string());
+            if let Some(Suggestion { span, replacement }) = error.suggestion() {
+                diagnostic =
+                    diagnostic.span_help(span.unwrap(), format!("replace with `{replacement}`"));
+            }
+            diagnostic.emit();
+        }
+        proc_macro2::TokenStream::new()
+    }
+
+    /// Folds a list of errors into a single result.
+    ///
+    /// # Arguments
+    ///
+    /// * `errors` - The errors found, in source order
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the list is empty, the error itself if there is exactly one,
+    /// and a flat `ConfigError::Multiple` otherwise
+    pub fn from_many(errors: Vec<ConfigError>) -> Result<(), ConfigError> {
+        let mut errors = errors
+            .into_iter()
+            .flat_map(ConfigError::into_errors)
+            .collect::<Vec<_>>();
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(ConfigError::Multiple(errors)),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<ConfigError> for syn::Error {
+    /// Converts a ConfigError into a syn::Error for use in procedural macros.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The ConfigError to convert
+    ///
+    /// # Returns
+    ///
+    /// Returns a syn::Error with the error message, spanned on the offending
+    /// attribute token and ending with a `help:` line for its suggestion.
+    /// `ConfigError::Multiple` becomes one combined error per problem
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::error::ConfigError;
+    /// use syn::Error;
+    ///
+    /// let config_error = ConfigError::MissingFunction;
+    /// let syn_error: Error = config_error.into();
+    /// ```
+    fn from(err: ConfigError) -> Self {
+        err.into_errors()
+            .into_iter()
+            .map(|error| {
+                let message = match error.suggestion() {
+                    Some(Suggestion { replacement, .. }) => {
+                        format!("{error}\nhelp: replace with `{replacement}`")
+                    }
+                    None => error.to_string(),
+                };
+                syn::Error::new(error.span(), message)
+            })
+            .reduce(|mut combined, error| {
+                combined.combine(error);
+                combined
+            })
+            .unwrap_or_else(|| syn::Error::new(Span::call_site(), "funlog error"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_already_set_error() {
+        let error = ConfigError::AlreadySet("test field", Span::call_site());
+        let message = format!("{error}");
+        assert!(message.contains("test field"));
+        assert!(message.contains("already been set"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_invalid_parameter_error_with_available() {
+        let error = ConfigError::InvalidParameter {
+            param: "invalid_param".to_string(),
+            available: vec!["x".to_string(), "y".to_string()],
+            suggestion: None,
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("invalid_param"));
+        assert!(message.contains("x, y"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_invalid_parameter_error_no_available() {
+        let error = ConfigError::InvalidParameter {
+            param: "invalid_param".to_string(),
+            available: vec![],
+            suggestion: None,
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("invalid_param"));
+        assert!(message.contains("no parameters"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_invalid_attribute_error_with_suggestion() {
+        let error = ConfigError::InvalidAttribute {
+            attr: "debg".to_string(),
+            suggestion: Some("debug".to_string()),
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("debg"));
+        assert!(!message.contains("Did you mean"));
+        assert!(message.contains("📖 Available"));
+        assert_eq!(error.suggestion().unwrap().replacement, "debug");
+    }
+
+    #[test]
+    fn test_invalid_attribute_error_no_suggestion() {
+        let error = ConfigError::InvalidAttribute {
+            attr: "unknown".to_string(),
+            suggestion: None,
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("unknown"));
+        assert!(message.contains("📖 Available"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = ConfigError::ParseError("test parse error".to_string(), Span::call_site());
+        let message = format!("{error}");
+        assert!(message.contains("test parse error"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_conflicting_options_error() {
+        let error = ConfigError::ConflictingOptions {
+            option1: "debug".to_string(),
+            option2: "info".to_string(),
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("debug"));
+        assert!(message.contains("info"));
+        assert!(message.contains("cannot be used together"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_missing_function_error() {
+        let error = ConfigError::MissingFunction;
+        let message = format!("{error}");
+        assert!(message.contains("can only be used on functions"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_invalid_parameter_syntax_error() {
+        let error = ConfigError::InvalidParameterSyntax {
+            param: "bad_syntax".to_string(),
+            expected: "param(name)".to_string(),
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("bad_syntax"));
+        assert!(message.contains("param(name)"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_unsupported_qualifier_error() {
+        let error = ConfigError::UnsupportedQualifier {
+            qualifier: "const fn".to_string(),
+            reason: "logging is not available in const contexts".to_string(),
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("'const fn' functions cannot be instrumented"));
+        assert!(message.contains("💡 Hint: logging is not available"));
+    }
+
+    #[test]
+    fn test_unknown_preset_error() {
+        let error = ConfigError::UnknownPreset {
+            preset: "audt".to_string(),
+            available: vec!["audit".to_string(), "hot_path".to_string()],
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("preset 'audt' is not defined"));
+        assert!(message.contains("Available presets are: audit, hot_path"));
+
+        let error = ConfigError::UnknownPreset {
+            preset: "audit".to_string(),
+            available: vec![],
+            span: Span::call_site(),
+        };
+        assert!(format!("{error}").contains("[presets] audit = "));
+    }
+
+    #[test]
+    fn test_invalid_config_file_error() {
+        let error = ConfigError::InvalidConfigFile {
+            path: "funlog.toml".to_string(),
+            message: "unknown field `levl`".to_string(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("'funlog.toml' is invalid: unknown field `levl`"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_invalid_expression_error() {
+        let error = ConfigError::InvalidExpression {
+            option: "when".to_string(),
+            message: "expected an expression".to_string(),
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("'when' is not a valid expression"));
+        assert!(message.contains("expected an expression"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_unknown_identifier_error() {
+        let error = ConfigError::UnknownIdentifier {
+            ident: "amout".to_string(),
+            option: "when".to_string(),
+            available: vec!["amount".to_string(), "user".to_string()],
+            span: Span::call_site(),
+        };
+        let message = format!("{error}");
+        assert!(message.contains("unknown identifier 'amout' in 'when'"));
+        assert!(message.contains("amount, user"));
+        assert!(message.contains("💡 Hint"));
+    }
+
+    #[test]
+    fn test_error_trait_implementation() {
+        let error = ConfigError::MissingFunction;
+        let _: &dyn std::error::Error = &error;
+        // This test ensures that ConfigError implements std::error::Error
+    }
+
+    #[test]
+    fn test_from_config_error_to_syn_error() {
+        let config_error = ConfigError::MissingFunction;
+        let syn_error: syn::Error = config_error.into();
+        let message = syn_error.to_string();
+        assert!(message.contains("can only be used on functions"));
+    }
+
+    #[test]
+    fn test_clone_and_debug() {
+        let error = ConfigError::AlreadySet("test", Span::call_site());
+        let cloned = error.clone();
+
+        // Test Debug implementation
+        let debug_str = format!("{error:?}");
+        assert!(debug_str.contains("AlreadySet"));
+
+        // Test that clone works
+        assert!(matches!(cloned, ConfigError::AlreadySet("test", _)));
+    }
+
+    #[test]
+    fn test_span_and_with_span() {
+        let error =
+            ConfigError::AlreadySet("when", Span::call_site()).with_span(Span::mixed_site());
+        assert!(matches!(error, ConfigError::AlreadySet("when", _)));
+        let _ = error.span();
+
+        // Errors without a token keep pointing at the call site
+        let error = ConfigError::MissingFunction.with_span(Span::mixed_site());
+        assert!(matches!(error, ConfigError::MissingFunction));
+    }
+
+    #[test]
+    fn test_from_many() {
+        assert!(ConfigError::from_many(vec![]).is_ok());
+
+        let single = ConfigError::from_many(vec![ConfigError::MissingFunction]);
+        assert!(matches!(single, Err(ConfigError::MissingFunction)));
+
+        let multiple = ConfigError::from_many(vec![
+            ConfigError::AlreadySet("when", Span::call_site()),
+            ConfigError::MissingFunction,
+        ]);
+        let Err(ConfigError::Multiple(errors)) = multiple else {
+            panic!("expected several errors");
+        };
+        assert!(matches!(
+            &errors[..],
+            [
+                ConfigError::AlreadySet("when", _),
+                ConfigError::MissingFunction
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_multiple_errors_are_combined() {
+        let error = ConfigError::Multiple(vec![
+            ConfigError::AlreadySet("when", Span::call_site()),
+            ConfigError::MissingFunction,
+        ]);
+        let when = "funlog configuration error: 'when' option has already been set\n\
+                    💡 Hint: Each configuration option can only be set once, please check \
+                    for duplicate configurations";
+        let function = "funlog error: can only be used on functions\n\
+                        💡 Hint: funlog macro can only be applied to function definitions, \
+                        not other items";
+        assert_eq!(error.to_string(), format!("{when}\n{function}"));
+
+        let syn_error: syn::Error = error.into();
+        let messages = syn_error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, [when, function]);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let error = ConfigError::InvalidParameter {
+            param: "nmae".to_string(),
+            available: vec!["name".to_string(), "age".to_string()],
+            suggestion: Some("name".to_string()),
+            span: Span::call_site(),
+        };
+        assert!(!error.to_string().contains("Did you mean"));
+        assert_eq!(error.suggestion().unwrap().replacement, "name");
+        let messages = syn::Error::from(error)
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("'nmae'"));
+        assert!(messages[0].ends_with("\nhelp: replace with `name`"));
+
+        let error = ConfigError::InvalidAttribute {
+            attr: "debg".to_string(),
+            suggestion: Some("debug".to_string()),
+            span: Span::call_site(),
+        };
+        assert_eq!(error.suggestion().unwrap().replacement, "debug");
+
+        assert!(ConfigError::MissingFunction.suggestion().is_none());
+    }
+}
@@ -0,0 +1,268 @@
+//! Expansion snapshots: each case expands `#[funlog(...)]` on a function and compares
+//! the pretty-printed result with `tests/snapshots/expand/<case>.snap`. Run the tests
+//! with `FUNLOG_UPDATE_SNAPSHOTS=1` to accept changes to the generated code.
+
+use crate::error::ConfigError;
+use crate::expand;
+use crate::file_config::FileConfig;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Expands `#[funlog(args)]` on `item` and checks it against the case's snapshot.
+fn check(case: &str, args: TokenStream, item: TokenStream) {
+    check_with(case, args, item, Ok(Rc::default()), None);
+}
+
+fn check_with(
+    case: &str,
+    args: TokenStream,
+    item: TokenStream,
+    file_config: Result<Rc<FileConfig>, ConfigError>,
+    module: Option<&str>,
+) {
+    let expanded = expand(args.clone(), item, file_config, module);
+    let file = syn::parse2::<syn::File>(expanded)
+        .unwrap_or_else(|e| panic!("expansion of {case} is not valid Rust: {e}"));
+    let snapshot = format!("// #[funlog({args})]\n{}", prettyplease::unparse(&file));
+    funlog_runtime::assert_snapshot(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/snapshots/expand")
+            .join(format!("{case}.snap")),
+        &snapshot,
+    );
+}
+
+fn file_config(contents: &str) -> Result<Rc<FileConfig>, ConfigError> {
+    Ok(Rc::new(FileConfig::parse(contents).unwrap()))
+}
+
+#[test]
+fn test_levels() {
+    for (case, args) in [
+        ("level_default", quote! {}),
+        ("level_print", quote! { print }),
+        ("level_trace", quote! { trace }),
+        ("level_debug", quote! { debug }),
+        ("level_info", quote! { info }),
+        ("level_warn", quote! { warn }),
+        ("level_error", quote! { error }),
+        ("level_key_value", quote! { level = "info" }),
+        (
+            "level_per_phase",
+            quote! { start(level = trace), end(level = debug) },
+        ),
+    ] {
+        check(
+            case,
+            args,
+            quote! { fn add(x: i32, y: i32) -> i32 { x + y } },
+        );
+    }
+}
+
+#[test]
+fn test_params() {
+    for (case, args) in [
+        ("params_all", quote! { debug, all }),
+        ("params_none", quote! { debug, none }),
+        ("params_specific", quote! { debug, params(y) }),
+        ("params_fields", quote! { debug, fields(n = items.len()) }),
+        (
+            "params_all_fields",
+            quote! { debug, all, fields(n = items.len()) },
+        ),
+    ] {
+        check(
+            case,
+            args,
+            quote! { fn sum(items: Vec<i64>, y: i64) -> i64 { items.iter().sum::<i64>() + y } },
+        );
+    }
+}
+
+#[test]
+fn test_positions_and_return_value() {
+    for (case, args) in [
+        ("position_start", quote! { debug, onStart }),
+        ("position_end", quote! { debug, onEnd }),
+        ("position_start_end", quote! { debug, onStartEnd }),
+        ("position_key_value", quote! { debug, position = "end" }),
+        ("ret_value", quote! { debug, retVal }),
+        ("ret_value_end", quote! { debug, onEnd, retVal }),
+        ("ret_value_start", quote! { debug, onStart, retVal }),
+        ("ret_key_value", quote! { debug, ret = true }),
+    ] {
+        check(case, args, quote! { fn square(x: u32) -> u32 { x * x } });
+    }
+    check(
+        "ret_value_void",
+        quote! { debug, retVal },
+        quote! { fn reset(x: &mut u32) { *x = 0; } },
+    );
+}
+
+#[test]
+fn test_guards() {
+    check(
+        "when",
+        quote! { debug, when = "amount > 100" },
+        quote! { fn pay(amount: u64) -> u64 { amount } },
+    );
+    check(
+        "when_ret",
+        quote! { debug, retVal, when_ret = "ret.is_err()" },
+        quote! { fn parse(text: &str) -> Result<u8, std::num::ParseIntError> { text.parse() } },
+    );
+    check(
+        "when_and_when_ret",
+        quote! { debug, when = "amount > 100", when_ret = "*ret == 0" },
+        quote! { fn pay(amount: u64) -> u64 { amount } },
+    );
+}
+
+#[test]
+fn test_stats_and_record() {
+    check(
+        "stats",
+        quote! { debug, stats },
+        quote! { fn parse(text: &str) -> Result<u8, std::num::ParseIntError> { text.parse() } },
+    );
+    check(
+        "record",
+        quote! { debug, record },
+        quote! { fn add(x: i32, y: i32) -> i32 { x + y } },
+    );
+    check(
+        "record_without_args",
+        quote! { debug, record, stats },
+        quote! { fn now() -> u64 { 7 } },
+    );
+}
+
+#[test]
+fn test_function_shapes() {
+    check(
+        "async_fn",
+        quote! { debug, retVal },
+        quote! { async fn fetch(id: u32) -> Option<u32> { Some(id) } },
+    );
+    check(
+        "generic_fn",
+        quote! { debug },
+        quote! { pub fn largest<T: PartialOrd + Copy + std::fmt::Debug>(a: T, b: T) -> T where T: Default { if a > b { a } else { b } } },
+    );
+    check(
+        "method_self",
+        quote! { debug },
+        quote! { pub fn get(&self, key: &str) -> Option<&u32> { self.map.get(key) } },
+    );
+    check(
+        "patterns",
+        quote! { debug },
+        quote! { fn dist((x, y): (f64, f64), mut scale: f64, _: u8) -> f64 { scale *= 2.0; (x * x + y * y).sqrt() * scale } },
+    );
+    check(
+        "unsafe_extern",
+        quote! { debug },
+        quote! { pub unsafe extern "C" fn read(ptr: *const u8) -> u8 { *ptr } },
+    );
+    check(
+        "track_caller",
+        quote! { debug },
+        quote! {
+            /// Checks `x`.
+            #[track_caller]
+            #[must_use]
+            fn check(x: u8) -> bool { x > 0 }
+        },
+    );
+    // Methods cannot move their body into a nested item, so it runs in the wrapper
+    check(
+        "track_caller_method",
+        quote! { debug, retVal },
+        quote! {
+            #[track_caller]
+            fn step(&self, input: &str) -> Result<u32, String> {
+                let n = input.parse::<u32>().map_err(|e| e.to_string())?;
+                if n == 0 {
+                    return Ok(self.step);
+                }
+                Ok(n * self.step)
+            }
+        },
+    );
+    // The attribute has no effect on async functions and stays on the wrapper
+    check(
+        "track_caller_async",
+        quote! { debug },
+        quote! {
+            #[track_caller]
+            async fn fetch(id: u32) -> u32 { id }
+        },
+    );
+    check(
+        "early_return",
+        quote! { debug, retVal },
+        quote! { fn first(items: &[u8]) -> Option<u8> { if items.is_empty() { return None; } let x = items.first()?; Some(*x) } },
+    );
+}
+
+#[test]
+fn test_file_config() {
+    let config = file_config(
+        r#"
+        [defaults]
+        level = "info"
+        ret = true
+
+        [format]
+        pretty = true
+        max_len = 40
+
+        [modules."db"]
+        level = "trace"
+        redact = ["password"]
+
+        [presets]
+        audit = "warn, all, retVal, onEnd"
+        "#,
+    );
+    let item = quote! { fn login(user: &str, password: &str) -> bool { user != password } };
+    check_with(
+        "file_defaults",
+        quote! {},
+        item.clone(),
+        config.clone(),
+        None,
+    );
+    check_with(
+        "file_module_override",
+        quote! {},
+        item.clone(),
+        config.clone(),
+        Some("db::users"),
+    );
+    check_with("file_preset", quote! { preset = audit }, item, config, None);
+}
+
+#[test]
+fn test_errors() {
+    // Errors keep the function uninstrumented after the compile errors
+    check(
+        "error_multiple",
+        quote! { debgu, info, params(z) },
+        quote! { fn add(x: i32, y: i32) -> i32 { x + y } },
+    );
+    check_with(
+        "error_config_file",
+        quote! { debug },
+        quote! { fn add(x: i32, y: i32) -> i32 { x + y } },
+        Err(ConfigError::InvalidConfigFile {
+            path: "funlog.toml".to_string(),
+            message: "expected `=`".to_string(),
+        }),
+        None,
+    );
+}
@@ -0,0 +1,579 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote_spanned;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{Block, Expr, ExprCall, ExprClosure, ExprMacro, ExprPath, Ident, Item, Lifetime};
+use syn::{ExprBreak, FieldValue, Pat, PatIdent, Token, Type, TypeImplTrait};
+
+/// Collects the local identifiers referenced by a user supplied expression.
+///
+/// Guard expressions such as `when = "amount > 10_000"` are evaluated inside the
+/// generated wrapper, where only the function parameters (or the return value) are
+/// in scope. This visitor finds every single-segment path that looks like a local
+/// variable so that unknown names can be reported before code generation.
+///
+/// Paths used as the callee of a call (`is_valid(x)`), paths that look like
+/// constants or types (`MAX`, `Some`), identifiers bound by closures inside the
+/// expression and the contents of macro invocations are ignored.
+struct IdentCollector {
+    bound: Vec<Ident>,
+    found: Vec<Ident>,
+}
+
+impl<'ast> Visit<'ast> for IdentCollector {
+    fn visit_expr_path(&mut self, expr: &'ast ExprPath) {
+        if expr.qself.is_some() || expr.path.leading_colon.is_some() {
+            return;
+        }
+        if let Some(ident) = expr.path.get_ident() {
+            let name = ident.to_string();
+            let looks_local = name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_lowercase() || c == '_');
+            if looks_local && name != "self" && !self.bound.contains(ident) {
+                self.found.push(ident.clone());
+            }
+        }
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if !matches!(call.func.as_ref(), Expr::Path(_)) {
+            self.visit_expr(&call.func);
+        }
+        for arg in &call.args {
+            self.visit_expr(arg);
+        }
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast ExprClosure) {
+        let depth = self.bound.len();
+        for input in &closure.inputs {
+            collect_pat_idents(input, &mut self.bound);
+        }
+        self.visit_expr(&closure.body);
+        self.bound.truncate(depth);
+    }
+
+    fn visit_expr_macro(&mut self, _: &'ast ExprMacro) {}
+}
+
+/// Collects every identifier bound by a pattern, e.g. `a` and `b` in `(a, mut b)`.
+///
+/// # Arguments
+///
+/// * `pat` - The pattern to inspect
+/// * `out` - The vector the bound identifiers are appended to
+pub fn collect_pat_idents(pat: &Pat, out: &mut Vec<Ident>) {
+    struct PatIdents<'a>(&'a mut Vec<Ident>);
+
+    impl<'ast> Visit<'ast> for PatIdents<'_> {
+        fn visit_pat_ident(&mut self, pat: &'ast PatIdent) {
+            self.0.push(pat.ident.clone());
+            visit::visit_pat_ident(self, pat);
+        }
+    }
+
+    PatIdents(out).visit_pat(pat);
+}
+
+/// Strips `ref` and `mut` from every binding in a pattern.
+///
+/// The result is used to destructure a borrowed argument for logging, e.g.
+/// `let (a, b) = &__funlog_arg_0__;`, where default binding modes make every
+/// sub-identifier a reference and explicit binding modes would move or conflict.
+///
+/// # Arguments
+///
+/// * `pat` - The parameter pattern as written in the signature
+///
+/// # Returns
+///
+/// Returns a copy of the pattern that binds the same identifiers by reference
+pub fn strip_binding_modes(pat: &Pat) -> Pat {
+    struct StripModes;
+
+    impl VisitMut for StripModes {
+        fn visit_pat_ident_mut(&mut self, pat: &mut PatIdent) {
+            pat.by_ref = None;
+            pat.mutability = None;
+            visit_mut::visit_pat_ident_mut(self, pat);
+        }
+    }
+
+    let mut pat = pat.clone();
+    StripModes.visit_pat_mut(&mut pat);
+    pat
+}
+
+/// Returns the place that each identifier bound by an argument pattern refers to.
+///
+/// `(a, &b): (i32, &i32)` bound to `arg` gives `a` at `arg.0` and `b` at `*arg.1`, so
+/// the identifiers can be borrowed from the argument with the types the original body
+/// sees, whatever binding modes the pattern would apply.
+///
+/// # Arguments
+///
+/// * `pat` - The parameter pattern as written in the signature
+/// * `ty` - The declared type, used to index elements after `..` in a tuple or array
+/// * `place` - The expression the whole argument is bound to
+///
+/// # Returns
+///
+/// Returns the identifiers with their places, or `None` if an element's place cannot
+/// be written, e.g. after `..` in a tuple whose type is not spelled out
+pub fn pattern_places(pat: &Pat, ty: &Type, place: Expr) -> Option<Vec<(Ident, Expr)>> {
+    let mut out = Vec::new();
+    push_places(pat, Some(ty), place, &mut out)?;
+    Some(out)
+}
+
+/// Appends the places of the identifiers bound by `pat` to `out`, see
+/// [`pattern_places`].
+fn push_places(
+    pat: &Pat,
+    ty: Option<&Type>,
+    place: Expr,
+    out: &mut Vec<(Ident, Expr)>,
+) -> Option<()> {
+    let ty = match ty {
+        Some(Type::Paren(paren)) => return push_places(pat, Some(&paren.elem), place, out),
+        Some(Type::Group(group)) => return push_places(pat, Some(&group.elem), place, out),
+        ty => ty,
+    };
+    // Field access and indexing bind tighter than `*`
+    let base = match &place {
+        Expr::Unary(_) => syn::parse_quote! { (#place) },
+        _ => place.clone(),
+    };
+    match pat {
+        Pat::Ident(ident) => {
+            if let Some((_, subpat)) = &ident.subpat {
+                // `rest @ ..` binds a sub-slice, which is not a single place
+                if matches!(&**subpat, Pat::Rest(_)) {
+                    return None;
+                }
+                push_places(subpat, ty, place.clone(), out)?;
+            }
+            out.push((ident.ident.clone(), place));
+        }
+        Pat::Tuple(tuple) => {
+            let types = match ty {
+                Some(Type::Tuple(types)) => types.elems.iter().collect(),
+                _ => Vec::new(),
+            };
+            let len = (!types.is_empty()).then_some(types.len());
+            for (position, pat) in tuple.elems.iter().enumerate() {
+                let Some(index) = element_index(&tuple.elems, position, len)? else {
+                    continue;
+                };
+                let member = syn::Index::from(index);
+                push_places(
+                    pat,
+                    types.get(index).copied(),
+                    syn::parse_quote! { #base.#member },
+                    out,
+                )?;
+            }
+        }
+        Pat::TupleStruct(tuple) => {
+            for (position, pat) in tuple.elems.iter().enumerate() {
+                let Some(index) = element_index(&tuple.elems, position, None)? else {
+                    continue;
+                };
+                let member = syn::Index::from(index);
+                push_places(pat, None, syn::parse_quote! { #base.#member }, out)?;
+            }
+        }
+        Pat::Struct(strukt) => {
+            for field in &strukt.fields {
+                let member = &field.member;
+                push_places(&field.pat, None, syn::parse_quote! { #base.#member }, out)?;
+            }
+        }
+        Pat::Slice(slice) => {
+            let (elem_ty, len) = match ty {
+                Some(Type::Array(array)) => (Some(&*array.elem), Some(&array.len)),
+                _ => (None, None),
+            };
+            let rest = slice
+                .elems
+                .iter()
+                .position(|pat| matches!(pat, Pat::Rest(_)));
+            for (position, pat) in slice.elems.iter().enumerate() {
+                let index: Expr = match rest {
+                    Some(rest) if position == rest => continue,
+                    Some(rest) if position > rest => {
+                        let from_end = slice.elems.len() - position;
+                        let len = len?;
+                        syn::parse_quote! { #len - #from_end }
+                    }
+                    _ => syn::parse_quote! { #position },
+                };
+                push_places(pat, elem_ty, syn::parse_quote! { #base[#index] }, out)?;
+            }
+        }
+        Pat::Reference(reference) => {
+            let elem_ty = match ty {
+                Some(Type::Reference(reference)) => Some(&*reference.elem),
+                _ => None,
+            };
+            push_places(&reference.pat, elem_ty, syn::parse_quote! { *#place }, out)?;
+        }
+        Pat::Paren(paren) => push_places(&paren.pat, ty, place, out)?,
+        Pat::Wild(_) => {}
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Returns the field index of the element at `position` of a tuple pattern, counted
+/// from the end after `..`, or `Some(None)` for the `..` itself.
+///
+/// # Arguments
+///
+/// * `elems` - The elements of the pattern
+/// * `position` - The position of the element in the pattern
+/// * `len` - The number of fields of the type, if known
+///
+/// # Returns
+///
+/// Returns `None` if the element follows `..` and the number of fields is unknown
+fn element_index(
+    elems: &Punctuated<Pat, Token![,]>,
+    position: usize,
+    len: Option<usize>,
+) -> Option<Option<usize>> {
+    match elems.iter().position(|pat| matches!(pat, Pat::Rest(_))) {
+        Some(rest) if position == rest => Some(None),
+        Some(rest) if position > rest => Some(Some(len?.checked_sub(elems.len() - position)?)),
+        _ => Some(Some(position)),
+    }
+}
+
+/// Dereferences the given identifiers wherever an expression uses them as values.
+///
+/// Identifiers bound inside a patterned parameter are borrowed from the argument for
+/// logging, so `when` and `fields` expressions see them as `(*a)`, a place of the same
+/// type as a plain parameter. Identifiers shadowed by closure parameters and the
+/// contents of macro invocations are left alone.
+///
+/// # Arguments
+///
+/// * `expr` - The expression to rewrite in place
+/// * `names` - The borrowed identifiers
+pub fn deref_idents(expr: &mut Expr, names: &[Ident]) {
+    struct DerefIdents<'a> {
+        names: &'a [Ident],
+        bound: Vec<Ident>,
+    }
+
+    impl VisitMut for DerefIdents<'_> {
+        fn visit_expr_mut(&mut self, expr: &mut Expr) {
+            match expr {
+                Expr::Path(path) if path.qself.is_none() => {
+                    if let Some(ident) = path.path.get_ident() {
+                        if self.names.contains(ident) && !self.bound.contains(ident) {
+                            *expr = syn::parse_quote_spanned! {ident.span()=> (*#ident) };
+                        }
+                    }
+                }
+                Expr::Closure(closure) => {
+                    let depth = self.bound.len();
+                    for input in &closure.inputs {
+                        collect_pat_idents(input, &mut self.bound);
+                    }
+                    self.visit_expr_mut(&mut closure.body);
+                    self.bound.truncate(depth);
+                }
+                _ => visit_mut::visit_expr_mut(self, expr),
+            }
+        }
+
+        fn visit_field_value_mut(&mut self, field: &mut FieldValue) {
+            // `Point { x }` has to become `Point { x: (*x) }`
+            if field.colon_token.is_none() {
+                field.colon_token = Some(Token![:](field.member.span()));
+            }
+            visit_mut::visit_field_value_mut(self, field);
+        }
+    }
+
+    DerefIdents {
+        names,
+        bound: Vec::new(),
+    }
+    .visit_expr_mut(expr);
+}
+
+/// Returns `true` if a type contains `impl Trait` anywhere.
+///
+/// Such return types cannot be repeated as the return type of a closure.
+///
+/// # Arguments
+///
+/// * `ty` - The type to inspect
+pub fn contains_impl_trait(ty: &Type) -> bool {
+    struct ImplTraitFinder(bool);
+
+    impl<'ast> Visit<'ast> for ImplTraitFinder {
+        fn visit_type_impl_trait(&mut self, _: &'ast TypeImplTrait) {
+            self.0 = true;
+        }
+    }
+
+    let mut finder = ImplTraitFinder(false);
+    finder.visit_type(ty);
+    finder.0
+}
+
+/// Returns `true` if the tokens mention the `Self` type, including inside nested groups.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens to scan, e.g. a function signature or body
+pub fn mentions_self_type(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(ident) => ident == "Self",
+        TokenTree::Group(group) => mentions_self_type(group.stream()),
+        _ => false,
+    })
+}
+
+/// Makes a function body leave a labeled block instead of the function.
+///
+/// Every `return value` becomes `break 'label value` and every `expr?` a `match` on
+/// `::funlog::__private::Branch` that breaks with the converted residual, so the body
+/// can run directly inside the wrapper and the end log still runs. Closures, async
+/// blocks and nested items keep their own `return` and `?`. The contents of macro
+/// invocations cannot be seen, so a `return` or `?` written inside one, or produced by
+/// one such as `anyhow::bail!`, still leaves the wrapper.
+///
+/// # Arguments
+///
+/// * `block` - The body to rewrite in place
+/// * `label` - The label of the block the body runs in
+pub fn break_from_body(block: &mut Block, label: &Lifetime) {
+    struct BreakFromBody<'a>(&'a Lifetime);
+
+    impl VisitMut for BreakFromBody<'_> {
+        fn visit_expr_mut(&mut self, expr: &mut Expr) {
+            let label = self.0;
+            match expr {
+                Expr::Closure(_) | Expr::Async(_) => {}
+                Expr::Return(ret) => {
+                    if let Some(value) = &mut ret.expr {
+                        self.visit_expr_mut(value);
+                    }
+                    // Built rather than parsed: syn reads `break 'label ::path` as a
+                    // labeled block
+                    *expr = Expr::Break(ExprBreak {
+                        attrs: ret.attrs.clone(),
+                        break_token: Token![break](ret.return_token.span),
+                        label: Some(label.clone()),
+                        expr: Some(ret.expr.take().unwrap_or_else(|| syn::parse_quote! { () })),
+                    });
+                }
+                Expr::Try(question) => {
+                    self.visit_expr_mut(&mut question.expr);
+                    let value = &question.expr;
+                    let tokens = quote_spanned! {question.question_token.span()=>
+                        match ::funlog::__private::Branch::branch(#value) {
+                            ::core::ops::ControlFlow::Continue(__funlog_output__) => __funlog_output__,
+                            ::core::ops::ControlFlow::Break(__funlog_residual__) => {
+                                let __funlog_residual__ =
+                                    ::funlog::__private::FromResidual::from_residual(__funlog_residual__);
+                                break #label __funlog_residual__
+                            }
+                        }
+                    };
+                    *expr = syn::parse2(tokens).expect("rewritten `?` is an expression");
+                }
+                _ => visit_mut::visit_expr_mut(self, expr),
+            }
+        }
+
+        fn visit_item_mut(&mut self, _: &mut Item) {}
+    }
+
+    BreakFromBody(label).visit_block_mut(block);
+}
+
+/// Returns the identifiers referenced by `expr` that are not in `available`.
+///
+/// # Arguments
+///
+/// * `expr` - The expression to check
+/// * `available` - The identifiers that are in scope where the expression is evaluated
+///
+/// # Returns
+///
+/// Returns the unknown identifiers in the order they appear, each carrying its own span
+pub fn unknown_identifiers(expr: &Expr, available: &[Ident]) -> Vec<Ident> {
+    let mut collector = IdentCollector {
+        bound: Vec::new(),
+        found: Vec::new(),
+    };
+    collector.visit_expr(expr);
+    collector
+        .found
+        .into_iter()
+        .filter(|ident| !available.contains(ident))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::{format_ident, quote};
+    use syn::parse_quote;
+
+    fn names(idents: Vec<Ident>) -> Vec<String> {
+        idents.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn test_break_from_body() {
+        let mut block: Block = parse_quote! {{
+            if x == 0 {
+                return;
+            }
+            let y = parse(x)?;
+            let f = || { return 1; };
+            fn nested() -> Option<u8> { Some(1)? ; None }
+            return y;
+        }};
+        break_from_body(&mut block, &parse_quote!('body));
+        let body = quote!(#block).to_string();
+        assert!(body.contains("if x == 0 { break 'body () ; }"));
+        assert!(body.contains(
+            "let y = match :: funlog :: __private :: Branch :: branch (parse (x)) { \
+             :: core :: ops :: ControlFlow :: Continue (__funlog_output__) => __funlog_output__ , \
+             :: core :: ops :: ControlFlow :: Break (__funlog_residual__) => { \
+             let __funlog_residual__ = :: funlog :: __private :: FromResidual :: from_residual (__funlog_residual__) ; \
+             break 'body __funlog_residual__ } } ;"
+        ));
+        assert!(body.contains("let f = | | { return 1 ; } ;"));
+        assert!(body.contains("fn nested () -> Option < u8 > { Some (1) ? ; None }"));
+        assert!(body.ends_with("break 'body y ; }"));
+    }
+
+    #[test]
+    fn test_pattern_places() {
+        let places = |pat: Pat, ty: Type| {
+            pattern_places(&pat, &ty, parse_quote!(arg)).map(|places| {
+                places
+                    .into_iter()
+                    .map(|(ident, place)| format!("{ident} = {}", quote!(#place)))
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            places(
+                parse_quote!((a, &b, Point { x, y: (c, _) })),
+                parse_quote!((u8, &u8, Point))
+            )
+            .unwrap(),
+            [
+                "a = arg . 0",
+                "b = * arg . 1",
+                "x = arg . 2 . x",
+                "c = arg . 2 . y . 0"
+            ]
+        );
+        assert_eq!(
+            places(
+                parse_quote!(&(first, .., last)),
+                parse_quote!(&(u8, u8, u8))
+            )
+            .unwrap(),
+            ["first = (* arg) . 0", "last = (* arg) . 2"]
+        );
+        assert_eq!(
+            places(parse_quote!([head, .., tail]), parse_quote!([u8; N])).unwrap(),
+            ["head = arg [0usize]", "tail = arg [N - 1usize]"]
+        );
+        assert_eq!(
+            places(parse_quote!(whole @ Pair(one, ..)), parse_quote!(Pair)).unwrap(),
+            ["one = arg . 0", "whole = arg"]
+        );
+        // Elements after `..` need the number of fields from a written-out type
+        assert!(places(parse_quote!((first, .., last)), parse_quote!(Triple)).is_none());
+        assert!(places(parse_quote!([head, rest @ ..]), parse_quote!([u8; 3])).is_none());
+    }
+
+    #[test]
+    fn test_deref_idents() {
+        let mut expr: Expr = parse_quote! {
+            a > 0 && b.len() > 2 && items.iter().any(|a| *a == c) && Pair { a, b: 1 }.ok()
+        };
+        let names = [format_ident!("a"), format_ident!("b"), format_ident!("c")];
+        deref_idents(&mut expr, &names);
+        assert_eq!(
+            quote!(#expr).to_string(),
+            "(* a) > 0 && (* b) . len () > 2 && items . iter () . any (| a | * a == (* c)) \
+             && Pair { a : (* a) , b : 1 } . ok ()"
+        );
+    }
+
+    #[test]
+    fn test_known_identifiers() {
+        let expr: Expr = parse_quote! { amount > 10_000 && user.is_admin() };
+        let available = vec![format_ident!("amount"), format_ident!("user")];
+        assert!(unknown_identifiers(&expr, &available).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_identifiers() {
+        let expr: Expr = parse_quote! { amout > 10_000 || other };
+        let available = vec![format_ident!("amount")];
+        assert_eq!(
+            names(unknown_identifiers(&expr, &available)),
+            vec!["amout", "other"]
+        );
+    }
+
+    #[test]
+    fn test_ignores_calls_constants_and_closures() {
+        let expr: Expr = parse_quote! {
+            is_valid(x) && x < MAX && items.iter().any(|item| *item > x) && Some(x).is_some()
+        };
+        let available = vec![format_ident!("x"), format_ident!("items")];
+        assert!(unknown_identifiers(&expr, &available).is_empty());
+    }
+
+    #[test]
+    fn test_collect_pat_idents() {
+        let pat: Pat = parse_quote! { (a, mut b, Point { x, .. }) };
+        let mut idents = Vec::new();
+        collect_pat_idents(&pat, &mut idents);
+        assert_eq!(names(idents), vec!["a", "b", "x"]);
+    }
+
+    #[test]
+    fn test_strip_binding_modes() {
+        let pat: Pat = parse_quote! { (ref a, mut b, Point { x: mut px, .. }) };
+        let stripped = strip_binding_modes(&pat);
+        let expected: Pat = parse_quote! { (a, b, Point { x: px, .. }) };
+        assert_eq!(stripped, expected);
+    }
+
+    #[test]
+    fn test_contains_impl_trait() {
+        let ty: Type = parse_quote! { Box<dyn Iterator<Item = u8>> };
+        assert!(!contains_impl_trait(&ty));
+
+        let ty: Type = parse_quote! { Result<impl Iterator<Item = u8>, String> };
+        assert!(contains_impl_trait(&ty));
+    }
+
+    #[test]
+    fn test_mentions_self_type() {
+        assert!(mentions_self_type(
+            quote::quote! { { let x: Vec<Self> = vec![]; } }
+        ));
+        assert!(!mentions_self_type(quote::quote! { { self.value } }));
+    }
+}
@@ -0,0 +1,573 @@
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use crate::config::{OutputPosition, OutputType};
+use crate::config_builder::ParameterEnum;
+use crate::error::ConfigError;
+
+/// The name of the optional configuration file read by the macro.
+pub const FILE_NAME: &str = "funlog.toml";
+
+/// Crate-wide settings read from `funlog.toml`.
+///
+/// The file is looked up in `CARGO_MANIFEST_DIR` and then at the workspace root.
+/// Every section is optional:
+///
+/// ```toml
+/// [defaults]
+/// level = "debug"
+/// position = "start_end"
+/// ret = true
+/// stats = true
+/// params = "all"
+/// redact = ["password", "token"]
+///
+/// [format]
+/// pretty = false
+/// max_len = 200
+///
+/// [modules."db::query"]
+/// level = "trace"
+/// redact = ["sql"]
+///
+/// [presets]
+/// audit = "info, all, retVal"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileConfig {
+    /// Settings used by every annotation that does not set them itself
+    #[serde(default)]
+    pub defaults: Defaults,
+    /// How logged values are formatted
+    #[serde(default)]
+    pub format: FormatOptions,
+    /// Settings layered over `defaults` for functions in a module and its submodules
+    #[serde(default)]
+    pub modules: BTreeMap<String, Defaults>,
+    /// Named option lists, used in an attribute as `preset = name`
+    #[serde(default)]
+    pub presets: BTreeMap<String, String>,
+    /// The file the settings were read from, if any
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Default options, for the whole crate or for one module.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Defaults {
+    /// The log level, like `level = ...` in the attribute
+    pub level: Option<Level>,
+    /// When to log, like `position = ...` in the attribute
+    pub position: Option<Position>,
+    /// Whether to log the return value, like `ret = ...` in the attribute
+    pub ret: Option<bool>,
+    /// Whether to record call statistics, like `stats = ...` in the attribute
+    pub stats: Option<bool>,
+    /// Whether to log every parameter or none of them
+    pub params: Option<Params>,
+    /// Parameter names whose values are logged as `<redacted>`
+    #[serde(default)]
+    pub redact: Vec<String>,
+}
+
+/// How logged values are formatted.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormatOptions {
+    /// Format values with `{:#?}` instead of `{:?}`
+    #[serde(default)]
+    pub pretty: bool,
+    /// Truncate each formatted value to this many characters, followed by `…`
+    pub max_len: Option<usize>,
+}
+
+/// A log level in the configuration file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Print,
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// A log position in the configuration file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Position {
+    #[serde(alias = "onStart")]
+    Start,
+    #[serde(alias = "onEnd")]
+    End,
+    #[serde(alias = "onStartEnd")]
+    StartEnd,
+}
+
+/// A parameter selection in the configuration file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Params {
+    All,
+    None,
+}
+
+impl From<Level> for OutputType {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Print => OutputType::Print,
+            Level::Trace => OutputType::Trace,
+            Level::Debug => OutputType::Debug,
+            Level::Info => OutputType::Info,
+            Level::Warn => OutputType::Warn,
+            Level::Error => OutputType::Error,
+        }
+    }
+}
+
+impl From<Position> for OutputPosition {
+    fn from(position: Position) -> Self {
+        match position {
+            Position::Start => OutputPosition::OnStart,
+            Position::End => OutputPosition::OnEnd,
+            Position::StartEnd => OutputPosition::OnStartAndEnd,
+        }
+    }
+}
+
+impl From<Params> for ParameterEnum {
+    fn from(params: Params) -> Self {
+        match params {
+            Params::All => ParameterEnum::AllParameters,
+            Params::None => ParameterEnum::NoneParameter,
+        }
+    }
+}
+
+/// A configuration read for one crate directory, with the file it came from.
+struct Cached {
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    config: Result<Rc<FileConfig>, ConfigError>,
+}
+
+thread_local! {
+    static LOADED: RefCell<HashMap<PathBuf, Cached>> = RefCell::new(HashMap::new());
+}
+
+impl FileConfig {
+    /// Returns the configuration for the crate being compiled.
+    ///
+    /// A crate without `funlog.toml` gets the default configuration.
+    ///
+    /// # Returns
+    ///
+    /// Returns the configuration, or `ConfigError::InvalidConfigFile` if the file
+    /// exists but cannot be read or parsed
+    pub fn load() -> Result<Rc<FileConfig>, ConfigError> {
+        match std::env::var_os("CARGO_MANIFEST_DIR") {
+            Some(manifest_dir) => Self::load_from(Path::new(&manifest_dir)),
+            None => Ok(Rc::default()),
+        }
+    }
+
+    /// Returns the configuration for the crate in `manifest_dir`.
+    ///
+    /// The parsed file is cached per crate directory, as a proc-macro server may expand
+    /// several crates, and read again when a different file is found or its
+    /// modification time changes, so edits are picked up without restarting it.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_dir` - The directory of the crate being compiled
+    ///
+    /// # Returns
+    ///
+    /// Returns the configuration, or `ConfigError::InvalidConfigFile`
+    pub fn load_from(manifest_dir: &Path) -> Result<Rc<FileConfig>, ConfigError> {
+        let path = Self::find(manifest_dir);
+        let modified = path.as_ref().and_then(|path| {
+            std::fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()
+        });
+        LOADED.with(|loaded| {
+            let mut loaded = loaded.borrow_mut();
+            if let Some(cached) = loaded.get(manifest_dir) {
+                if cached.path == path && cached.modified == modified {
+                    return cached.config.clone();
+                }
+            }
+            let config = match &path {
+                Some(path) => Self::read(path).map(Rc::new),
+                None => Ok(Rc::default()),
+            };
+            loaded.insert(
+                manifest_dir.to_path_buf(),
+                Cached {
+                    path,
+                    modified,
+                    config: config.clone(),
+                },
+            );
+            config
+        })
+    }
+
+    /// Finds `funlog.toml` in `manifest_dir`, or else at the workspace root.
+    ///
+    /// The workspace root is the nearest parent directory whose `Cargo.toml` declares a
+    /// `[workspace]`; crates outside a workspace only use their own directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_dir` - The directory of the crate being compiled
+    ///
+    /// # Returns
+    ///
+    /// Returns the path of the file, or `None` if there is none
+    pub fn find(manifest_dir: &Path) -> Option<PathBuf> {
+        let candidate = manifest_dir.join(FILE_NAME);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let workspace_root = manifest_dir.ancestors().skip(1).find(|dir| {
+            std::fs::read_to_string(dir.join("Cargo.toml"))
+                .map(|manifest| manifest.lines().any(|line| line.trim() == "[workspace]"))
+                .unwrap_or(false)
+        })?;
+        let candidate = workspace_root.join(FILE_NAME);
+        candidate.is_file().then_some(candidate)
+    }
+
+    /// Reads and parses a configuration file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file
+    ///
+    /// # Returns
+    ///
+    /// Returns the configuration, or `ConfigError::InvalidConfigFile`
+    pub fn read(path: &Path) -> Result<FileConfig, ConfigError> {
+        let invalid = |message: String| ConfigError::InvalidConfigFile {
+            path: path.display().to_string(),
+            message,
+        };
+        let contents = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        let mut config = Self::parse(&contents).map_err(invalid)?;
+        config.path = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// Parses the contents of a configuration file.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The TOML text
+    ///
+    /// # Returns
+    ///
+    /// Returns the configuration, or the parser's message describing the problem and
+    /// the line it was found on
+    pub fn parse(contents: &str) -> Result<FileConfig, String> {
+        toml::from_str(contents).map_err(|e| match e.span() {
+            Some(span) => {
+                let line = contents[..span.start].matches('\n').count() + 1;
+                format!("{} (line {line})", e.message().trim_end())
+            }
+            None => e.message().trim_end().to_string(),
+        })
+    }
+
+    /// Returns the defaults that apply to a function in `module`.
+    ///
+    /// Overrides for `module` and each of its ancestors are layered over `[defaults]`,
+    /// from the outermost module inwards; redaction lists are combined.
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - The module path of the function relative to the crate root, such
+    ///   as `db::query`, or `None` if it is not known
+    ///
+    /// # Returns
+    ///
+    /// Returns the merged defaults
+    pub fn resolve(&self, module: Option<&str>) -> Defaults {
+        let mut resolved = self.defaults.clone();
+        let Some(module) = module else {
+            return resolved;
+        };
+
+        let mut overrides = self
+            .modules
+            .iter()
+            .map(|(key, defaults)| (normalize_module(key), defaults))
+            .filter(|(key, _)| {
+                key.is_empty()
+                    || module == key
+                    || module
+                        .strip_prefix(key.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .collect::<Vec<_>>();
+        overrides.sort_by_key(|(key, _)| key.len());
+
+        for (_, defaults) in overrides {
+            resolved.level = defaults.level.or(resolved.level);
+            resolved.position = defaults.position.or(resolved.position);
+            resolved.ret = defaults.ret.or(resolved.ret);
+            resolved.stats = defaults.stats.or(resolved.stats);
+            resolved.params = defaults.params.or(resolved.params);
+            resolved.redact.extend(defaults.redact.iter().cloned());
+        }
+        resolved
+    }
+}
+
+/// Strips a leading `crate` from a module key, so `crate::db` and `db` are the same.
+fn normalize_module(key: &str) -> String {
+    let key = key.trim();
+    match key.strip_prefix("crate") {
+        Some("") => String::new(),
+        Some(rest) if rest.starts_with("::") => rest[2..].to_string(),
+        _ => key.to_string(),
+    }
+}
+
+/// Derives the module path of a source file from its location under `src/`.
+///
+/// `src/lib.rs` and `src/main.rs` are the crate root, `src/db/mod.rs` and `src/db.rs`
+/// are `db`, and `src/db/query.rs` is `db::query`. Inline `mod` blocks cannot be seen
+/// from the file path, so functions in them use the module of their file.
+///
+/// # Arguments
+///
+/// * `file` - The source file, absolute or relative to `manifest_dir`
+/// * `manifest_dir` - The directory of the crate being compiled
+///
+/// # Returns
+///
+/// Returns the module path, or `None` if the file is not under `src/`
+pub fn module_path(file: &Path, manifest_dir: &Path) -> Option<String> {
+    let file = if file.is_absolute() {
+        file.to_path_buf()
+    } else {
+        manifest_dir.join(file)
+    };
+    let relative = file.strip_prefix(manifest_dir.join("src")).ok()?;
+
+    let mut segments = relative
+        .components()
+        .map(|component| match component {
+            Component::Normal(segment) => segment.to_str().map(str::to_string),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let file_name = segments.pop()?;
+    let stem = file_name.strip_suffix(".rs")?;
+    let is_root = segments.is_empty() && (stem == "lib" || stem == "main");
+    if stem != "mod" && !is_root {
+        segments.push(stem.to_string());
+    }
+    Some(segments.join("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_empty() {
+        let config = FileConfig::parse("").unwrap();
+        assert!(config.defaults.level.is_none());
+        assert!(config.defaults.redact.is_empty());
+        assert!(!config.format.pretty);
+        assert!(config.format.max_len.is_none());
+        assert!(config.modules.is_empty());
+    }
+
+    #[test]
+    fn test_parse_full() {
+        let config = FileConfig::parse(
+            r#"
+            [defaults]
+            level = "debug"
+            position = "onEnd"
+            ret = true
+            stats = true
+            params = "none"
+            redact = ["password"]
+
+            [format]
+            pretty = true
+            max_len = 40
+
+            [modules."db::query"]
+            level = "trace"
+
+            [presets]
+            audit = "info, all, retVal"
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(config.defaults.level, Some(Level::Debug)));
+        assert!(matches!(config.defaults.position, Some(Position::End)));
+        assert_eq!(config.defaults.ret, Some(true));
+        assert_eq!(config.defaults.stats, Some(true));
+        assert!(matches!(config.defaults.params, Some(Params::None)));
+        assert_eq!(config.defaults.redact, vec!["password"]);
+        assert!(config.format.pretty);
+        assert_eq!(config.format.max_len, Some(40));
+        assert!(matches!(
+            config.modules["db::query"].level,
+            Some(Level::Trace)
+        ));
+        assert_eq!(config.presets["audit"], "info, all, retVal");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = FileConfig::parse("[defaults]\nlevl = \"debug\"").unwrap_err();
+        assert!(error.contains("unknown field `levl`"));
+        assert!(error.ends_with("(line 2)"));
+
+        let error = FileConfig::parse("[defaults]\nlevel = \"loud\"").unwrap_err();
+        assert!(error.contains("unknown variant `loud`"));
+
+        let error = FileConfig::parse("[format]\nmax_len = \"long\"").unwrap_err();
+        assert!(error.contains("invalid type"));
+
+        assert!(FileConfig::parse("[defaults").is_err());
+    }
+
+    #[test]
+    fn test_resolve_layers_module_overrides() {
+        let config = FileConfig::parse(
+            r#"
+            [defaults]
+            level = "info"
+            ret = true
+            redact = ["password"]
+
+            [modules.db]
+            level = "debug"
+            redact = ["dsn"]
+
+            [modules."crate::db::query"]
+            level = "trace"
+            ret = false
+
+            [modules.dbx]
+            level = "error"
+            "#,
+        )
+        .unwrap();
+
+        let root = config.resolve(Some(""));
+        assert!(matches!(root.level, Some(Level::Info)));
+        assert_eq!(root.ret, Some(true));
+
+        let db = config.resolve(Some("db::pool"));
+        assert!(matches!(db.level, Some(Level::Debug)));
+        assert_eq!(db.redact, vec!["password", "dsn"]);
+
+        let query = config.resolve(Some("db::query"));
+        assert!(matches!(query.level, Some(Level::Trace)));
+        assert_eq!(query.ret, Some(false));
+        assert_eq!(query.redact, vec!["password", "dsn"]);
+
+        let unknown = config.resolve(None);
+        assert!(matches!(unknown.level, Some(Level::Info)));
+    }
+
+    #[test]
+    fn test_module_path() {
+        let manifest_dir = Path::new("/work/app");
+        let module = |file: &str| module_path(Path::new(file), manifest_dir);
+
+        assert_eq!(module("src/lib.rs").as_deref(), Some(""));
+        assert_eq!(module("/work/app/src/main.rs").as_deref(), Some(""));
+        assert_eq!(module("src/db.rs").as_deref(), Some("db"));
+        assert_eq!(module("src/db/mod.rs").as_deref(), Some("db"));
+        assert_eq!(module("src/db/query.rs").as_deref(), Some("db::query"));
+        assert_eq!(module("tests/it.rs"), None);
+        assert_eq!(module("/elsewhere/src/lib.rs"), None);
+    }
+
+    #[test]
+    fn test_find_prefers_crate_then_workspace_root() {
+        let root = std::env::temp_dir().join(format!("funlog-find-{}", std::process::id()));
+        let member = root.join("crates").join("app");
+        std::fs::create_dir_all(&member).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        assert_eq!(FileConfig::find(&member), None);
+
+        std::fs::write(root.join(FILE_NAME), "").unwrap();
+        assert_eq!(FileConfig::find(&member), Some(root.join(FILE_NAME)));
+
+        // Directories between the crate and the workspace root are not searched
+        std::fs::write(root.join("crates").join(FILE_NAME), "").unwrap();
+        assert_eq!(FileConfig::find(&member), Some(root.join(FILE_NAME)));
+
+        std::fs::write(member.join(FILE_NAME), "").unwrap();
+        assert_eq!(FileConfig::find(&member), Some(member.join(FILE_NAME)));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_load_from_rereads_edited_file() {
+        let dir = std::env::temp_dir().join(format!("funlog-load-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(FILE_NAME);
+        assert!(FileConfig::load_from(&dir).unwrap().path.is_none());
+
+        std::fs::write(&path, "[defaults]\nret = true\n").unwrap();
+        let first = FileConfig::load_from(&dir).unwrap();
+        assert_eq!(first.defaults.ret, Some(true));
+        assert!(Rc::ptr_eq(&first, &FileConfig::load_from(&dir).unwrap()));
+
+        std::fs::write(&path, "[defaults]\nret = false\n").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(10);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(
+            FileConfig::load_from(&dir).unwrap().defaults.ret,
+            Some(false)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_reports_path() {
+        let path = std::env::temp_dir().join(format!("funlog-read-{}.toml", std::process::id()));
+        std::fs::write(&path, "[defaults]\nret = \"yes\"\n").unwrap();
+        let error = FileConfig::read(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        match error {
+            ConfigError::InvalidConfigFile {
+                path: shown,
+                message,
+            } => {
+                assert_eq!(shown, path.display().to_string());
+                assert!(message.contains("invalid type"));
+            }
+            other => panic!("Expected InvalidConfigFile, got {other:?}"),
+        }
+    }
+}
@@ -7,7 +7,7 @@ use syn::{Attribute, Block, ItemFn, Signature, Visibility};
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// use syn::{parse_quote, ItemFn};
 /// use funlog::generics_item_fn::GenericsFn;
 ///
@@ -17,8 +17,7 @@ use syn::{Attribute, Block, ItemFn, Signature, Visibility};
 /// let generics_fn = GenericsFn::from(func);
 /// ```
 pub struct GenericsFn {
-    /// Function attributes (currently unused but preserved for future use)
-    #[allow(dead_code)]
+    /// Function attributes, distributed by [`GenericsFn::split_attrs`]
     pub attrs: Vec<Attribute>,
     /// Function visibility (pub, pub(crate), etc.)
     pub vis: Visibility,
@@ -41,7 +40,7 @@ impl From<ItemFn> for GenericsFn {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// use syn::{parse_quote, ItemFn};
     /// use funlog::generics_item_fn::GenericsFn;
     ///
@@ -61,6 +60,45 @@ impl From<ItemFn> for GenericsFn {
     }
 }
 
+impl GenericsFn {
+    /// Splits the function's outer attributes between the public wrapper and the
+    /// statement that runs the original body inside it.
+    ///
+    /// - Lint levels (`allow`, `warn`, `deny`, `forbid`) go on both, since they affect
+    ///   the signature as well as the body
+    /// - `expect` goes on the body only, where the body's lints are raised, so the
+    ///   wrapper cannot leave the expectation unfulfilled
+    /// - Everything else, including doc comments, `must_use`, `deprecated`, `inline`,
+    ///   `cfg`, `track_caller`, `no_mangle`, `cfg_attr` and test attributes, goes on the
+    ///   wrapper only
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple of (wrapper attributes, body attributes)
+    pub fn split_attrs(&self) -> (Vec<Attribute>, Vec<Attribute>) {
+        const SHARED: [&str; 4] = ["allow", "warn", "deny", "forbid"];
+
+        let mut outer = Vec::new();
+        let mut inner = Vec::new();
+        for attr in &self.attrs {
+            let name = attr
+                .path()
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            if name == "expect" {
+                inner.push(attr.clone());
+            } else if SHARED.contains(&name.as_str()) {
+                outer.push(attr.clone());
+                inner.push(attr.clone());
+            } else {
+                outer.push(attr.clone());
+            }
+        }
+        (outer, inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,6 +151,46 @@ mod tests {
         assert_eq!(generics_fn.attrs.len(), 2);
     }
 
+    #[test]
+    fn test_split_attrs() {
+        let func: ItemFn = parse_quote! {
+            /// Documentation
+            #[must_use]
+            #[inline]
+            #[cfg(unix)]
+            #[allow(clippy::needless_return)]
+            #[track_caller]
+            #[expect(unused_variables)]
+            #[deprecated(note = "use something else")]
+            pub fn attributed_func(x: i32) -> i32 {
+                x * 2
+            }
+        };
+
+        let generics_fn = GenericsFn::from(func);
+        let (outer, inner) = generics_fn.split_attrs();
+        let names = |attrs: &[Attribute]| {
+            attrs
+                .iter()
+                .map(|a| a.path().get_ident().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(&outer),
+            vec![
+                "doc",
+                "must_use",
+                "inline",
+                "cfg",
+                "allow",
+                "track_caller",
+                "deprecated"
+            ]
+        );
+        assert_eq!(names(&inner), vec!["allow", "expect"]);
+    }
+
     #[test]
     fn test_from_function_with_complex_signature() {
         let func: ItemFn = parse_quote! {
@@ -0,0 +1,429 @@
+//! # Funlog Macros
+//!
+//! The `#[funlog]` attribute. Use it through the `funlog` crate, which also provides
+//! the runtime the generated code calls into.
+
+#![cfg_attr(feature = "nightly", feature(proc_macro_diagnostic))]
+
+mod config;
+mod config_builder;
+mod error;
+#[cfg(test)]
+mod expansion_tests;
+mod expr_check;
+mod file_config;
+mod generics_item_fn;
+mod log_template;
+mod output;
+mod replay;
+
+use config_builder::ConfigBuilder;
+use file_config::FileConfig;
+use generics_item_fn::GenericsFn;
+use proc_macro::TokenStream;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::Meta;
+use syn::{parse_macro_input, ItemFn};
+
+/// A procedural macro attribute for adding automatic logging to functions.
+///
+/// This macro generates logging code that can output function entry, exit,
+/// parameters, and return values based on the provided configuration.
+///
+/// # Arguments
+///
+/// The macro accepts various configuration options:
+///
+/// ## Log Levels
+/// - `print` - Use `println!` for output (default)
+/// - `trace` - Use `log::trace!`
+/// - `debug` - Use `log::debug!`
+/// - `info` - Use `log::info!`
+/// - `warn` - Use `log::warn!`
+/// - `error` - Use `log::error!`
+///
+/// ## Parameter Control
+/// - `all` - Log all function parameters (default)
+/// - `none` - Log no parameters
+/// - `params(param1, param2, ...)` - Log specific parameters
+/// - Parameters declared with patterns, such as `(a, b): (i32, i32)` or
+///   `Point { x, y }: Point`, are logged by the identifiers they bind; `_` is never logged.
+///   In `when` and `fields` expressions those identifiers have the types the body sees,
+///   like plain parameters, so `when = "a > 0"` works for both
+/// - `fields(name = expr, ...)` - Evaluate expressions over the parameters on entry and
+///   log them as `name:value`; without `all` or `params(...)`, only the fields are logged
+///
+/// ## Position Control
+/// - `onStart` - Log only at function entry
+/// - `onEnd` - Log only at function exit
+/// - `onStartEnd` - Log at both entry and exit (default)
+///
+/// ## Return Value
+/// - `retVal` - Include return value in logging
+///
+/// ## Statistics
+/// - `stats` - Count every call of the function: calls, total, min and max duration, a
+///   latency histogram and, for functions returning `Result`, the `Err` returns. Read
+///   them with `funlog::report()`. Statistics are kept whether or not the call is logged
+///
+/// ## Record and Replay
+/// - `record` - With the `record` feature of `funlog`, append the arguments and return
+///   value of every call to the file named by `FUNLOG_RECORD`, one JSON object per
+///   line, for [`macro@replay_test`]. Arguments, except `self`, and the return value
+///   must implement `serde::Serialize`. Without the feature, `record` is a compile
+///   error that says to enable it
+///
+/// ## Key-Value Options
+/// Every flag above also has a `key = value` form; the flags remain as shorthand and
+/// the same conflict detection applies to both forms.
+/// - `level = "debug"` (or `level = debug`) - Same as the bare level flags
+/// - `position = "start" | "end" | "start_end"` - Same as `onStart`, `onEnd`, `onStartEnd`
+/// - `ret = true | false` - Whether to log the return value, like `retVal`
+/// - `stats = true | false` - Whether to record call statistics, like `stats`
+/// - `record = true | false` - Whether to record calls for replay, like `record`
+/// - `start(level = ...)` / `end(level = ...)` - Level for the entry or exit log only,
+///   overriding the function's level for that phase; the position is unchanged, so
+///   `onEnd, end(level = info)` logs only the exit, at `info`
+///
+/// ## Presets
+/// - `preset = name` - Use the options of a preset from the `[presets]` table of
+///   `funlog.toml`; options written in the attribute replace the preset's options of
+///   the same kind, so `preset = audit, level = "debug"` keeps everything but the level
+///
+/// ## Conditions
+/// - `when = "expr"` - Only log the call when `expr` is `true`; evaluated against the
+///   parameters on entry
+/// - `when_ret = "expr"` - Only log the exit when `expr` is `true`; evaluated on exit
+///   with `ret` bound to a reference to the return value
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use funlog::funlog;
+///
+/// // Basic usage with debug logging and all parameters
+/// #[funlog(debug, all)]
+/// fn add(x: i32, y: i32) -> i32 {
+///     x + y
+/// }
+///
+/// // Log specific parameters only at function start
+/// #[funlog(info, params(name), onStart)]
+/// fn greet(name: &str, age: u32) {
+///     println!("Hello, {}!", name);
+/// }
+///
+/// // Include return value in logging
+/// #[funlog(debug, all, retVal)]
+/// fn multiply(x: i32, y: i32) -> i32 {
+///     x * y
+/// }
+///
+/// // No parameter logging, only function entry/exit
+/// #[funlog(trace, none)]
+/// fn process_data() {
+///     // processing logic
+/// }
+///
+/// // Log a couple of derived values instead of whole parameters
+/// #[funlog(debug, fields(n = items.len(), first = items.first()))]
+/// fn sum(items: Vec<i64>) -> i64 {
+///     items.iter().sum()
+/// }
+///
+/// // Entry at trace, exit with the return value at debug
+/// #[funlog(start(level = trace), end(level = debug), retVal)]
+/// fn lookup(key: &str) -> Option<u32> {
+///     key.parse().ok()
+/// }
+///
+/// // Key-value form of `info, onEnd, retVal`
+/// #[funlog(level = "info", position = "end", ret = true)]
+/// fn total(prices: &[u32]) -> u32 {
+///     prices.iter().sum()
+/// }
+///
+/// // Only log large transfers, and only report failures on exit
+/// #[funlog(debug, when = "amount > 10_000", when_ret = "ret.is_err()")]
+/// fn transfer(amount: u64) -> Result<(), String> {
+///     Ok(())
+/// }
+/// ```
+///
+/// # Configuration File
+///
+/// An optional `funlog.toml` in the crate directory, or at the workspace root, sets
+/// defaults for every annotation. Options given in the attribute always win; the file
+/// only fills in what is left unset, before the built-in defaults above.
+///
+/// ```toml
+/// [defaults]
+/// level = "debug"          # print, trace, debug, info, warn, error
+/// position = "start_end"   # start, end, start_end
+/// ret = true
+/// stats = true
+/// params = "all"           # all, none
+/// redact = ["password"]    # parameters logged as <redacted>; need not be Debug
+///
+/// [format]
+/// pretty = false           # use {:#?}
+/// max_len = 200            # truncate each value to this many characters
+///
+/// [modules."db::query"]    # this module and its submodules, by file location
+/// level = "trace"
+///
+/// [presets]                # used as #[funlog(preset = audit)]
+/// audit = "info, all, retVal"
+/// ```
+///
+/// Module overrides are matched against the module of the source file under `src/`;
+/// functions in inline `mod` blocks use the module of their file. An invalid file is
+/// reported as a compile error on each annotation.
+///
+/// Annotated code is recompiled when the file it was built with changes. Cargo does
+/// not track files that do not exist yet, so creating `funlog.toml` after a build, or
+/// one in the crate directory that now takes the place of the workspace's, takes
+/// effect on the next rebuild for another reason; touch a source file of the crate to
+/// apply it.
+///
+/// # Attributes and Qualifiers
+///
+/// Outer attributes are kept. Lint levels (`allow`, `warn`, `deny`, `forbid`) apply to
+/// both the public function and the original body, and `expect` to the body only,
+/// where its lints are raised. Every other attribute, including doc comments,
+/// `must_use`, `deprecated`, `inline`, `no_mangle`, `cfg`, `track_caller` and
+/// `target_feature`, stays on the public function only; the original body runs inline
+/// inside it. Caller locations of `track_caller` functions still point at the caller:
+/// their body runs in a nested `#[track_caller]` function or, for methods and
+/// functions that name `Self`, directly in the public function, where a `return` or
+/// `?` written in or produced by a macro call skips the exit log. On `async`
+/// functions, `track_caller` has no effect on stable Rust, with or without `#[funlog]`,
+/// and rustc warns about it. Generic, `async`, `unsafe` and
+/// `extern "ABI"` functions are supported; `const fn` is rejected because logging is
+/// not available in const contexts.
+///
+/// # Errors
+///
+/// The macro will produce compile-time errors for:
+/// - Conflicting options (e.g., `debug, info`)
+/// - Invalid parameter names
+/// - Invalid attribute names (with suggestions)
+/// - Unknown identifiers in `when` / `when_ret` expressions
+/// - Incorrect syntax
+/// - Logged parameters, or a return value logged with `retVal`, whose type does not
+///   implement `Debug`
+///
+/// Errors are reported on the offending token, all at once, and the function is still
+/// emitted without instrumentation so that callers do not produce follow-on errors.
+///
+/// # Note
+///
+/// The macro only generates logging code in debug builds. In release builds,
+/// the original function is returned unchanged for optimal performance.
+#[proc_macro_attribute]
+pub fn funlog(args: TokenStream, item: TokenStream) -> TokenStream {
+    let is_debug = cfg!(debug_assertions);
+
+    // when not debug, just return the original function
+    if !is_debug {
+        return item;
+    }
+
+    expand(
+        args.into(),
+        item.into(),
+        FileConfig::load(),
+        module_path().as_deref(),
+    )
+    .into()
+}
+
+/// Expands `#[funlog(args)]` on `item`, without the compiler-only lookups of
+/// `funlog`, so the expansion can be tested.
+///
+/// # Arguments
+///
+/// * `args` - The tokens inside `#[funlog(...)]`
+/// * `item` - The annotated function
+/// * `file_config` - The loaded `funlog.toml`, or the error from loading it
+/// * `module` - The module of the annotated function, for module overrides
+///
+/// # Returns
+///
+/// Returns the instrumented function, or the errors followed by the function unchanged
+fn expand(
+    args: proc_macro2::TokenStream,
+    item: proc_macro2::TokenStream,
+    file_config: Result<std::rc::Rc<FileConfig>, error::ConfigError>,
+    module: Option<&str>,
+) -> proc_macro2::TokenStream {
+    let func = match syn::parse2::<ItemFn>(item) {
+        Ok(func) => func,
+        Err(e) => return e.into_compile_error(),
+    };
+    let original = func.clone();
+    let func = GenericsFn::from(func);
+    let result = file_config
+        .and_then(|file_config| {
+            let attr_meta = Punctuated::<Meta, Comma>::parse_terminated
+                .parse2(args)
+                .map_err(|e| error::ConfigError::ParseError(e.to_string(), e.span()))?;
+            ConfigBuilder::from_with_file_config(attr_meta, func, &file_config, module)
+        })
+        .and_then(ConfigBuilder::build);
+    match result {
+        Ok(config) => config.to_output().into(),
+        Err(config_error) => {
+            // Keep the function itself so callers do not produce follow-on errors
+            let compile_error = config_error.into_compile_error();
+            quote::quote! {
+                #compile_error
+                #original
+            }
+        }
+    }
+}
+
+/// Turns a function into a test that replays the calls recorded in a cassette.
+///
+/// Functions instrumented with `#[funlog(record)]` append each call to the file named
+/// by `FUNLOG_RECORD` when the `record` feature of `funlog` is on. A replay test takes
+/// the recorded arguments in order, as owned types that implement
+/// `serde::Deserialize`, and calls the code under test with them. The test fails,
+/// listing every difference, if a call returns something other than the recorded
+/// value; return values are compared as JSON, so they only need `serde::Serialize`.
+///
+/// # Arguments
+///
+/// - `"path"` - The cassette, relative to the crate directory
+/// - `module = "path"` - The module of the recorded function; defaults to the test's
+///   module, or the nearest module around it with recorded calls, so a test in
+///   `app::tests` replays `app`
+/// - `name = "function"` - The recorded function to replay; defaults to the test's
+///   name without a `replay_` prefix
+///
+/// Calls of functions with the same name in other modules are skipped.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[funlog(debug, record)]
+/// fn greet(name: &str, times: usize) -> String {
+///     name.repeat(times)
+/// }
+///
+/// // FUNLOG_RECORD=tests/cassettes/greet.jsonl cargo run
+///
+/// #[funlog::replay_test("tests/cassettes/greet.jsonl")]
+/// fn replay_greet(name: String, times: usize) -> String {
+///     greet(&name, times)
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Replay tests cannot be `async`, generic or take `self`; recorded methods are
+/// replayed from a test that builds the receiver itself. Like `#[funlog(record)]`,
+/// they need the `record` feature of `funlog`.
+#[proc_macro_attribute]
+pub fn replay_test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as replay::ReplayArgs);
+    let func = parse_macro_input!(item as ItemFn);
+    replay::expand(args, func)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Returns the module path of the file being expanded, for `funlog.toml` module overrides.
+fn module_path() -> Option<String> {
+    let file = proc_macro::Span::call_site().local_file()?;
+    // Relative paths are relative to the directory rustc runs in, the workspace root
+    let file = std::env::current_dir().map_or_else(|_| file.clone(), |cwd| cwd.join(&file));
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")?;
+    file_config::module_path(&file, std::path::Path::new(&manifest_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::{parse_quote, ItemFn};
+
+    #[test]
+    fn test_funlog_macro_exists() {
+        // This test ensures the macro is properly exported
+        // We can't easily test the macro directly in unit tests,
+        // but we can verify the supporting functions work
+
+        let func: ItemFn = parse_quote! {
+            fn test_func(x: i32) -> i32 { x + 1 }
+        };
+
+        let generics_fn = GenericsFn::from(func);
+        assert_eq!(generics_fn.sig.ident.to_string(), "test_func");
+    }
+
+    #[test]
+    fn test_debug_build_behavior() {
+        // In debug builds, the macro should process the function
+        // In release builds, it should return the original
+
+        // We can test this by checking the cfg! macro
+        let is_debug = cfg!(debug_assertions);
+
+        // This test documents the expected behavior
+        if is_debug {
+            // In debug builds, funlog should process the function
+            // Note: This is a documentation test, not an assertion
+        } else {
+            // In release builds, funlog should return original function
+            // Note: This is a documentation test, not an assertion
+        }
+    }
+
+    #[test]
+    fn test_integration_with_config_builder() {
+        let func: ItemFn = parse_quote! {
+            pub fn integration_test(x: i32, y: String) -> i32 {
+                x + 1
+            }
+        };
+
+        let generics_fn = GenericsFn::from(func);
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { debug, all, retVal };
+
+        let config_builder = ConfigBuilder::from(meta_list, generics_fn);
+        assert!(config_builder.is_ok());
+
+        let config = config_builder.unwrap().build();
+        assert!(config.is_ok());
+
+        let output = config.unwrap().to_output();
+        assert!(!output.func_declare_body.is_empty());
+    }
+
+    #[test]
+    fn test_error_handling_integration() {
+        let func: ItemFn = parse_quote! {
+            fn error_test() {}
+        };
+
+        let generics_fn = GenericsFn::from(func);
+
+        // Test with conflicting options
+        let meta_list: Punctuated<Meta, Comma> = parse_quote! { debug, info };
+
+        let result = ConfigBuilder::from(meta_list, generics_fn);
+        assert!(result.is_err());
+
+        // Verify it's the expected error type
+        match result.unwrap_err() {
+            error::ConfigError::ConflictingOptions { .. } => {
+                // Expected error type
+            }
+            _ => panic!("Expected ConflictingOptions error"),
+        }
+    }
+}
@@ -0,0 +1,952 @@
+use crate::config::{OutputPosition, OutputType};
+use crate::file_config::FormatOptions;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Ident, ReturnType};
+
+/// Template for generating log statements with proper formatting.
+///
+/// This struct handles the creation of log message templates and generates
+/// the appropriate logging code based on the configuration.
+///
+/// # Examples
+///
+/// ```ignore
+/// use funlog::log_template::LogTemplate;
+/// use syn::{parse_quote, ReturnType, Ident};
+/// use quote::format_ident;
+///
+/// let params = vec![format_ident!("x"), format_ident!("y")];
+/// let return_type: ReturnType = parse_quote! { -> i32 };
+/// let template = LogTemplate::new("test_func", &params, &return_type, true);
+/// ```
+pub struct LogTemplate {
+    /// The name of the function being logged
+    pub function_name: String,
+    /// Template string for parameter formatting
+    pub parameters_placeholder: String,
+    /// Template string for return value formatting
+    pub return_placeholder: String,
+    /// Whether the function has parameters to log
+    pub has_parameters: bool,
+    /// Whether the function has a return value to log
+    pub has_return_value: bool,
+    /// Identifiers holding the pre-formatted values of `fields(...)` entries
+    pub field_values: Vec<Ident>,
+    /// Names of the logged parameters and fields, as reported in `CallEvent::params`
+    pub value_labels: Vec<String>,
+    /// The span of the return type in the signature, used to report a missing `Debug`
+    pub return_span: Span,
+    /// How logged values are formatted
+    pub format: FormatOptions,
+    /// Names of parameters whose values are logged as `<redacted>`
+    pub redact: Vec<String>,
+}
+
+impl LogTemplate {
+    /// Creates a new LogTemplate with the specified configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `function_name` - The name of the function to log
+    /// * `params_for_output` - The parameters to include in logging
+    /// * `return_type` - The return type of the function
+    /// * `output_ret_value` - Whether to include return value in logs
+    ///
+    /// # Returns
+    ///
+    /// Returns a new LogTemplate instance
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::log_template::LogTemplate;
+    /// use syn::{parse_quote, ReturnType};
+    /// use quote::format_ident;
+    ///
+    /// let params = vec![format_ident!("x")];
+    /// let return_type: ReturnType = parse_quote! { -> i32 };
+    /// let template = LogTemplate::new("test", &params, &return_type, true);
+    /// assert_eq!(template.function_name, "test");
+    /// assert!(template.has_parameters);
+    /// assert!(template.has_return_value);
+    /// ```
+    pub fn new(
+        function_name: &str,
+        params_for_output: &[Ident],
+        return_type: &ReturnType,
+        output_ret_value: bool,
+    ) -> Self {
+        let parameters_placeholder = params_for_output
+            .iter()
+            .map(|p| format!("{p}:{{}}"))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let (return_placeholder, return_span) = match return_type {
+            ReturnType::Default => (String::new(), Span::call_site()),
+            ReturnType::Type(_, ty) => ("return:{}".to_string(), ty.span()),
+        };
+
+        Self {
+            function_name: function_name.to_string(),
+            parameters_placeholder,
+            return_placeholder,
+            has_parameters: !params_for_output.is_empty(),
+            has_return_value: !matches!(return_type, ReturnType::Default) && output_ret_value,
+            field_values: Vec::new(),
+            value_labels: params_for_output.iter().map(Ident::to_string).collect(),
+            return_span,
+            format: FormatOptions::default(),
+            redact: Vec::new(),
+        }
+    }
+
+    /// Adds labelled `fields(...)` values after the parameters in the log output.
+    ///
+    /// Field values are formatted once on entry into the identifiers returned by
+    /// [`LogTemplate::field_value_name`], so the same value is reused by the end log.
+    ///
+    /// # Arguments
+    ///
+    /// * `labels` - The labels of the fields, in declaration order
+    ///
+    /// # Returns
+    ///
+    /// Returns the LogTemplate with the field placeholders appended
+    pub fn with_fields(mut self, labels: &[Ident]) -> Self {
+        for label in labels {
+            if !self.parameters_placeholder.is_empty() {
+                self.parameters_placeholder.push_str(", ");
+            }
+            self.parameters_placeholder
+                .push_str(&format!("{label}:{{}}"));
+            self.field_values.push(Self::field_value_name(label));
+            self.value_labels.push(label.to_string());
+        }
+        self.has_parameters = self.has_parameters || !labels.is_empty();
+        self
+    }
+
+    /// Sets how logged values are formatted and which parameters are redacted.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The formatting options from `funlog.toml`
+    /// * `redact` - Names of parameters whose values are logged as `<redacted>`
+    ///
+    /// # Returns
+    ///
+    /// Returns the LogTemplate with the options applied
+    pub fn with_format(mut self, format: &FormatOptions, redact: &[String]) -> Self {
+        self.format = format.clone();
+        self.redact = redact.to_vec();
+        self
+    }
+
+    /// Generates the expression that formats `value` with `Debug` as a `String`.
+    ///
+    /// The value is formatted with `{:#?}` when `pretty` is set, and cut to `max_len`
+    /// characters followed by `…` when it is longer.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - An expression for the value to format
+    ///
+    /// # Returns
+    ///
+    /// Returns an expression of type `String`
+    pub fn format_value(&self, value: TokenStream) -> TokenStream {
+        let spec = if self.format.pretty { "{:#?}" } else { "{:?}" };
+        match self.format.max_len {
+            Some(max_len) => quote! {
+                match format!(#spec, #value) {
+                    text => match text.char_indices().nth(#max_len) {
+                        Some((end, _)) => format!("{}…", &text[..end]),
+                        None => text,
+                    },
+                }
+            },
+            None => quote! { format!(#spec, #value) },
+        }
+    }
+
+    /// Returns the identifier that holds the formatted value of a field.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The label of the field
+    pub fn field_value_name(label: &Ident) -> Ident {
+        format_ident!("__funlog_field_{}__", label)
+    }
+
+    /// Returns the identifier that holds the formatted value of a parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - The logged parameter
+    pub fn param_value_name(param: &Ident) -> Ident {
+        format_ident!("__{}_value__", param)
+    }
+
+    /// Generates the `__FUNLOG_CALLSITE__` static and the statement that starts the
+    /// runtime `Call` shared by the entry and exit events, so it has to run before
+    /// anything else in the wrapper.
+    ///
+    /// # Returns
+    ///
+    /// Returns the static item followed by a `let __funlog_call__` statement
+    pub fn call_start(&self) -> TokenStream {
+        let function_name = &self.function_name;
+        quote! {
+            static __FUNLOG_CALLSITE__: ::funlog::__private::Callsite =
+                ::funlog::__private::Callsite {
+                    fn_name: #function_name,
+                    module: module_path!(),
+                    file: file!(),
+                    line: line!(),
+                };
+            let __funlog_call__ = ::funlog::__private::Call::start(&__FUNLOG_CALLSITE__);
+        }
+    }
+
+    /// Returns the name of the trait that formats a logged parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - The parameter being logged
+    pub fn debug_trait_name(param: &Ident) -> Ident {
+        format_ident!("__FunlogDebug_{}", param)
+    }
+
+    /// Generates the formatting traits that check logged values implement `Debug`.
+    ///
+    /// Each logged parameter, and the return value when it is logged, is formatted
+    /// through its own trait with a blanket implementation for `Debug` types. A value
+    /// that is not `Debug` therefore fails with a single `on_unimplemented` message
+    /// naming the parameter, instead of an error inside the generated `format!` code.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The logged parameters, spanned on the signature
+    /// * `output_position` - When logs are emitted; the return value is only checked
+    ///   when an end log prints it
+    ///
+    /// # Returns
+    ///
+    /// Returns the trait items, to be placed at the top level of the wrapper body
+    pub fn debug_checks(&self, params: &[Ident], output_position: &OutputPosition) -> TokenStream {
+        let function_name = &self.function_name;
+        let param_checks = params.iter().map(|param| {
+            let trait_name = Self::debug_trait_name(param);
+            let param = param.to_string();
+            if self.redact.contains(&param) {
+                return Self::redacted_trait(&trait_name);
+            }
+            self.debug_trait(
+                &trait_name,
+                &format!("`{param}` cannot be logged by #[funlog]: `{{Self}}` does not implement `Debug`"),
+                &format!("parameter `{param}` of `{function_name}` is logged here"),
+                &format!("implement or derive `Debug` for `{{Self}}`, or leave `{param}` out of the log with `params(...)` or `none`"),
+            )
+        });
+
+        let logs_return =
+            self.has_return_value && !matches!(output_position, OutputPosition::OnStart);
+        let return_check = logs_return.then(|| {
+            self.debug_trait(
+                &format_ident!("__FunlogDebugReturn"),
+                &format!("the return value of `{function_name}` cannot be logged by #[funlog]: `{{Self}}` does not implement `Debug`"),
+                "the return value is logged because of `retVal`",
+                "implement or derive `Debug` for `{Self}`, or remove `retVal`",
+            )
+        });
+
+        quote! {
+            #(#param_checks)*
+            #return_check
+        }
+    }
+
+    /// Generates one formatting trait with its blanket `Debug` implementation.
+    fn debug_trait(
+        &self,
+        trait_name: &Ident,
+        message: &str,
+        label: &str,
+        note: &str,
+    ) -> TokenStream {
+        let format_value = self.format_value(quote! { self });
+        quote! {
+            #[allow(non_camel_case_types)]
+            #[diagnostic::on_unimplemented(message = #message, label = #label, note = #note)]
+            trait #trait_name {
+                fn __funlog_format(&self) -> String;
+            }
+
+            impl<T: ?Sized + ::core::fmt::Debug> #trait_name for T {
+                fn __funlog_format(&self) -> String {
+                    #format_value
+                }
+            }
+        }
+    }
+
+    /// Generates a formatting trait for a redacted parameter, which need not be `Debug`.
+    fn redacted_trait(trait_name: &Ident) -> TokenStream {
+        quote! {
+            #[allow(non_camel_case_types)]
+            trait #trait_name {
+                fn __funlog_format(&self) -> String;
+            }
+
+            impl<T: ?Sized> #trait_name for T {
+                fn __funlog_format(&self) -> String {
+                    String::from("<redacted>")
+                }
+            }
+        }
+    }
+
+    /// Generates the expression that formats a logged parameter.
+    ///
+    /// The expression is spanned on the parameter so a missing `Debug` implementation
+    /// is reported on the signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - The parameter being logged
+    pub fn format_param(param: &Ident) -> TokenStream {
+        let trait_name = Self::debug_trait_name(param);
+        quote_spanned! {param.span()=>
+            #trait_name::__funlog_format(&#param)
+        }
+    }
+
+    /// Generates the expression that formats the return value held in `output`.
+    fn format_return(&self) -> TokenStream {
+        quote_spanned! {self.return_span=>
+            __FunlogDebugReturn::__funlog_format(&output)
+        }
+    }
+
+    /// Formats the template string for function start logging.
+    ///
+    /// # Returns
+    ///
+    /// Returns a formatted string template for the start of function execution
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::log_template::LogTemplate;
+    /// use syn::{parse_quote, ReturnType};
+    /// use quote::format_ident;
+    ///
+    /// let params = vec![format_ident!("x")];
+    /// let return_type: ReturnType = parse_quote! { -> i32 };
+    /// let template = LogTemplate::new("test", &params, &return_type, false);
+    /// let start_template = template.format_start_template();
+    /// assert!(start_template.contains("test [in ]"));
+    /// ```
+    pub fn format_start_template(&self) -> String {
+        if self.has_parameters {
+            format!(
+                "{} [in ]: {}",
+                self.function_name, self.parameters_placeholder
+            )
+        } else {
+            format!("{} [in ]", self.function_name)
+        }
+    }
+
+    /// Formats the template string for function end logging.
+    ///
+    /// # Arguments
+    ///
+    /// * `include_params` - Whether to include parameters in the end template
+    ///
+    /// # Returns
+    ///
+    /// Returns a formatted string template for the end of function execution
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::log_template::LogTemplate;
+    /// use syn::{parse_quote, ReturnType};
+    /// use quote::format_ident;
+    ///
+    /// let params = vec![format_ident!("x")];
+    /// let return_type: ReturnType = parse_quote! { -> i32 };
+    /// let template = LogTemplate::new("test", &params, &return_type, true);
+    /// let end_template = template.format_end_template(true);
+    /// assert!(end_template.contains("test [out]"));
+    /// ```
+    pub fn format_end_template(&self, include_params: bool) -> String {
+        match (include_params && self.has_parameters, self.has_return_value) {
+            (true, true) => format!(
+                "{} [out]: {}, {}",
+                self.function_name, self.parameters_placeholder, self.return_placeholder
+            ),
+            (true, false) => format!(
+                "{} [out]: {}",
+                self.function_name, self.parameters_placeholder
+            ),
+            (false, true) => format!("{} [out]: {}", self.function_name, self.return_placeholder),
+            (false, false) => format!("{} [out]", self.function_name),
+        }
+    }
+
+    /// Generates the actual log statements as TokenStreams.
+    ///
+    /// Parameter and field values are formatted beforehand into `param_values` and
+    /// [`LogTemplate::field_values`]; each statement passes them to the runtime both as
+    /// the `CallEvent` parameters and as the arguments of the message.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_position` - When to output logs (start, end, or both)
+    /// * `start_output_type` - What type of logging to use for the entry statement
+    /// * `end_output_type` - What type of logging to use for the exit statement
+    /// * `param_values` - The identifiers holding the formatted parameter values
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple of (start_statement, end_statement) TokenStreams
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::log_template::LogTemplate;
+    /// use funlog::config::{OutputPosition, OutputType};
+    /// use syn::{parse_quote, ReturnType};
+    /// use quote::format_ident;
+    ///
+    /// let params = vec![format_ident!("x")];
+    /// let return_type: ReturnType = parse_quote! { -> i32 };
+    /// let template = LogTemplate::new("test", &params, &return_type, false);
+    /// let (start, end) = template.generate_log_statements_with_context(
+    ///     &OutputPosition::OnStart,
+    ///     &OutputType::Print,
+    ///     &OutputType::Print,
+    ///     &[format_ident!("__x_value__")],
+    /// );
+    /// ```
+    pub fn generate_log_statements_with_context(
+        &self,
+        output_position: &OutputPosition,
+        start_output_type: &OutputType,
+        end_output_type: &OutputType,
+        param_values: &[Ident],
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let start_method = self.get_log_method(start_output_type, quote! { Enter });
+        let end_method = self.get_log_method(end_output_type, quote! { Exit });
+        let values = param_values
+            .iter()
+            .chain(&self.field_values)
+            .collect::<Vec<_>>();
+        let labels = &self.value_labels;
+        let event_params = if self.has_parameters {
+            quote! { &[#((#labels, &*#values),)*] }
+        } else {
+            quote! { &[] }
+        };
+
+        let start_statement = match output_position {
+            OutputPosition::OnStart | OutputPosition::OnStartAndEnd => {
+                let template = self.format_start_template();
+                let args = if self.has_parameters {
+                    &values[..]
+                } else {
+                    &[]
+                };
+                quote! {
+                    ::funlog::__private::emit(#start_method, #event_params, None, format_args!(#template, #(#args,)*));
+                }
+            }
+            _ => quote! {},
+        };
+
+        // For OnEnd the parameters are part of the end log; for OnStartAndEnd they were
+        // already logged at start, so only the return value is
+        let (template, args, end_params) = match output_position {
+            OutputPosition::OnEnd if self.has_parameters => {
+                (self.format_end_template(true), &values[..], event_params)
+            }
+            OutputPosition::OnEnd => (self.format_end_template(true), &[][..], quote! { &[] }),
+            _ => (self.format_end_template(false), &[][..], quote! { &[] }),
+        };
+        let end_statement = match output_position {
+            OutputPosition::OnEnd | OutputPosition::OnStartAndEnd if self.has_return_value => {
+                let format_return = self.format_return();
+                quote! {
+                    {
+                        let __funlog_ret__ = #format_return;
+                        ::funlog::__private::emit(#end_method, #end_params, Some(&*__funlog_ret__), format_args!(#template, #(#args,)* __funlog_ret__));
+                    }
+                }
+            }
+            OutputPosition::OnEnd | OutputPosition::OnStartAndEnd => quote! {
+                ::funlog::__private::emit(#end_method, #end_params, None, format_args!(#template, #(#args,)*));
+            },
+            _ => quote! {},
+        };
+
+        (start_statement, end_statement)
+    }
+
+    /// Gets the leading arguments of the runtime `emit` call for an output type.
+    ///
+    /// Generated code emits events through `::funlog::__private::emit`, passing the
+    /// call started by [`LogTemplate::call_start`], the level and whether the event is
+    /// an entry or exit; the caller appends the values and the formatted message.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_type` - The type of output to generate method for
+    /// * `kind` - The `EventKind` variant, `Enter` or `Exit`
+    ///
+    /// # Returns
+    ///
+    /// Returns the call, level and kind arguments as a TokenStream
+    fn get_log_method(
+        &self,
+        output_type: &OutputType,
+        kind: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let level = Self::level(output_type);
+        quote! {
+            &__funlog_call__,
+            #level,
+            ::funlog::__private::EventKind::#kind
+        }
+    }
+
+    /// Returns the runtime `Level` for an output type.
+    fn level(output_type: &OutputType) -> proc_macro2::TokenStream {
+        let level = match output_type {
+            OutputType::Debug => quote! { Debug },
+            OutputType::Info => quote! { Info },
+            OutputType::Warn => quote! { Warn },
+            OutputType::Error => quote! { Error },
+            OutputType::Trace => quote! { Trace },
+            OutputType::Print => quote! { Print },
+        };
+        quote! { ::funlog::__private::Level::#level }
+    }
+
+    /// Generates the check of whether anything would receive the call's events.
+    ///
+    /// It is evaluated once on entry; when it is false the values are not formatted
+    /// and neither event is emitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_position` - Which events the call emits
+    /// * `start_output_type` - The level of the entry event
+    /// * `end_output_type` - The level of the exit event
+    ///
+    /// # Returns
+    ///
+    /// Returns a `bool` expression calling `::funlog::__private::is_active`
+    pub fn is_active(
+        &self,
+        output_position: &OutputPosition,
+        start_output_type: &OutputType,
+        end_output_type: &OutputType,
+    ) -> proc_macro2::TokenStream {
+        let levels = match output_position {
+            OutputPosition::OnStart => vec![Self::level(start_output_type)],
+            OutputPosition::OnEnd => vec![Self::level(end_output_type)],
+            OutputPosition::OnStartAndEnd if start_output_type == end_output_type => {
+                vec![Self::level(start_output_type)]
+            }
+            OutputPosition::OnStartAndEnd => {
+                vec![Self::level(start_output_type), Self::level(end_output_type)]
+            }
+        };
+        quote! { ::funlog::__private::is_active(&__funlog_call__, &[#(#levels),*]) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::format_ident;
+    use syn::{parse_quote, ReturnType};
+
+    #[test]
+    fn test_new_with_parameters_and_return() {
+        let params = vec![format_ident!("x"), format_ident!("y")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+
+        assert_eq!(template.function_name, "test_func");
+        assert_eq!(template.parameters_placeholder, "x:{}, y:{}");
+        assert_eq!(template.return_placeholder, "return:{}");
+        assert!(template.has_parameters);
+        assert!(template.has_return_value);
+    }
+
+    #[test]
+    fn test_new_no_parameters_no_return() {
+        let params = vec![];
+        let return_type: ReturnType = parse_quote! {};
+
+        let template = LogTemplate::new("test_func", &params, &return_type, false);
+
+        assert_eq!(template.function_name, "test_func");
+        assert_eq!(template.parameters_placeholder, "");
+        assert_eq!(template.return_placeholder, "");
+        assert!(!template.has_parameters);
+        assert!(!template.has_return_value);
+    }
+
+    #[test]
+    fn test_new_with_parameters_no_return() {
+        let params = vec![format_ident!("x")];
+        let return_type: ReturnType = parse_quote! {};
+
+        let template = LogTemplate::new("test_func", &params, &return_type, false);
+
+        assert_eq!(template.parameters_placeholder, "x:{}");
+        assert!(template.has_parameters);
+        assert!(!template.has_return_value);
+    }
+
+    #[test]
+    fn test_new_no_parameters_with_return() {
+        let params = vec![];
+        let return_type: ReturnType = parse_quote! { -> String };
+
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+
+        assert!(!template.has_parameters);
+        assert!(template.has_return_value);
+        assert_eq!(template.return_placeholder, "return:{}");
+    }
+
+    #[test]
+    fn test_format_start_template_with_params() {
+        let params = vec![format_ident!("x"), format_ident!("y")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+        let start_template = template.format_start_template();
+
+        assert_eq!(start_template, "test_func [in ]: x:{}, y:{}");
+    }
+
+    #[test]
+    fn test_format_start_template_no_params() {
+        let params = vec![];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+        let start_template = template.format_start_template();
+
+        assert_eq!(start_template, "test_func [in ]");
+    }
+
+    #[test]
+    fn test_format_end_template_with_params_and_return() {
+        let params = vec![format_ident!("x")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+        let end_template = template.format_end_template(true);
+
+        assert_eq!(end_template, "test_func [out]: x:{}, return:{}");
+    }
+
+    #[test]
+    fn test_format_end_template_with_params_no_return() {
+        let params = vec![format_ident!("x")];
+        let return_type: ReturnType = parse_quote! {};
+
+        let template = LogTemplate::new("test_func", &params, &return_type, false);
+        let end_template = template.format_end_template(true);
+
+        assert_eq!(end_template, "test_func [out]: x:{}");
+    }
+
+    #[test]
+    fn test_format_end_template_no_params_with_return() {
+        let params = vec![];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+        let end_template = template.format_end_template(false);
+
+        assert_eq!(end_template, "test_func [out]: return:{}");
+    }
+
+    #[test]
+    fn test_format_end_template_no_params_no_return() {
+        let params = vec![];
+        let return_type: ReturnType = parse_quote! {};
+
+        let template = LogTemplate::new("test_func", &params, &return_type, false);
+        let end_template = template.format_end_template(false);
+
+        assert_eq!(end_template, "test_func [out]");
+    }
+
+    #[test]
+    fn test_get_log_method() {
+        let template = LogTemplate::new("test", &[], &parse_quote! {}, false);
+
+        // Test all output types
+        let debug_method = template.get_log_method(&OutputType::Debug, quote! { Enter });
+        let info_method = template.get_log_method(&OutputType::Info, quote! { Enter });
+        let warn_method = template.get_log_method(&OutputType::Warn, quote! { Enter });
+        let error_method = template.get_log_method(&OutputType::Error, quote! { Enter });
+        let trace_method = template.get_log_method(&OutputType::Trace, quote! { Enter });
+        let print_method = template.get_log_method(&OutputType::Print, quote! { Enter });
+
+        // We can't easily test the exact token content, but we can verify
+        // that the methods return non-empty token streams
+        assert!(!debug_method.is_empty());
+        assert!(!info_method.is_empty());
+        assert!(!warn_method.is_empty());
+        assert!(!error_method.is_empty());
+        assert!(!trace_method.is_empty());
+        assert!(!print_method.is_empty());
+
+        let debug_method = debug_method.to_string();
+        assert!(debug_method.starts_with("& __funlog_call__"));
+        assert!(debug_method.contains(":: funlog :: __private :: Level :: Debug"));
+        assert!(debug_method.ends_with(":: funlog :: __private :: EventKind :: Enter"));
+    }
+
+    #[test]
+    fn test_is_active() {
+        let template = LogTemplate::new("f", &[], &ReturnType::Default, false);
+        let levels = |position, start, end| template.is_active(&position, &start, &end).to_string();
+        let single = levels(
+            OutputPosition::OnStartAndEnd,
+            OutputType::Info,
+            OutputType::Info,
+        );
+        assert_eq!(
+            single,
+            ":: funlog :: __private :: is_active (& __funlog_call__ , & [:: funlog :: __private :: Level :: Info])"
+        );
+        let both = levels(
+            OutputPosition::OnStartAndEnd,
+            OutputType::Trace,
+            OutputType::Warn,
+        );
+        assert!(both.contains("Level :: Trace , :: funlog :: __private :: Level :: Warn"));
+        let end = levels(OutputPosition::OnEnd, OutputType::Trace, OutputType::Warn);
+        assert!(!end.contains("Trace") && end.contains("Warn"));
+    }
+
+    #[test]
+    fn test_call_start() {
+        let template = LogTemplate::new("test", &[], &parse_quote! {}, false);
+        let call_start = template.call_start().to_string();
+        assert!(call_start
+            .starts_with("static __FUNLOG_CALLSITE__ : :: funlog :: __private :: Callsite"));
+        assert!(call_start.ends_with(
+            "let __funlog_call__ = :: funlog :: __private :: Call :: start (& __FUNLOG_CALLSITE__) ;"
+        ));
+        assert!(call_start.contains("fn_name : \"test\""));
+        assert!(call_start.contains("line : line ! ()"));
+    }
+
+    #[test]
+    fn test_generate_log_statements_on_start() {
+        let params = vec![format_ident!("x")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+
+        let (start, end) = template.generate_log_statements_with_context(
+            &OutputPosition::OnStart,
+            &OutputType::Print,
+            &OutputType::Print,
+            &[format_ident!("__x_value__")],
+        );
+
+        assert!(!start.is_empty());
+        assert!(start.to_string().contains(
+            "& [(\"x\" , & * __x_value__) ,] , None , format_args ! (\"test_func [in ]: x:{}\" , __x_value__ ,)"
+        ));
+        assert!(end.is_empty());
+    }
+
+    #[test]
+    fn test_generate_log_statements_on_end() {
+        let params = vec![format_ident!("x")];
+        let saved_params = vec![format_ident!("__x_value__")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+
+        let (start, end) = template.generate_log_statements_with_context(
+            &OutputPosition::OnEnd,
+            &OutputType::Print,
+            &OutputType::Print,
+            &saved_params,
+        );
+
+        assert!(start.is_empty());
+        let end = end.to_string();
+        assert!(
+            end.contains("let __funlog_ret__ = __FunlogDebugReturn :: __funlog_format (& output)")
+        );
+        assert!(end.contains("& [(\"x\" , & * __x_value__) ,] , Some (& * __funlog_ret__)"));
+        assert!(end.contains(
+            "format_args ! (\"test_func [out]: x:{}, return:{}\" , __x_value__ , __funlog_ret__)"
+        ));
+    }
+
+    #[test]
+    fn test_generate_log_statements_on_start_and_end() {
+        let params = vec![format_ident!("x")];
+        let saved_params = vec![format_ident!("__x_value__")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+
+        let (start, end) = template.generate_log_statements_with_context(
+            &OutputPosition::OnStartAndEnd,
+            &OutputType::Print,
+            &OutputType::Print,
+            &saved_params,
+        );
+
+        assert!(!start.is_empty());
+        assert!(!end.is_empty());
+    }
+
+    #[test]
+    fn test_generate_log_statements_no_params_no_return() {
+        let params = vec![];
+        let return_type: ReturnType = parse_quote! {};
+        let template = LogTemplate::new("test_func", &params, &return_type, false);
+
+        let (start, end) = template.generate_log_statements_with_context(
+            &OutputPosition::OnStartAndEnd,
+            &OutputType::Debug,
+            &OutputType::Debug,
+            &[],
+        );
+
+        assert!(!start.is_empty());
+        assert!(!end.is_empty());
+    }
+
+    #[test]
+    fn test_with_fields() {
+        let params = vec![format_ident!("x")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+
+        let template = LogTemplate::new("test_func", &params, &return_type, false)
+            .with_fields(&[format_ident!("id"), format_ident!("n")]);
+
+        assert_eq!(template.parameters_placeholder, "x:{}, id:{}, n:{}");
+        assert_eq!(
+            template.format_start_template(),
+            "test_func [in ]: x:{}, id:{}, n:{}"
+        );
+        assert_eq!(template.field_values[0].to_string(), "__funlog_field_id__");
+
+        let (start, _) = template.generate_log_statements_with_context(
+            &OutputPosition::OnStart,
+            &OutputType::Print,
+            &OutputType::Print,
+            &[format_ident!("__x_value__")],
+        );
+        let start = start.to_string();
+        assert!(start.contains("__x_value__ , __funlog_field_id__ , __funlog_field_n__"));
+        assert!(start.contains("(\"id\" , & * __funlog_field_id__)"));
+    }
+
+    #[test]
+    fn test_with_fields_only() {
+        let params = vec![];
+        let return_type: ReturnType = parse_quote! {};
+
+        let template = LogTemplate::new("test_func", &params, &return_type, false)
+            .with_fields(&[format_ident!("id")]);
+
+        assert!(template.has_parameters);
+        assert_eq!(template.format_end_template(true), "test_func [out]: id:{}");
+    }
+
+    #[test]
+    fn test_debug_checks() {
+        let params = vec![format_ident!("x")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+
+        let checks = template
+            .debug_checks(&params, &OutputPosition::OnStartAndEnd)
+            .to_string();
+        assert!(checks.contains("trait __FunlogDebug_x"));
+        assert!(checks.contains("parameter `x` of `test_func` is logged here"));
+        assert!(checks.contains("trait __FunlogDebugReturn"));
+        assert!(checks.contains("or remove `retVal`"));
+
+        // The return value is never printed when only the start is logged
+        let checks = template
+            .debug_checks(&params, &OutputPosition::OnStart)
+            .to_string();
+        assert!(!checks.contains("__FunlogDebugReturn"));
+
+        assert_eq!(
+            LogTemplate::format_param(&params[0]).to_string(),
+            "__FunlogDebug_x :: __funlog_format (& x)"
+        );
+    }
+
+    #[test]
+    fn test_format_options_and_redaction() {
+        let params = vec![format_ident!("user"), format_ident!("password")];
+        let return_type: ReturnType = parse_quote! { -> bool };
+        let template = LogTemplate::new("login", &params, &return_type, false);
+
+        assert_eq!(
+            template.format_value(quote! { self }).to_string(),
+            "format ! (\"{:?}\" , self)"
+        );
+
+        let format = FormatOptions {
+            pretty: true,
+            max_len: Some(8),
+        };
+        let template = template.with_format(&format, &["password".to_string()]);
+        let formatted = template.format_value(quote! { self }).to_string();
+        assert!(formatted.contains("format ! (\"{:#?}\" , self)"));
+        assert!(formatted.contains("char_indices () . nth (8usize)"));
+
+        // A redacted parameter is never formatted, so it does not need `Debug`
+        let checks = template
+            .debug_checks(&params, &OutputPosition::OnStartAndEnd)
+            .to_string();
+        assert!(checks.contains("parameter `user` of `login` is logged here"));
+        assert!(!checks.contains("parameter `password`"));
+        assert!(checks.contains("impl < T : ? Sized > __FunlogDebug_password for T"));
+        assert!(checks.contains("String :: from (\"<redacted>\")"));
+    }
+
+    #[test]
+    fn test_generate_log_statements_with_phase_levels() {
+        let params = vec![format_ident!("x")];
+        let return_type: ReturnType = parse_quote! { -> i32 };
+        let template = LogTemplate::new("test_func", &params, &return_type, true);
+
+        let (start, end) = template.generate_log_statements_with_context(
+            &OutputPosition::OnStartAndEnd,
+            &OutputType::Trace,
+            &OutputType::Info,
+            &[],
+        );
+        assert!(start.to_string().contains("Level :: Trace"));
+        assert!(start.to_string().contains("EventKind :: Enter"));
+        assert!(end.to_string().contains("Level :: Info"));
+        assert!(end.to_string().contains("EventKind :: Exit"));
+    }
+}
@@ -0,0 +1,149 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+/// Represents the generated output code components for a function with logging.
+///
+/// This struct contains all the TokenStream components needed to generate
+/// the final function with logging capabilities.
+///
+/// # Examples
+///
+/// ```ignore
+/// use funlog::output::Output;
+/// use quote::quote;
+///
+/// let output = Output {
+///     func_declare_start: quote! { fn test() },
+///     func_output_start: quote! { println!("start"); },
+///     func_declare_body: quote! { let output = (move || { 42 })(); },
+///     func_output_end: quote! { println!("end"); },
+///     func_declare_end: quote! { output },
+/// };
+/// ```
+pub struct Output {
+    /// The start of the outer function declaration
+    pub func_declare_start: TokenStream2,
+    /// The logging output at function start
+    pub func_output_start: TokenStream2,
+    /// The body of the outer function (runs the original body)
+    pub func_declare_body: TokenStream2,
+    /// The logging output at function end
+    pub func_output_end: TokenStream2,
+    /// The end of the outer function (return statement)
+    pub func_declare_end: TokenStream2,
+}
+
+impl From<Output> for TokenStream2 {
+    /// Converts the Output struct into a final TokenStream for the macro.
+    ///
+    /// This method combines all the output components into a single TokenStream
+    /// that represents the complete function with logging.
+    ///
+    /// # Arguments
+    ///
+    /// * `val` - The Output struct to convert
+    ///
+    /// # Returns
+    ///
+    /// Returns a TokenStream containing the complete generated code
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use funlog::output::Output;
+    /// use proc_macro2::TokenStream;
+    /// use quote::quote;
+    ///
+    /// let output = Output {
+    ///     func_declare_start: quote! { fn test() },
+    ///     func_output_start: quote! {},
+    ///     func_declare_body: quote! { let output = (move || { 42 })(); },
+    ///     func_output_end: quote! {},
+    ///     func_declare_end: quote! { output },
+    /// };
+    /// let token_stream: TokenStream = output.into();
+    /// ```
+    fn from(val: Output) -> Self {
+        let Output {
+            func_declare_start,
+            func_output_start,
+            func_declare_body,
+            func_output_end,
+            func_declare_end,
+        } = val;
+        quote! {
+            #func_declare_start {
+                #func_output_start
+                #func_declare_body
+                #func_output_end
+                #func_declare_end
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Converts `output` and returns the tokens as a string.
+    fn tokens(output: Output) -> String {
+        TokenStream2::from(output).to_string()
+    }
+
+    #[test]
+    fn test_output_into_tokens() {
+        let output = Output {
+            func_declare_start: quote! { fn test() -> i32 },
+            func_output_start: quote! { println!("start"); },
+            func_declare_body: quote! { let output = 42; },
+            func_output_end: quote! { println!("end"); },
+            func_declare_end: quote! { output },
+        };
+        assert_eq!(
+            tokens(output),
+            "fn test () -> i32 { println ! (\"start\") ; let output = 42 ; \
+             println ! (\"end\") ; output }"
+        );
+    }
+
+    #[test]
+    fn test_output_with_empty_components() {
+        let output = Output {
+            func_declare_start: quote! { fn empty() },
+            func_output_start: quote! {},
+            func_declare_body: quote! { let output = (); },
+            func_output_end: quote! {},
+            func_declare_end: quote! { output },
+        };
+        assert_eq!(tokens(output), "fn empty () { let output = () ; output }");
+    }
+
+    #[test]
+    fn test_output_with_logging() {
+        let output = Output {
+            func_declare_start: quote! {
+                fn check(x: i32, y: String) -> Result<i32, String>
+            },
+            func_output_start: quote! {
+                let __x_value__ = format!("{:?}", x);
+                log::debug!("check [in ]: x:{}", __x_value__);
+            },
+            func_declare_body: quote! {
+                let output: Result<i32, String> = if x > 0 { Ok(x) } else { Err(y) };
+            },
+            func_output_end: quote! {
+                log::debug!("check [out]: return:{:?}", output);
+            },
+            func_declare_end: quote! { output },
+        };
+        assert_eq!(
+            tokens(output),
+            "fn check (x : i32 , y : String) -> Result < i32 , String > { \
+             let __x_value__ = format ! (\"{:?}\" , x) ; \
+             log :: debug ! (\"check [in ]: x:{}\" , __x_value__) ; \
+             let output : Result < i32 , String > = if x > 0 { Ok (x) } else { Err (y) } ; \
+             log :: debug ! (\"check [out]: return:{:?}\" , output) ; output }"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{FnArg, ItemFn, LitStr, ReturnType, Token};
+
+/// The arguments of
+/// `#[funlog::replay_test("cassette.jsonl", module = "app", name = "add")]`.
+pub(crate) struct ReplayArgs {
+    /// The cassette, relative to the crate directory
+    path: LitStr,
+    /// The module of the recorded function, when it does not contain the test
+    module: Option<LitStr>,
+    /// The recorded function to replay, when it is not the test's name
+    name: Option<LitStr>,
+}
+
+impl Parse for ReplayArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse::<LitStr>()?;
+        let mut module = None;
+        let mut name = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            let option = if key == "module" {
+                &mut module
+            } else if key == "name" {
+                &mut name
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "unknown replay_test option, expected `module = \"path\"` or \
+                     `name = \"function\"`",
+                ));
+            };
+            if option.is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("`{key}` is set more than once"),
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            *option = Some(input.parse::<LitStr>()?);
+        }
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the replay_test options"));
+        }
+        Ok(Self { path, module, name })
+    }
+}
+
+/// Expands a replay test into a `#[test]` that calls the annotated function with the
+/// arguments of each recorded call and compares the return value with the recording.
+///
+/// The recorded function defaults to the test's name without a `replay_` prefix, and
+/// its module to the test's module, which the runtime widens to the nearest module
+/// around it with recorded calls.
+///
+/// # Arguments
+///
+/// * `args` - The cassette and options from the attribute
+/// * `func` - The test function, taking the recorded arguments in order
+///
+/// # Returns
+///
+/// Returns the test, or an error on the test's signature if it cannot be replayed
+pub(crate) fn expand(args: ReplayArgs, func: ItemFn) -> syn::Result<TokenStream2> {
+    let sig = &func.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span,
+            "replay tests cannot be async; call the async function from a runtime in the body",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "replay tests cannot be generic; name the concrete argument types",
+        ));
+    }
+    let mut arg_types = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Typed(pat_type) => arg_types.push(&*pat_type.ty),
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "replay tests take the recorded arguments, not `self`",
+                ))
+            }
+        }
+    }
+    let arg_names = (0..arg_types.len())
+        .map(|index| format_ident!("__funlog_arg_{}__", index))
+        .collect::<Vec<_>>();
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = &func;
+    let test_name = &sig.ident;
+    let name = match &args.name {
+        Some(name) => name.value(),
+        None => {
+            let test_name = test_name.to_string();
+            test_name
+                .strip_prefix("replay_")
+                .unwrap_or(&test_name)
+                .to_string()
+        }
+    };
+    let path = &args.path;
+    let module = match &args.module {
+        Some(module) => quote! { #module },
+        None => quote! { ::core::module_path!() },
+    };
+    let inputs = &sig.inputs;
+    let output = match &sig.output {
+        ReturnType::Default => quote! {},
+        output => quote! { #output },
+    };
+    let unsafety = &sig.unsafety;
+    let call = match unsafety {
+        Some(_) => quote! { unsafe { __funlog_replay__(#(#arg_names),*) } },
+        None => quote! { __funlog_replay__(#(#arg_names),*) },
+    };
+
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #test_name() {
+            ::funlog::__private::require_record!("#[replay_test]");
+            #unsafety fn __funlog_replay__(#inputs) #output #block
+            ::funlog::__private::replay(
+                env!("CARGO_MANIFEST_DIR"),
+                #path,
+                #module,
+                #name,
+                |(#(#arg_names,)*): (#(#arg_types,)*)| #call,
+            );
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_expand() {
+        let args: ReplayArgs = syn::parse_quote! { "tests/add.jsonl" };
+        let func: ItemFn = parse_quote! {
+            fn replay_add(x: i32, mut y: i32) -> i32 {
+                y += x;
+                y
+            }
+        };
+        let output = expand(args, func).unwrap().to_string();
+        assert!(output.starts_with(
+            "# [test] fn replay_add () { :: funlog :: __private :: require_record ! (\"#[replay_test]\") ;"
+        ));
+        assert!(output.contains("fn __funlog_replay__ (x : i32 , mut y : i32) -> i32"));
+        assert!(output.contains(
+            ":: funlog :: __private :: replay (env ! (\"CARGO_MANIFEST_DIR\") , \"tests/add.jsonl\" , :: core :: module_path ! () , \"add\" ,"
+        ));
+        assert!(output.contains(
+            "| (__funlog_arg_0__ , __funlog_arg_1__ ,) : (i32 , i32 ,) | __funlog_replay__ (__funlog_arg_0__ , __funlog_arg_1__)"
+        ));
+    }
+
+    #[test]
+    fn test_expand_named_without_args() {
+        let args: ReplayArgs =
+            syn::parse_quote! { "tests/now.jsonl", name = "now", module = "app::clock", };
+        let func: ItemFn = parse_quote! {
+            fn clock() -> u64 { 7 }
+        };
+        let output = expand(args, func).unwrap().to_string();
+        assert!(output.contains("\"tests/now.jsonl\" , \"app::clock\" , \"now\" , | () : () |"));
+    }
+
+    #[test]
+    fn test_invalid_replay_tests() {
+        let args = || -> ReplayArgs {
+            syn::parse_quote! { "a.jsonl" }
+        };
+        for func in [
+            parse_quote! { async fn replay_a(x: u8) -> u8 { x } },
+            parse_quote! { fn replay_a<T>(x: T) -> T { x } },
+            parse_quote! { fn replay_a(&self) {} },
+        ] {
+            assert!(expand(args(), func).is_err());
+        }
+        assert!(syn::parse_str::<ReplayArgs>(r#""a.jsonl", fn_name = "a""#).is_err());
+        assert!(syn::parse_str::<ReplayArgs>(r#""a.jsonl", name = "a", name = "b""#).is_err());
+        assert!(syn::parse_str::<ReplayArgs>(r#""a.jsonl" name = "a""#).is_err());
+        assert!(syn::parse_str::<ReplayArgs>("a").is_err());
+    }
+}
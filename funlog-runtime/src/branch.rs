@@ -0,0 +1,167 @@
+//! The `?` operator on stable Rust, for `#[track_caller]` bodies that run inside the
+//! generated wrapper and leave it with `break` instead of `return`.
+//!
+//! `std::ops::Try` is unstable, so these traits cover the types `?` works on in stable
+//! code: `Result`, `Option`, `Poll<Result<..>>`, `Poll<Option<Result<..>>>` and
+//! `ControlFlow`, converting errors with `From` like `?` does.
+
+use std::convert::Infallible;
+use std::ops::ControlFlow;
+use std::task::Poll;
+
+/// Splits a value into the output `?` continues with and the residual it returns.
+pub trait Branch {
+    /// The value `?` evaluates to
+    type Output;
+    /// The value `?` returns early with, converted by [`FromResidual`]
+    type Residual;
+
+    /// Returns `Continue` with the output or `Break` with the residual.
+    fn branch(self) -> ControlFlow<Self::Residual, Self::Output>;
+}
+
+/// Builds the return value of a function from the residual of a `?`.
+pub trait FromResidual<R> {
+    /// Returns the early return value for `residual`.
+    ///
+    /// # Arguments
+    ///
+    /// * `residual` - The residual of the value `?` was applied to
+    fn from_residual(residual: R) -> Self;
+}
+
+impl<T, E> Branch for Result<T, E> {
+    type Output = T;
+    type Residual = Result<Infallible, E>;
+
+    fn branch(self) -> ControlFlow<Self::Residual, T> {
+        match self {
+            Ok(value) => ControlFlow::Continue(value),
+            Err(e) => ControlFlow::Break(Err(e)),
+        }
+    }
+}
+
+impl<T, E, F: From<E>> FromResidual<Result<Infallible, E>> for Result<T, F> {
+    fn from_residual(residual: Result<Infallible, E>) -> Self {
+        match residual {
+            Err(e) => Err(From::from(e)),
+            Ok(never) => match never {},
+        }
+    }
+}
+
+impl<T> Branch for Option<T> {
+    type Output = T;
+    type Residual = Option<Infallible>;
+
+    fn branch(self) -> ControlFlow<Self::Residual, T> {
+        match self {
+            Some(value) => ControlFlow::Continue(value),
+            None => ControlFlow::Break(None),
+        }
+    }
+}
+
+impl<T> FromResidual<Option<Infallible>> for Option<T> {
+    fn from_residual(_: Option<Infallible>) -> Self {
+        None
+    }
+}
+
+impl<T, E> Branch for Poll<Result<T, E>> {
+    type Output = Poll<T>;
+    type Residual = Result<Infallible, E>;
+
+    fn branch(self) -> ControlFlow<Self::Residual, Poll<T>> {
+        match self {
+            Poll::Ready(Ok(value)) => ControlFlow::Continue(Poll::Ready(value)),
+            Poll::Ready(Err(e)) => ControlFlow::Break(Err(e)),
+            Poll::Pending => ControlFlow::Continue(Poll::Pending),
+        }
+    }
+}
+
+impl<T, E, F: From<E>> FromResidual<Result<Infallible, E>> for Poll<Result<T, F>> {
+    fn from_residual(residual: Result<Infallible, E>) -> Self {
+        Poll::Ready(FromResidual::from_residual(residual))
+    }
+}
+
+impl<T, E> Branch for Poll<Option<Result<T, E>>> {
+    type Output = Poll<Option<T>>;
+    type Residual = Result<Infallible, E>;
+
+    fn branch(self) -> ControlFlow<Self::Residual, Poll<Option<T>>> {
+        match self {
+            Poll::Ready(Some(Ok(value))) => ControlFlow::Continue(Poll::Ready(Some(value))),
+            Poll::Ready(Some(Err(e))) => ControlFlow::Break(Err(e)),
+            Poll::Ready(None) => ControlFlow::Continue(Poll::Ready(None)),
+            Poll::Pending => ControlFlow::Continue(Poll::Pending),
+        }
+    }
+}
+
+impl<T, E, F: From<E>> FromResidual<Result<Infallible, E>> for Poll<Option<Result<T, F>>> {
+    fn from_residual(residual: Result<Infallible, E>) -> Self {
+        Poll::Ready(Some(FromResidual::from_residual(residual)))
+    }
+}
+
+impl<B, C> Branch for ControlFlow<B, C> {
+    type Output = C;
+    type Residual = ControlFlow<B, Infallible>;
+
+    fn branch(self) -> ControlFlow<Self::Residual, C> {
+        match self {
+            ControlFlow::Continue(value) => ControlFlow::Continue(value),
+            ControlFlow::Break(value) => ControlFlow::Break(ControlFlow::Break(value)),
+        }
+    }
+}
+
+impl<B, C> FromResidual<ControlFlow<B, Infallible>> for ControlFlow<B, C> {
+    fn from_residual(residual: ControlFlow<B, Infallible>) -> Self {
+        match residual {
+            ControlFlow::Break(value) => ControlFlow::Break(value),
+            ControlFlow::Continue(never) => match never {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies `?` to `value` through the traits, as rewritten bodies do.
+    fn question<T: Branch, R: FromResidual<T::Residual>>(value: T) -> Result<T::Output, R> {
+        match Branch::branch(value) {
+            ControlFlow::Continue(output) => Ok(output),
+            ControlFlow::Break(residual) => Err(FromResidual::from_residual(residual)),
+        }
+    }
+
+    #[test]
+    fn test_branch() {
+        assert_eq!(question::<_, Result<(), String>>(Ok::<u8, &str>(1)), Ok(1));
+        assert_eq!(
+            question::<_, Result<(), String>>(Err::<u8, &str>("bad")),
+            Err(Err("bad".to_string()))
+        );
+        assert_eq!(question::<_, Option<bool>>(None::<u8>), Err(None));
+        assert_eq!(
+            question::<_, Poll<Result<(), String>>>(Poll::Ready(Err::<u8, &str>("bad"))),
+            Err(Poll::Ready(Err("bad".to_string())))
+        );
+        assert_eq!(
+            question::<_, Poll<Option<Result<(), String>>>>(
+                Poll::<Option<Result<u8, &str>>>::Pending
+            ),
+            Ok(Poll::Pending)
+        );
+        assert_eq!(
+            question::<_, ControlFlow<u8, ()>>(ControlFlow::<u8, u16>::Break(3)),
+            Err(ControlFlow::Break(3))
+        );
+    }
+}
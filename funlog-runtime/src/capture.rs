@@ -0,0 +1,454 @@
+use crate::event::{CallEvent, EventKind};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+thread_local! {
+    /// The events of every active `capture` on this thread, innermost last.
+    static CAPTURES: RefCell<Vec<Vec<CallEvent>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Runs `f` and returns the calls of instrumented functions it made on this thread.
+///
+/// Capturing only sees the current thread, so tests running in parallel do not see
+/// each other's calls. Calls are recorded whatever the level, [`set_enabled`] and the
+/// registered sinks, which still receive the events as usual. Captures can be nested;
+/// each one sees every call made while it is active.
+///
+/// [`set_enabled`]: crate::set_enabled
+///
+/// # Arguments
+///
+/// * `f` - The code to run
+///
+/// # Returns
+///
+/// Returns the captured calls, in the order they were made
+///
+/// # Examples
+///
+/// ```
+/// use funlog_runtime::capture;
+///
+/// let calls = capture(|| {
+///     // call instrumented functions here
+/// });
+/// assert!(calls.is_empty());
+/// ```
+pub fn capture(f: impl FnOnce()) -> Calls {
+    /// Ends the capture even if `f` panics.
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            CAPTURES.with(|captures| captures.borrow_mut().pop());
+        }
+    }
+
+    CAPTURES.with(|captures| captures.borrow_mut().push(Vec::new()));
+    let guard = Guard;
+    f();
+    let events = CAPTURES.with(|captures| captures.borrow_mut().last_mut().map(std::mem::take));
+    drop(guard);
+    Calls::from_events(events.unwrap_or_default())
+}
+
+/// Returns whether a `capture` is active on this thread.
+pub(crate) fn is_capturing() -> bool {
+    CAPTURES.with(|captures| !captures.borrow().is_empty())
+}
+
+/// Records an event in every active `capture` on this thread.
+pub(crate) fn record(event: &CallEvent) {
+    CAPTURES.with(|captures| {
+        for events in captures.borrow_mut().iter_mut() {
+            events.push(event.clone());
+        }
+    });
+}
+
+/// One call of an instrumented function, combining its entry and exit events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedCall {
+    /// The name of the function
+    pub fn_name: &'static str,
+    /// The module path of the function
+    pub module: &'static str,
+    /// The logged parameters and `fields(...)` values, as name and `Debug` text
+    pub params: Vec<(&'static str, String)>,
+    /// The `Debug` text of the return value, if it is logged
+    pub ret: Option<String>,
+    /// The duration of the call, if its exit was logged
+    pub elapsed: Option<Duration>,
+    /// The id shared by the call's events
+    pub call_id: u64,
+}
+
+impl CapturedCall {
+    /// Returns the logged value of a parameter or field.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the parameter or field
+    ///
+    /// # Returns
+    ///
+    /// Returns its `Debug` text, or `None` if it was not logged
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns whether this call is to `fn_name` and logged each of `params` with the
+    /// given `Debug` text.
+    fn matches(&self, fn_name: &str, params: &[(&str, String)]) -> bool {
+        self.fn_name == fn_name
+            && params
+                .iter()
+                .all(|(name, value)| self.param(name) == Some(value.as_str()))
+    }
+}
+
+impl fmt::Display for CapturedCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.fn_name)?;
+        for (i, (name, value)) in self.params.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{name}: {value}")?;
+        }
+        f.write_str(")")?;
+        if let Some(ret) = &self.ret {
+            write!(f, " -> {ret}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The calls recorded by [`capture`].
+///
+/// Use [`assert_called!`](crate::assert_called) and
+/// [`assert_not_called!`](crate::assert_not_called) to check them, or inspect the
+/// calls directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Calls {
+    calls: Vec<CapturedCall>,
+    events: Vec<CallEvent>,
+}
+
+impl Calls {
+    /// Combines the entry and exit events of each call, ordered by call start.
+    fn from_events(events: Vec<CallEvent>) -> Self {
+        let mut calls: Vec<CapturedCall> = Vec::new();
+        // The index in `calls` of each call, by call id
+        let mut index = HashMap::new();
+        for event in &events {
+            let i = *index.entry(event.call_id).or_insert_with(|| {
+                calls.push(CapturedCall {
+                    fn_name: event.fn_name,
+                    module: event.module,
+                    params: Vec::new(),
+                    ret: None,
+                    elapsed: None,
+                    call_id: event.call_id,
+                });
+                calls.len() - 1
+            });
+            let call = &mut calls[i];
+            for (name, value) in &event.params {
+                if call.param(name).is_none() {
+                    call.params.push((name, value.clone()));
+                }
+            }
+            if event.kind == EventKind::Exit {
+                call.ret.clone_from(&event.ret);
+                call.elapsed = event.elapsed;
+            }
+        }
+        // Call ids are taken on entry, so they order calls even when only exits are logged
+        calls.sort_by_key(|call| call.call_id);
+        Self { calls, events }
+    }
+
+    /// Returns the number of calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns whether no calls were captured.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Returns the calls in the order they were made.
+    pub fn iter(&self) -> std::slice::Iter<'_, CapturedCall> {
+        self.calls.iter()
+    }
+
+    /// Returns the calls to one function, in the order they were made.
+    ///
+    /// # Arguments
+    ///
+    /// * `fn_name` - The name of the function
+    pub fn named<'a>(&'a self, fn_name: &'a str) -> impl Iterator<Item = &'a CapturedCall> {
+        self.calls
+            .iter()
+            .filter(move |call| call.fn_name == fn_name)
+    }
+
+    /// Returns the names of the called functions, in the order they were called.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.calls.iter().map(|call| call.fn_name).collect()
+    }
+
+    /// Returns the raw entry and exit events, in the order they were emitted.
+    pub fn events(&self) -> &[CallEvent] {
+        &self.events
+    }
+
+    /// Panics unless a call to `fn_name` logged each of `params` with the given text.
+    ///
+    /// This is what [`assert_called!`](crate::assert_called) expands to.
+    ///
+    /// # Arguments
+    ///
+    /// * `fn_name` - The name of the function
+    /// * `params` - Parameter names with the expected `Debug` text of their values
+    #[track_caller]
+    pub fn assert_called(&self, fn_name: &str, params: &[(&str, String)]) {
+        if !self.calls.iter().any(|call| call.matches(fn_name, params)) {
+            panic!(
+                "expected a call to `{}`{}, captured calls:\n{}",
+                fn_name,
+                Self::describe(params),
+                self
+            );
+        }
+    }
+
+    /// Panics if a call to `fn_name` logged each of `params` with the given text.
+    ///
+    /// This is what [`assert_not_called!`](crate::assert_not_called) expands to.
+    ///
+    /// # Arguments
+    ///
+    /// * `fn_name` - The name of the function
+    /// * `params` - Parameter names with the `Debug` text of their values
+    #[track_caller]
+    pub fn assert_not_called(&self, fn_name: &str, params: &[(&str, String)]) {
+        if let Some(call) = self.calls.iter().find(|call| call.matches(fn_name, params)) {
+            panic!(
+                "expected no call to `{}`{}, but found `{}`",
+                fn_name,
+                Self::describe(params),
+                call
+            );
+        }
+    }
+
+    fn describe(params: &[(&str, String)]) -> String {
+        if params.is_empty() {
+            return String::new();
+        }
+        let params = params
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(" with {params}")
+    }
+}
+
+impl fmt::Display for Calls {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.calls.is_empty() {
+            return f.write_str("  (none)");
+        }
+        for (i, call) in self.calls.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "  {call}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Calls {
+    type Item = &'a CapturedCall;
+    type IntoIter = std::slice::Iter<'a, CapturedCall>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::ops::Index<usize> for Calls {
+    type Output = CapturedCall;
+
+    fn index(&self, index: usize) -> &CapturedCall {
+        &self.calls[index]
+    }
+}
+
+/// Asserts that captured calls include a call to a function, optionally with
+/// particular logged values.
+///
+/// Each `name = value` is compared as the `Debug` text of `value` against the text
+/// logged for the parameter or field `name`, so `x = 1` matches `x:1` and
+/// `name = "bob"` matches `name:"bob"`. Values logged with `pretty`, `max_len` or
+/// `redact` from `funlog.toml` have to be compared as that text instead.
+///
+/// # Examples
+///
+/// ```
+/// use funlog_runtime::{assert_called, assert_not_called, capture};
+///
+/// let calls = capture(|| {});
+/// assert_not_called!(calls, "add");
+/// ```
+///
+/// With an instrumented `add(x: i32, y: i32)`:
+///
+/// ```ignore
+/// let calls = funlog::capture(|| {
+///     add(1, 2);
+/// });
+/// assert_called!(calls, "add");
+/// assert_called!(calls, "add", x = 1, y = 2);
+/// ```
+#[macro_export]
+macro_rules! assert_called {
+    ($calls:expr, $fn_name:expr $(, $param:ident = $value:expr)* $(,)?) => {
+        $crate::Calls::assert_called(
+            &$calls,
+            $fn_name,
+            &[$((stringify!($param), format!("{:?}", $value))),*],
+        )
+    };
+}
+
+/// Asserts that captured calls include no call to a function, or none with
+/// particular logged values.
+///
+/// Values are compared as in [`assert_called!`](crate::assert_called).
+///
+/// # Examples
+///
+/// ```
+/// use funlog_runtime::{assert_not_called, capture};
+///
+/// let calls = capture(|| {});
+/// assert_not_called!(calls, "add", x = 1);
+/// ```
+#[macro_export]
+macro_rules! assert_not_called {
+    ($calls:expr, $fn_name:expr $(, $param:ident = $value:expr)* $(,)?) => {
+        $crate::Calls::assert_not_called(
+            &$calls,
+            $fn_name,
+            &[$((stringify!($param), format!("{:?}", $value))),*],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Call, Callsite, Level};
+
+    static ADD: Callsite = Callsite {
+        fn_name: "add",
+        module: "app",
+        file: "src/main.rs",
+        line: 1,
+    };
+
+    fn emit(call: &Call, kind: EventKind, params: &[(&'static str, &str)], ret: Option<&str>) {
+        record(&CallEvent::new(
+            call,
+            Level::Trace,
+            kind,
+            params,
+            ret,
+            String::new(),
+        ));
+    }
+
+    #[test]
+    fn test_capture_combines_events() {
+        let calls = capture(|| {
+            let outer = Call::start(&ADD);
+            let inner = Call::start(&ADD);
+            emit(&outer, EventKind::Enter, &[("x", "1")], None);
+            // Only the exit of the inner call is logged, after the outer entry
+            emit(&inner, EventKind::Exit, &[("x", "2")], Some("4"));
+            emit(&outer, EventKind::Exit, &[], Some("3"));
+        });
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls.events().len(), 3);
+        assert_eq!(calls[0].param("x"), Some("1"));
+        assert_eq!(calls[0].ret.as_deref(), Some("3"));
+        assert!(calls[0].elapsed.is_some());
+        assert_eq!(calls[1].to_string(), "add(x: 2) -> 4");
+        assert_eq!(calls.names(), vec!["add", "add"]);
+        assert_eq!(calls.named("add").count(), 2);
+        assert_eq!(calls.named("sub").count(), 0);
+
+        assert_called!(calls, "add");
+        assert_called!(calls, "add", x = 2);
+        assert_not_called!(calls, "add", x = 3);
+        assert_not_called!(calls, "sub");
+    }
+
+    #[test]
+    fn test_capture_nesting_and_threads() {
+        assert!(!is_capturing());
+        let mut inner = Calls::default();
+        let outer = capture(|| {
+            assert!(is_capturing());
+            inner = capture(|| emit(&Call::start(&ADD), EventKind::Enter, &[], None));
+            // Another thread's calls are not captured
+            std::thread::spawn(|| {
+                assert!(!is_capturing());
+                record(&CallEvent::new(
+                    &Call::start(&ADD),
+                    Level::Trace,
+                    EventKind::Enter,
+                    &[],
+                    None,
+                    String::new(),
+                ));
+            })
+            .join()
+            .unwrap();
+        });
+        assert!(!is_capturing());
+        assert_eq!(inner.len(), 1);
+        assert_eq!(outer, inner);
+
+        // A panic inside the closure still ends the capture
+        let _ = std::panic::catch_unwind(|| capture(|| panic!("inside capture")));
+        assert!(!is_capturing());
+    }
+
+    #[test]
+    fn test_assert_called_message() {
+        let calls = capture(|| {
+            emit(&Call::start(&ADD), EventKind::Enter, &[("x", "1")], None);
+        });
+        let message = std::panic::catch_unwind(|| assert_called!(calls, "add", x = 2))
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap();
+        assert_eq!(
+            *message,
+            "expected a call to `add` with x: 2, captured calls:\n  add(x: 1)"
+        );
+    }
+}
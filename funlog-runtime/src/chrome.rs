@@ -0,0 +1,238 @@
+use crate::event::{CallEvent, EventKind};
+use crate::json;
+use crate::sink::FunlogSink;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Writes events in the Chrome Trace Event Format, for `chrome://tracing` and
+/// [Perfetto](https://ui.perfetto.dev).
+///
+/// Every call is an async slice with the call id as its `id`: the entry opens it with a
+/// `b` event at the call's start and the exit closes it with an `e` event at the start
+/// plus its elapsed time, so calls interleaved on one thread by async code keep their
+/// own slices. A call whose entry was not logged (`onEnd`) writes both events on exit.
+/// Slices carry the logged values, and the return value as `return`, in their `args`.
+/// Functions logged only `onStart` leave their slices open.
+///
+/// The output is a JSON array written one event per line and flushed as it goes; the
+/// closing `]` is left out, as the format allows, so a trace stays readable when the
+/// program exits without cleanup.
+///
+/// Setting `FUNLOG_CHROME_TRACE` to a file path writes a trace of the whole program
+/// there, in addition to the other output.
+///
+/// # Examples
+///
+/// ```no_run
+/// use funlog_runtime::{register_sink, ChromeTraceSink, LogSink};
+///
+/// register_sink(LogSink);
+/// register_sink(ChromeTraceSink::create("trace.json").unwrap());
+/// ```
+pub struct ChromeTraceSink {
+    state: Mutex<State>,
+    epoch: Instant,
+    pid: u32,
+}
+
+struct State {
+    out: Box<dyn Write + Send>,
+    /// Calls whose `b` event has been written and not yet closed, by call id
+    open: HashSet<u64>,
+}
+
+impl ChromeTraceSink {
+    /// Creates a sink that writes a trace to the file at `path`, replacing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to write
+    ///
+    /// # Returns
+    ///
+    /// Returns the sink, or the error from creating the file
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(LineWriter::new(File::create(path)?))
+    }
+
+    /// Creates a sink that writes a trace to `out`.
+    ///
+    /// Timestamps are measured from this call.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Where to write the trace
+    ///
+    /// # Returns
+    ///
+    /// Returns the sink, or the error from writing the start of the trace
+    pub fn new(mut out: impl Write + Send + 'static) -> io::Result<Self> {
+        out.write_all(b"[\n")?;
+        Ok(Self {
+            state: Mutex::new(State {
+                out: Box::new(out),
+                open: HashSet::new(),
+            }),
+            epoch: Instant::now(),
+            pid: std::process::id(),
+        })
+    }
+
+    /// Formats one trace event as a line of the JSON array.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event whose call the slice belongs to
+    /// * `phase` - `b` to open the slice or `e` to close it
+    /// * `ts` - When the slice opens or closes
+    /// * `args` - Whether to add the logged values and return value
+    fn format(&self, event: &CallEvent, phase: char, ts: Instant, args: bool) -> String {
+        let mut line = String::from("{\"name\":");
+        json::push_str(&mut line, event.fn_name);
+        line.push_str(",\"cat\":");
+        json::push_str(&mut line, event.module);
+        let _ = write!(
+            line,
+            ",\"ph\":\"{phase}\",\"id\":{},\"pid\":{},\"tid\":{},\"ts\":{}",
+            event.call_id,
+            self.pid,
+            event.thread,
+            micros(ts.saturating_duration_since(self.epoch))
+        );
+        line.push_str(",\"args\":");
+        let ret = event.ret.as_deref().map(|ret| ("return", ret));
+        let values = event
+            .params
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .chain(ret)
+            .filter(|_| args);
+        json::push_object(&mut line, values);
+        line.push_str("},\n");
+        line
+    }
+}
+
+/// Formats a duration as the microseconds used by trace timestamps.
+fn micros(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1_000_000.0)
+}
+
+impl FunlogSink for ChromeTraceSink {
+    fn on_event(&self, event: &CallEvent) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut lines = String::new();
+        match event.kind {
+            EventKind::Enter => {
+                state.open.insert(event.call_id);
+                lines.push_str(&self.format(event, 'b', event.started, true));
+            }
+            EventKind::Exit => {
+                if !state.open.remove(&event.call_id) {
+                    lines.push_str(&self.format(event, 'b', event.started, false));
+                }
+                let end = event.started + event.elapsed.unwrap_or_default();
+                lines.push_str(&self.format(event, 'e', end, true));
+            }
+        }
+        // A trace is best effort; a full disk must not break the traced program
+        let _ = state.out.write_all(lines.as_bytes());
+    }
+
+    fn flush(&self) {
+        let _ = self
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .out
+            .flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Call, Callsite, Level};
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    static ADD: Callsite = Callsite {
+        fn_name: "add",
+        module: "app::math",
+        file: "src/math.rs",
+        line: 3,
+    };
+
+    #[test]
+    fn test_chrome_trace_events() {
+        let buffer = Buffer::default();
+        let sink = ChromeTraceSink::new(buffer.clone()).unwrap();
+        let event = |call: &Call, kind, params: &[(&'static str, &str)], ret| {
+            CallEvent::new(call, Level::Debug, kind, params, ret, String::new())
+        };
+
+        // Two calls interleaved on one thread, as by async code
+        let first = Call::start(&ADD);
+        let second = Call::start(&ADD);
+        sink.on_event(&event(
+            &first,
+            EventKind::Enter,
+            &[("x", "1"), ("s", "\"a\"")],
+            None,
+        ));
+        sink.on_event(&event(&second, EventKind::Enter, &[("x", "2")], None));
+        sink.on_event(&event(&first, EventKind::Exit, &[], Some("3")));
+        sink.on_event(&event(&second, EventKind::Exit, &[], Some("4")));
+        let end_only = Call::start(&ADD);
+        sink.on_event(&event(&end_only, EventKind::Exit, &[("x", "5")], None));
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "[");
+        let pid = format!("\"pid\":{}", std::process::id());
+        let id = |call: &Call| format!("\"id\":{},", call.id);
+        assert!(lines[1].starts_with(r#"{"name":"add","cat":"app::math","ph":"b","#));
+        assert!(lines[1].contains(&id(&first)));
+        assert!(lines[1].contains(&pid));
+        assert!(lines[1].contains(&format!("\"tid\":{}", crate::thread_id())));
+        assert!(lines[1].ends_with(r#""args":{"x":"1","s":"\"a\""}},"#));
+        assert!(lines[2].contains(r#""ph":"b""#) && lines[2].contains(&id(&second)));
+        assert!(lines[3].contains(r#""ph":"e""#) && lines[3].contains(&id(&first)));
+        assert!(lines[3].ends_with(r#""args":{"return":"3"}},"#));
+        assert!(lines[4].contains(r#""ph":"e""#) && lines[4].contains(&id(&second)));
+        assert!(lines[4].ends_with(r#""args":{"return":"4"}},"#));
+        assert!(lines[5].contains(r#""ph":"b""#) && lines[5].contains(&id(&end_only)));
+        assert!(lines[5].ends_with(r#""args":{}},"#));
+        assert!(lines[6].contains(r#""ph":"e""#));
+        assert!(lines[6].ends_with(r#""args":{"x":"5"}},"#));
+
+        // Timestamps come from the call, not from when the sink sees the event
+        let ts = |line: &str| -> f64 {
+            let start = line.find("\"ts\":").unwrap() + 5;
+            let end = start + line[start..].find(',').unwrap();
+            line[start..end].parse().unwrap()
+        };
+        let exit = event(&first, EventKind::Exit, &[], None);
+        let micros = exit.elapsed.unwrap().as_secs_f64() * 1_000_000.0;
+        assert!(ts(lines[3]) - ts(lines[1]) <= micros + 0.001);
+        assert!(ts(lines[1]) <= ts(lines[2]));
+    }
+}
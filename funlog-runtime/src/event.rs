@@ -0,0 +1,386 @@
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Whether an event was emitted on entry to or exit from a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// The function was called
+    Enter,
+    /// The function returned
+    Exit,
+}
+
+/// The level an event is emitted at, as chosen in the `#[funlog(...)]` attribute.
+///
+/// # Examples
+///
+/// ```
+/// use funlog_runtime::Level;
+///
+/// assert_eq!(Level::Debug.as_log(), Some(log::Level::Debug));
+/// assert_eq!(Level::Print.as_log(), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Level {
+    /// Written to standard output
+    Print,
+    /// `log::Level::Error`
+    Error,
+    /// `log::Level::Warn`
+    Warn,
+    /// `log::Level::Info`
+    Info,
+    /// `log::Level::Debug`
+    Debug,
+    /// `log::Level::Trace`
+    Trace,
+}
+
+impl Level {
+    /// Returns the corresponding `log` level.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` for `Level::Print`, which does not go through `log`
+    pub fn as_log(self) -> Option<log::Level> {
+        match self {
+            Level::Print => None,
+            Level::Error => Some(log::Level::Error),
+            Level::Warn => Some(log::Level::Warn),
+            Level::Info => Some(log::Level::Info),
+            Level::Debug => Some(log::Level::Debug),
+            Level::Trace => Some(log::Level::Trace),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Print => "PRINT",
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        f.pad(name)
+    }
+}
+
+/// Where an instrumented function is defined.
+///
+/// Generated code creates one `Callsite` per function as a `'static` constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Callsite {
+    /// The name of the function
+    pub fn_name: &'static str,
+    /// The module path of the function, from `module_path!()`
+    pub module: &'static str,
+    /// The source file of the function, from `file!()`
+    pub file: &'static str,
+    /// The line of the `#[funlog]` attribute, from `line!()`
+    pub line: u32,
+}
+
+thread_local! {
+    /// The call whose body is running on this thread, if any
+    static CURRENT: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// One call of an instrumented function, from entry to exit.
+///
+/// Generated code starts a `Call` before anything else in the wrapper, so the entry
+/// and exit events share its id and the exit event can report the elapsed time. The
+/// body then runs inside [`Call::enter`], on every poll for an async body, so calls
+/// made in it find this one as their parent even when async calls interleave on a
+/// thread.
+#[derive(Debug, Clone, Copy)]
+pub struct Call {
+    /// Where the function is defined
+    pub callsite: &'static Callsite,
+    /// Identifies this call; unique within the process
+    pub id: u64,
+    /// The id of the instrumented call whose body made this one, if any
+    pub parent: Option<u64>,
+    /// When the call started
+    pub started: Instant,
+}
+
+impl Call {
+    /// Starts a call of the function at `callsite`.
+    ///
+    /// # Arguments
+    ///
+    /// * `callsite` - Where the function is defined
+    ///
+    /// # Returns
+    ///
+    /// Returns the call with a new id
+    pub fn start(callsite: &'static Callsite) -> Self {
+        static NEXT_CALL_ID: AtomicU64 = AtomicU64::new(1);
+        let call = Self {
+            callsite,
+            id: NEXT_CALL_ID.fetch_add(1, Ordering::Relaxed),
+            parent: CURRENT.with(Cell::get),
+            started: Instant::now(),
+        };
+        #[cfg(feature = "opentelemetry")]
+        crate::otel::start(&call);
+        call
+    }
+
+    /// Makes this call the parent of the calls started on this thread until the
+    /// returned guard is dropped, including by a panic.
+    ///
+    /// # Returns
+    ///
+    /// Returns the guard that restores the previous current call
+    pub fn enter(&self) -> Entered {
+        Entered {
+            call_id: self.id,
+            previous: CURRENT.with(|current| current.replace(Some(self.id))),
+            panicking: std::thread::panicking(),
+            _not_send: PhantomData,
+        }
+    }
+}
+
+/// Keeps a call current on its thread, see [`Call::enter`].
+#[must_use = "the call is only current until the guard is dropped"]
+pub struct Entered {
+    call_id: u64,
+    previous: Option<u64>,
+    /// Whether the thread was already unwinding when the call was entered
+    panicking: bool,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.previous));
+        // The body panicked, so the call will not be finished
+        if std::thread::panicking() && !self.panicking {
+            crate::abandon(self.call_id, true);
+        }
+    }
+}
+
+/// Returns funlog's id for the current thread.
+///
+/// Threads are numbered from 1 in the order they first emit an event, which keeps the
+/// ids short and stable across runs of the same program.
+pub fn thread_id() -> u64 {
+    static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+    }
+    THREAD_ID.with(|id| *id)
+}
+
+/// One entry or exit of an instrumented function.
+///
+/// # Examples
+///
+/// ```
+/// use funlog_runtime::{CallEvent, EventKind, Level};
+/// use std::time::Instant;
+///
+/// let event = CallEvent {
+///     kind: EventKind::Exit,
+///     level: Level::Debug,
+///     fn_name: "add",
+///     module: "app",
+///     file: "src/main.rs",
+///     line: 3,
+///     params: vec![],
+///     ret: Some("3".to_string()),
+///     started: Instant::now(),
+///     elapsed: None,
+///     call_id: 1,
+///     parent: None,
+///     thread: 1,
+///     message: "add [out]: return:3".to_string(),
+/// };
+/// assert_eq!(event.to_string(), "add [out]: return:3");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallEvent {
+    /// Entry or exit
+    pub kind: EventKind,
+    /// The level chosen for this phase of the call
+    pub level: Level,
+    /// The name of the function
+    pub fn_name: &'static str,
+    /// The module path of the function
+    pub module: &'static str,
+    /// The source file of the function
+    pub file: &'static str,
+    /// The line of the `#[funlog]` attribute
+    pub line: u32,
+    /// The logged parameters and `fields(...)` values in this event, as name and
+    /// `Debug` text
+    pub params: Vec<(&'static str, String)>,
+    /// The `Debug` text of the return value, on exit events that log it
+    pub ret: Option<String>,
+    /// When the call started, shared by its entry and exit events
+    pub started: Instant,
+    /// The time since the call started, on exit events
+    pub elapsed: Option<Duration>,
+    /// Identifies the call; the entry and exit events of one call share it
+    pub call_id: u64,
+    /// The id of the instrumented call this one was made in, if any
+    pub parent: Option<u64>,
+    /// The thread the call ran on, see [`thread_id`]
+    pub thread: u64,
+    /// The formatted log line, e.g. `add [in ]: x:1, y:2`
+    pub message: String,
+}
+
+impl CallEvent {
+    /// Creates an event for a call.
+    ///
+    /// # Arguments
+    ///
+    /// * `call` - The call the event belongs to
+    /// * `level` - The level chosen for this phase of the call
+    /// * `kind` - Entry or exit
+    /// * `params` - The logged values, as name and `Debug` text
+    /// * `ret` - The `Debug` text of the return value, if it is logged
+    /// * `message` - The formatted log line
+    ///
+    /// # Returns
+    ///
+    /// Returns the event, with the elapsed time set for exit events
+    pub fn new(
+        call: &Call,
+        level: Level,
+        kind: EventKind,
+        params: &[(&'static str, &str)],
+        ret: Option<&str>,
+        message: String,
+    ) -> Self {
+        let callsite = call.callsite;
+        Self {
+            kind,
+            level,
+            fn_name: callsite.fn_name,
+            module: callsite.module,
+            file: callsite.file,
+            line: callsite.line,
+            params: params
+                .iter()
+                .map(|(name, value)| (*name, value.to_string()))
+                .collect(),
+            ret: ret.map(str::to_string),
+            started: call.started,
+            elapsed: (kind == EventKind::Exit).then(|| call.started.elapsed()),
+            call_id: call.id,
+            parent: call.parent,
+            thread: thread_id(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for CallEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_as_log() {
+        assert_eq!(Level::Print.as_log(), None);
+        assert_eq!(Level::Error.as_log(), Some(log::Level::Error));
+        assert_eq!(Level::Trace.as_log(), Some(log::Level::Trace));
+        assert_eq!(format!("{:<5}|", Level::Info), "INFO |");
+    }
+
+    #[test]
+    fn test_call_event_new() {
+        static CALLSITE: Callsite = Callsite {
+            fn_name: "add",
+            module: "app::math",
+            file: "src/math.rs",
+            line: 7,
+        };
+        let call = Call::start(&CALLSITE);
+        let enter = CallEvent::new(
+            &call,
+            Level::Info,
+            EventKind::Enter,
+            &[("x", "1"), ("y", "2")],
+            None,
+            "add [in ]: x:1, y:2".to_string(),
+        );
+        let exit = CallEvent::new(
+            &call,
+            Level::Info,
+            EventKind::Exit,
+            &[],
+            Some("3"),
+            "add [out]: return:3".to_string(),
+        );
+
+        assert_eq!(enter.kind, EventKind::Enter);
+        assert_eq!(enter.fn_name, "add");
+        assert_eq!(enter.module, "app::math");
+        assert_eq!(enter.line, 7);
+        assert_eq!(
+            enter.params,
+            vec![("x", "1".to_string()), ("y", "2".to_string())]
+        );
+        assert_eq!(enter.elapsed, None);
+        assert_eq!(exit.ret.as_deref(), Some("3"));
+        assert!(exit.elapsed.is_some());
+        assert_eq!(enter.call_id, exit.call_id);
+        assert_eq!(enter.thread, exit.thread);
+        assert_eq!(exit.to_string(), "add [out]: return:3");
+
+        assert_ne!(Call::start(&CALLSITE).id, call.id);
+    }
+
+    #[test]
+    fn test_thread_id() {
+        let here = thread_id();
+        assert_eq!(here, thread_id());
+        let there = std::thread::spawn(thread_id).join().unwrap();
+        assert_ne!(here, there);
+    }
+
+    #[test]
+    fn test_call_parent() {
+        static CALLSITE: Callsite = Callsite {
+            fn_name: "run",
+            module: "app",
+            file: "src/main.rs",
+            line: 1,
+        };
+        let outer = Call::start(&CALLSITE);
+        assert_eq!(outer.parent, None);
+        let inner = {
+            let _entered = outer.enter();
+            let inner = Call::start(&CALLSITE);
+            let _nested = inner.enter();
+            assert_eq!(Call::start(&CALLSITE).parent, Some(inner.id));
+            inner
+        };
+        assert_eq!(inner.parent, Some(outer.id));
+
+        // A panicking body leaves its call
+        let result = std::panic::catch_unwind(|| {
+            let _entered = outer.enter();
+            panic!("body panicked");
+        });
+        assert!(result.is_err());
+        assert_eq!(Call::start(&CALLSITE).parent, None);
+    }
+}
@@ -0,0 +1,365 @@
+use crate::event::{CallEvent, EventKind};
+use crate::sink::FunlogSink;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The time spent in one unique stack of instrumented functions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StackTime {
+    /// The number of calls that returned with this stack
+    pub calls: u64,
+    /// The time spent in the innermost function itself, excluding instrumented callees
+    pub self_time: Duration,
+    /// The time spent in the innermost function, including its callees
+    pub total_time: Duration,
+}
+
+/// Aggregates calls into stacks of instrumented functions and writes them in the
+/// folded-stack format read by `inferno-flamegraph` and `flamegraph.pl`.
+///
+/// Each call's stack is its parent's stack, from [`CallEvent::parent`], followed by
+/// itself, so calls interleaved on one thread by async code are attributed to the
+/// call that made them. When a call returns, its self and total time are added to
+/// its stack, named `module::function` from the outermost instrumented call down,
+/// e.g. `app::main;app::db::query`. The folded output gives each stack its self time
+/// in nanoseconds, so a flamegraph of it shows where time went among the
+/// instrumented functions only, without sampling.
+///
+/// Stacks are built from entry and exit events, so functions should log both, which
+/// is the default. A call with only its exit logged counts as a leaf, and a call with
+/// only its entry logged is dropped when its caller returns.
+///
+/// The sink is a cheap handle: register a clone and keep one to write the profile on
+/// demand. Setting `FUNLOG_FOLDED` to a file path profiles the whole program and
+/// writes that file as described in [`FoldedStackSink::write_to`].
+///
+/// # Examples
+///
+/// ```
+/// use funlog_runtime::{register_sink, FoldedStackSink};
+///
+/// let profile = FoldedStackSink::new();
+/// register_sink(profile.clone());
+/// // ... run instrumented code ...
+/// profile.write_folded(std::io::stdout()).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct FoldedStackSink {
+    state: Arc<Mutex<State>>,
+    output: Option<Arc<PathBuf>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// The calls whose entry has been seen and that have not returned, by call id
+    open: HashMap<u64, Frame>,
+    stacks: BTreeMap<String, StackTime>,
+    /// When the output file was last written
+    written: Option<Instant>,
+}
+
+struct Frame {
+    parent: Option<u64>,
+    /// The names of the calls from the outermost down to this one, joined by `;`
+    stack: String,
+    /// The ids of the open calls made from this one
+    callees: Vec<u64>,
+    /// The total time of the instrumented calls made from this one so far
+    children: Duration,
+}
+
+/// The shortest time between two writes of the output file when calls return.
+const WRITE_INTERVAL: Duration = Duration::from_secs(1);
+
+impl FoldedStackSink {
+    /// Creates a sink with no recorded stacks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the sink write the file at `path` on [`flush`](crate::flush), and when an
+    /// outermost instrumented call returns at least a second after the last write or
+    /// was already running at the last write.
+    ///
+    /// The file is complete once an instrumented `main`, or any outermost call that
+    /// runs as long as the program, returns. When the last outermost calls are short
+    /// ones made from uninstrumented code, call `flush` before the program exits so
+    /// the file includes them.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to write the folded stacks to
+    ///
+    /// # Returns
+    ///
+    /// Returns the sink with the output set
+    pub fn write_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.output = Some(Arc::new(path.into()));
+        self
+    }
+
+    /// Returns the time recorded for each unique stack so far.
+    pub fn stacks(&self) -> BTreeMap<String, StackTime> {
+        self.lock().stacks.clone()
+    }
+
+    /// Writes the stacks recorded so far in the folded-stack format.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Where to write the stacks, one `stack self_time_ns` line each
+    ///
+    /// # Returns
+    ///
+    /// Returns the error from writing, if any
+    pub fn write_folded(&self, out: impl Write) -> io::Result<()> {
+        write_stacks(&self.lock().stacks, out)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Writes the output file, if one is set, reporting errors on standard error.
+    fn write_output(&self, state: &mut State) {
+        let Some(path) = &self.output else {
+            return;
+        };
+        state.written = Some(Instant::now());
+        if let Err(e) = write_file(&state.stacks, path) {
+            eprintln!(
+                "funlog: cannot write folded stacks to {}: {e}",
+                path.display()
+            );
+        }
+    }
+}
+
+fn write_stacks(stacks: &BTreeMap<String, StackTime>, mut out: impl Write) -> io::Result<()> {
+    for (stack, time) in stacks {
+        writeln!(out, "{} {}", stack, time.self_time.as_nanos())?;
+    }
+    out.flush()
+}
+
+fn write_file(stacks: &BTreeMap<String, StackTime>, path: &Path) -> io::Result<()> {
+    write_stacks(stacks, BufWriter::new(File::create(path)?))
+}
+
+/// Returns the stack of a call named `name` made in `parent`.
+fn stack_of(open: &HashMap<u64, Frame>, parent: Option<u64>, name: &str) -> String {
+    match parent.and_then(|parent| open.get(&parent)) {
+        Some(frame) => format!("{};{name}", frame.stack),
+        None => name.to_string(),
+    }
+}
+
+/// Drops the open calls in `callees`, and in turn the calls made in them, whose exits
+/// were not logged.
+fn drop_callees(open: &mut HashMap<u64, Frame>, mut callees: Vec<u64>) {
+    while let Some(callee) = callees.pop() {
+        if let Some(frame) = open.remove(&callee) {
+            callees.extend(frame.callees);
+        }
+    }
+}
+
+impl FunlogSink for FoldedStackSink {
+    fn on_event(&self, event: &CallEvent) {
+        let mut state = self.lock();
+        let name = format!("{}::{}", event.module, event.fn_name);
+
+        if event.kind == EventKind::Enter {
+            let stack = stack_of(&state.open, event.parent, &name);
+            if let Some(parent) = event.parent.and_then(|parent| state.open.get_mut(&parent)) {
+                parent.callees.push(event.call_id);
+            }
+            state.open.insert(
+                event.call_id,
+                Frame {
+                    parent: event.parent,
+                    stack,
+                    callees: Vec::new(),
+                    children: Duration::ZERO,
+                },
+            );
+            return;
+        }
+
+        let total_time = event.elapsed.unwrap_or_default();
+        let (stack, children) = match state.open.remove(&event.call_id) {
+            Some(frame) => {
+                if let Some(parent) = frame.parent.and_then(|parent| state.open.get_mut(&parent)) {
+                    if let Some(i) = parent.callees.iter().position(|id| *id == event.call_id) {
+                        parent.callees.swap_remove(i);
+                    }
+                }
+                drop_callees(&mut state.open, frame.callees);
+                (frame.stack, frame.children)
+            }
+            None => (stack_of(&state.open, event.parent, &name), Duration::ZERO),
+        };
+
+        let time = state.stacks.entry(stack).or_default();
+        time.calls += 1;
+        time.self_time += total_time.saturating_sub(children);
+        time.total_time += total_time;
+
+        match event.parent.and_then(|parent| state.open.get_mut(&parent)) {
+            Some(parent) => parent.children += total_time,
+            None => {
+                // A call that was running at the last write, such as `main`, may be
+                // the last to return, so it is not held back until the next flush
+                if state.written.is_none_or(|written| {
+                    let since = written.elapsed();
+                    since >= WRITE_INTERVAL || since <= total_time
+                }) {
+                    self.write_output(&mut state);
+                }
+            }
+        }
+    }
+
+    fn flush(&self) {
+        let mut state = self.lock();
+        self.write_output(&mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Call, Callsite, Level};
+
+    static OUTER: Callsite = Callsite {
+        fn_name: "outer",
+        module: "app",
+        file: "src/main.rs",
+        line: 1,
+    };
+    static INNER: Callsite = Callsite {
+        fn_name: "inner",
+        module: "app::db",
+        file: "src/db.rs",
+        line: 1,
+    };
+
+    fn event(call: &Call, kind: EventKind, elapsed_ms: u64) -> CallEvent {
+        let mut event = CallEvent::new(call, Level::Trace, kind, &[], None, String::new());
+        event.elapsed = (kind == EventKind::Exit).then(|| Duration::from_millis(elapsed_ms));
+        event
+    }
+
+    #[test]
+    fn test_folded_stacks() {
+        let sink = FoldedStackSink::new();
+        let outer = Call::start(&OUTER);
+        sink.on_event(&event(&outer, EventKind::Enter, 0));
+        {
+            let _entered = outer.enter();
+            for _ in 0..2 {
+                let inner = Call::start(&INNER);
+                sink.on_event(&event(&inner, EventKind::Enter, 0));
+                sink.on_event(&event(&inner, EventKind::Exit, 3));
+            }
+            // An inner call with only its exit logged is still a child of `outer`
+            sink.on_event(&event(&Call::start(&INNER), EventKind::Exit, 1));
+        }
+        sink.on_event(&event(&outer, EventKind::Exit, 10));
+
+        let stacks = sink.stacks();
+        assert_eq!(
+            stacks["app::outer"],
+            StackTime {
+                calls: 1,
+                self_time: Duration::from_millis(3),
+                total_time: Duration::from_millis(10),
+            }
+        );
+        assert_eq!(
+            stacks["app::outer;app::db::inner"],
+            StackTime {
+                calls: 3,
+                self_time: Duration::from_millis(7),
+                total_time: Duration::from_millis(7),
+            }
+        );
+
+        let mut folded = Vec::new();
+        sink.write_folded(&mut folded).unwrap();
+        assert_eq!(
+            String::from_utf8(folded).unwrap(),
+            "app::outer 3000000\napp::outer;app::db::inner 7000000\n"
+        );
+    }
+
+    #[test]
+    fn test_interleaved_calls() {
+        let sink = FoldedStackSink::new();
+        let first = Call::start(&OUTER);
+        let second = Call::start(&OUTER);
+        sink.on_event(&event(&first, EventKind::Enter, 0));
+        sink.on_event(&event(&second, EventKind::Enter, 0));
+        // Each call's callee is attributed to it, not to the call entered last
+        let inner = first.enter();
+        let first_inner = Call::start(&INNER);
+        drop(inner);
+        sink.on_event(&event(&first_inner, EventKind::Enter, 0));
+        sink.on_event(&event(&first, EventKind::Exit, 5));
+        // `first_inner` was dropped with its caller, so its late exit is a root
+        sink.on_event(&event(&first_inner, EventKind::Exit, 2));
+        sink.on_event(&event(&second, EventKind::Exit, 4));
+
+        let stacks = sink.stacks();
+        assert_eq!(stacks["app::outer"].calls, 2);
+        assert_eq!(stacks["app::outer"].self_time, Duration::from_millis(9));
+        assert_eq!(stacks["app::db::inner"].calls, 1);
+        assert!(!stacks.contains_key("app::outer;app::outer"));
+        assert!(sink.lock().open.is_empty());
+    }
+
+    #[test]
+    fn test_unmatched_entries_and_output_file() {
+        let path = std::env::temp_dir().join(format!("funlog-folded-{}.txt", std::process::id()));
+        let sink = FoldedStackSink::new().write_to(&path);
+        let outer = Call::start(&OUTER);
+        sink.on_event(&event(&outer, EventKind::Enter, 0));
+        // Only the entry of this call is logged; it is dropped when `outer` returns
+        let entry_only = {
+            let _entered = outer.enter();
+            Call::start(&INNER)
+        };
+        sink.on_event(&event(&entry_only, EventKind::Enter, 0));
+        assert!(!path.exists());
+        sink.on_event(&event(&outer, EventKind::Exit, 2));
+        assert!(sink.lock().open.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "app::outer 2000000\n"
+        );
+
+        // Returns within a second of the last write of calls that started after it
+        // wait for the next flush
+        std::thread::sleep(Duration::from_millis(5));
+        sink.on_event(&event(&Call::start(&OUTER), EventKind::Exit, 1));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "app::outer 2000000\n"
+        );
+        sink.flush();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "app::outer 3000000\n"
+        );
+
+        // A call that was running at the last write is written when it returns
+        sink.on_event(&event(&Call::start(&OUTER), EventKind::Exit, 1000));
+        let folded = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(folded, "app::outer 1003000000\n");
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt::Write;
+
+/// Appends `value` to `out` as a JSON string literal, quotes included.
+///
+/// # Arguments
+///
+/// * `out` - The buffer to append to
+/// * `value` - The text to quote
+pub(crate) fn push_str(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Appends a JSON object of string values to `out`.
+///
+/// # Arguments
+///
+/// * `out` - The buffer to append to
+/// * `entries` - The keys and values, in order
+pub(crate) fn push_object<'a>(
+    out: &mut String,
+    entries: impl IntoIterator<Item = (&'a str, &'a str)>,
+) {
+    out.push('{');
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        push_str(out, key);
+        out.push(':');
+        push_str(out, value);
+    }
+    out.push('}');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_str_and_object() {
+        let mut out = String::new();
+        push_str(&mut out, "say \"hi\"\\\n\u{1}é");
+        assert_eq!(out, r#""say \"hi\"\\\n\u0001é""#);
+
+        let mut out = String::new();
+        push_object(&mut out, [("x", "1"), ("name", "\"bob\"")]);
+        assert_eq!(out, r#"{"x":"1","name":"\"bob\""}"#);
+
+        let mut out = String::new();
+        push_object(&mut out, []);
+        assert_eq!(out, "{}");
+    }
+}
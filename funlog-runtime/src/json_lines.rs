@@ -0,0 +1,167 @@
+use crate::event::{CallEvent, EventKind};
+use crate::json;
+use crate::sink::FunlogSink;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Writes every event as one line of JSON, for the `funlog` command-line tool and
+/// other programs.
+///
+/// Each line is an object with the event's `kind` (`"enter"` or `"exit"`), `level`,
+/// `fn`, `module`, `file`, `line`, `thread` and `call_id`, the logged values as a
+/// `params` object of `Debug` texts, the `ret` text and `elapsed_ns` on exits that
+/// have them, and the log line as `message`:
+///
+/// ```text
+/// {"kind":"exit","level":"DEBUG","fn":"add","module":"app","file":"src/main.rs","line":3,"thread":1,"call_id":1,"params":{},"ret":"3","elapsed_ns":1250,"message":"add [out]: return:3"}
+/// ```
+///
+/// Lines are written as events happen. Setting `FUNLOG_JSON` to a file path writes
+/// the events of the whole program there, in addition to the other output.
+///
+/// # Examples
+///
+/// ```no_run
+/// use funlog_runtime::{register_sink, JsonLinesSink, LogSink};
+///
+/// register_sink(LogSink);
+/// register_sink(JsonLinesSink::create("calls.jsonl").unwrap());
+/// ```
+pub struct JsonLinesSink {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl JsonLinesSink {
+    /// Creates a sink that writes to the file at `path`, replacing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to write
+    ///
+    /// # Returns
+    ///
+    /// Returns the sink, or the error from creating the file
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(LineWriter::new(File::create(path)?)))
+    }
+
+    /// Creates a sink that writes to `out`.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Where to write the events
+    pub fn new(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Mutex::new(Box::new(out)),
+        }
+    }
+}
+
+/// Formats an event as one line of JSON, newline included.
+fn format(event: &CallEvent) -> String {
+    let kind = match event.kind {
+        EventKind::Enter => "enter",
+        EventKind::Exit => "exit",
+    };
+    let mut line = format!(
+        "{{\"kind\":\"{kind}\",\"level\":\"{}\",\"fn\":",
+        event.level
+    );
+    json::push_str(&mut line, event.fn_name);
+    line.push_str(",\"module\":");
+    json::push_str(&mut line, event.module);
+    line.push_str(",\"file\":");
+    json::push_str(&mut line, event.file);
+    let _ = write!(
+        line,
+        ",\"line\":{},\"thread\":{},\"call_id\":{},\"params\":",
+        event.line, event.thread, event.call_id
+    );
+    json::push_object(
+        &mut line,
+        event
+            .params
+            .iter()
+            .map(|(name, value)| (*name, value.as_str())),
+    );
+    if let Some(ret) = &event.ret {
+        line.push_str(",\"ret\":");
+        json::push_str(&mut line, ret);
+    }
+    if let Some(elapsed) = event.elapsed {
+        let _ = write!(line, ",\"elapsed_ns\":{}", elapsed.as_nanos());
+    }
+    line.push_str(",\"message\":");
+    json::push_str(&mut line, &event.message);
+    line.push_str("}\n");
+    line
+}
+
+impl FunlogSink for JsonLinesSink {
+    fn on_event(&self, event: &CallEvent) {
+        let line = format(event);
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        // The output is best effort; a full disk must not break the program
+        let _ = out.write_all(line.as_bytes());
+    }
+
+    fn flush(&self) {
+        let _ = self.out.lock().unwrap_or_else(|e| e.into_inner()).flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Call, Callsite, Level};
+    use std::time::Duration;
+
+    static ADD: Callsite = Callsite {
+        fn_name: "add",
+        module: "app::math",
+        file: "src/math.rs",
+        line: 3,
+    };
+
+    #[test]
+    fn test_format() {
+        let call = Call::start(&ADD);
+        let enter = CallEvent::new(
+            &call,
+            Level::Debug,
+            EventKind::Enter,
+            &[("x", "1"), ("s", "\"a\"")],
+            None,
+            "add [in ]: x:1, s:\"a\"".to_string(),
+        );
+        assert_eq!(
+            format(&enter),
+            format!(
+                concat!(
+                    r#"{{"kind":"enter","level":"DEBUG","fn":"add","module":"app::math","#,
+                    r#""file":"src/math.rs","line":3,"thread":{},"call_id":{},"#,
+                    r#""params":{{"x":"1","s":"\"a\""}},"message":"add [in ]: x:1, s:\"a\""}}"#,
+                    "\n"
+                ),
+                enter.thread, call.id
+            )
+        );
+
+        let mut exit = CallEvent::new(
+            &call,
+            Level::Info,
+            EventKind::Exit,
+            &[],
+            Some("3"),
+            "add [out]: return:3".to_string(),
+        );
+        exit.elapsed = Some(Duration::from_micros(15));
+        assert!(format(&exit).ends_with(
+            r#""params":{},"ret":"3","elapsed_ns":15000,"message":"add [out]: return:3"}
+"#
+        ));
+    }
+}
@@ -0,0 +1,411 @@
+//! # Funlog Runtime
+//!
+//! Runtime support for code generated by `#[funlog]`. Use it through the `funlog`
+//! crate, which re-exports everything public here.
+//!
+//! Every instrumented call produces a [`CallEvent`] on entry and exit, carrying the
+//! function, its call site, the logged values, the return value, the elapsed time and
+//! ids for the call and thread. The events go to every sink registered with
+//! [`register_sink`]. Without registered sinks, `print` events go to [`PrintSink`]
+//! and the other levels to [`LogSink`], which forwards them to the `log` crate with
+//! the instrumented function's module as the target. [`set_enabled`] turns all output
+//! off or on at runtime, and [`capture`] records the calls made on the current thread
+//! for tests, which [`assert_trace_snapshot!`] compares with a stored trace. Functions
+//! instrumented with `stats` also count their calls, durations and `Err` returns, read
+//! with [`report`]. With the `metrics` feature, every call is also
+//! recorded with the installed `metrics` recorder as `funlog_calls_total` and
+//! `funlog_call_duration_seconds`, labelled with `fn`, `module` and `outcome`. With the
+//! `opentelemetry` feature, every call is a span of the global tracer provider.
+//! With the `record` feature, functions instrumented with `record` write their
+//! arguments and return values to a cassette that `#[funlog::replay_test]` replays.
+//!
+//! Some outputs are also turned on by environment variables, read on the first event,
+//! in addition to the sinks:
+//!
+//! - `FUNLOG_CHROME_TRACE=path` - Write a Chrome trace of every call to `path`, see
+//!   [`ChromeTraceSink`]
+//! - `FUNLOG_FOLDED=path` - Write the time spent in each stack of instrumented
+//!   functions to `path`, for flamegraphs, complete once an instrumented `main`
+//!   returns or after [`flush`], see [`FoldedStackSink::write_to`]
+//! - `FUNLOG_JSON=path` - Write every event to `path` as one line of JSON, for the
+//!   `funlog` command-line tool, see [`JsonLinesSink`]
+//! - `FUNLOG_RECORD=path` - Append the arguments and return value of every call of a
+//!   function instrumented with `record` to `path`, one JSON object per line
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use funlog_runtime::{register_sink, set_enabled, CallEvent, LogSink};
+//!
+//! // Keep the usual log output and also report slow calls
+//! register_sink(LogSink);
+//! register_sink(|event: &CallEvent| {
+//!     if event.elapsed.is_some_and(|elapsed| elapsed.as_millis() > 100) {
+//!         eprintln!("slow: {} ({:?})", event.fn_name, event.elapsed);
+//!     }
+//! });
+//! set_enabled(false);
+//! ```
+
+mod branch;
+mod capture;
+mod chrome;
+mod event;
+mod folded;
+mod json;
+mod json_lines;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(not(feature = "record"))]
+mod no_record;
+#[cfg(feature = "opentelemetry")]
+mod otel;
+#[cfg(feature = "record")]
+mod record;
+mod sink;
+mod snapshot;
+mod stats;
+
+pub use capture::{capture, Calls, CapturedCall};
+pub use chrome::ChromeTraceSink;
+pub use event::{thread_id, Call, CallEvent, Callsite, Entered, EventKind, Level};
+pub use folded::{FoldedStackSink, StackTime};
+pub use json_lines::JsonLinesSink;
+#[cfg(feature = "metrics")]
+pub use metrics::{CALLS_TOTAL, CALL_DURATION_SECONDS};
+pub use sink::{FunlogSink, LogSink, PrintSink};
+pub use snapshot::assert_snapshot;
+pub use stats::{report, CallStats, Report, SiteStats, LATENCY_BUCKETS};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+/// A snapshot of the registered sinks, replaced as a whole when they change.
+type Sinks = Arc<[Arc<dyn FunlogSink>]>;
+
+static SINKS: RwLock<Option<Sinks>> = RwLock::new(None);
+
+/// Turns the output of every instrumented function on or off.
+///
+/// Output is enabled by default. Disabling it skips every sink, and instrumented
+/// functions no longer format the values they log unless calls are being captured.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether events are emitted
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether instrumented functions emit events.
+///
+/// # Returns
+///
+/// Returns the value last passed to [`set_enabled`], `true` by default
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Adds a sink that receives every event, replacing the default output.
+///
+/// Sinks are called in the order they were registered.
+///
+/// # Arguments
+///
+/// * `sink` - The sink to add
+pub fn register_sink(sink: impl FunlogSink + 'static) {
+    let mut sinks = SINKS.write().unwrap_or_else(|e| e.into_inner());
+    let mut list = sinks.as_deref().unwrap_or_default().to_vec();
+    list.push(Arc::new(sink));
+    *sinks = Some(list.into());
+}
+
+/// Replaces every registered sink with `sink`.
+///
+/// # Arguments
+///
+/// * `sink` - The only sink to keep
+pub fn set_sink(sink: impl FunlogSink + 'static) {
+    let sink: Arc<dyn FunlogSink> = Arc::new(sink);
+    *SINKS.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new([sink]));
+}
+
+/// Removes every registered sink, restoring the default output.
+pub fn clear_sinks() {
+    *SINKS.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Flushes every registered sink and the outputs configured through environment
+/// variables.
+///
+/// Call it before the program exits so buffered output is complete, such as the
+/// profile of `FUNLOG_FOLDED` when `main` is not instrumented.
+pub fn flush() {
+    for sink in current_sinks().as_deref().unwrap_or_default() {
+        sink.flush();
+    }
+    for sink in env_sinks() {
+        sink.flush();
+    }
+}
+
+/// Releases what was kept for a call that will not return, because its body panicked
+/// or its future was dropped.
+///
+/// # Arguments
+///
+/// * `call_id` - The id of the call
+/// * `panicked` - Whether the body panicked
+fn abandon(call_id: u64, panicked: bool) {
+    #[cfg(feature = "opentelemetry")]
+    otel::abandon(call_id, panicked);
+    #[cfg(not(feature = "opentelemetry"))]
+    let _ = (call_id, panicked);
+}
+
+/// Returns a snapshot of the registered sinks, so the lock is not held while they run.
+fn current_sinks() -> Option<Sinks> {
+    SINKS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Returns the outputs configured through environment variables, created on first use.
+fn env_sinks() -> &'static [Box<dyn FunlogSink>] {
+    static ENV_SINKS: OnceLock<Vec<Box<dyn FunlogSink>>> = OnceLock::new();
+    ENV_SINKS.get_or_init(|| {
+        let mut sinks: Vec<Box<dyn FunlogSink>> = Vec::new();
+        if let Some(path) = std::env::var_os("FUNLOG_CHROME_TRACE") {
+            match ChromeTraceSink::create(&path) {
+                Ok(sink) => sinks.push(Box::new(sink)),
+                Err(e) => eprintln!(
+                    "funlog: cannot write FUNLOG_CHROME_TRACE file {}: {e}",
+                    std::path::Path::new(&path).display()
+                ),
+            }
+        }
+        if let Some(path) = std::env::var_os("FUNLOG_JSON") {
+            match JsonLinesSink::create(&path) {
+                Ok(sink) => sinks.push(Box::new(sink)),
+                Err(e) => eprintln!(
+                    "funlog: cannot write FUNLOG_JSON file {}: {e}",
+                    std::path::Path::new(&path).display()
+                ),
+            }
+        }
+        if let Some(path) = std::env::var_os("FUNLOG_FOLDED") {
+            sinks.push(Box::new(FoldedStackSink::new().write_to(path)));
+        }
+        sinks
+    })
+}
+
+/// Items used by generated code. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::__funlog_require_record as require_record;
+    pub use crate::branch::{Branch, FromResidual};
+    #[cfg(not(feature = "record"))]
+    pub use crate::no_record::{replay, Recording};
+    #[cfg(feature = "record")]
+    pub use crate::record::{replay, Recording};
+    pub use crate::{Call, CallStats, Callsite, EventKind, Level};
+    use crate::{CallEvent, FunlogSink, LogSink, PrintSink};
+
+    /// Records a finished call in the statistics of a function instrumented with
+    /// `stats`, in the `metrics` recorder and ends its OpenTelemetry span when those
+    /// features are on.
+    #[inline]
+    pub fn finish(call: &Call, stats: Option<&'static CallStats>, is_err: bool) {
+        if let Some(stats) = stats {
+            stats.record(call.started.elapsed(), is_err);
+        }
+        #[cfg(feature = "metrics")]
+        crate::metrics::record(call, is_err);
+        #[cfg(feature = "opentelemetry")]
+        crate::otel::finish(call, is_err);
+    }
+
+    /// Runs an async body with its call entered on every poll, so the calls it makes
+    /// find their parent however the executor interleaves them.
+    ///
+    /// # Arguments
+    ///
+    /// * `call` - The call the body belongs to
+    /// * `body` - The original body of the async function
+    ///
+    /// # Returns
+    ///
+    /// Returns the output of the body
+    pub async fn in_call<F: std::future::Future>(call: Call, body: F) -> F::Output {
+        /// Abandons the call if the future is dropped before the body completes
+        struct Pending(u64);
+
+        impl Drop for Pending {
+            fn drop(&mut self) {
+                crate::abandon(self.0, false);
+            }
+        }
+
+        let pending = Pending(call.id);
+        let mut body = std::pin::pin!(body);
+        let output = std::future::poll_fn(|cx| {
+            let _entered = call.enter();
+            body.as_mut().poll(cx)
+        })
+        .await;
+        std::mem::forget(pending);
+        output
+    }
+
+    /// Returns whether an event of `call` at any of `levels` would be received, so
+    /// generated code can skip formatting the logged values when it would not.
+    ///
+    /// Captures and recording OpenTelemetry spans receive every event; otherwise
+    /// output has to be enabled and there has to be a registered or environment sink,
+    /// or a `log` logger accepting one of the levels.
+    ///
+    /// # Arguments
+    ///
+    /// * `call` - The call about to emit its events
+    /// * `levels` - The levels of the events the call may emit
+    #[inline]
+    pub fn is_active(call: &Call, levels: &[Level]) -> bool {
+        #[cfg(not(feature = "opentelemetry"))]
+        let _ = call;
+        #[cfg(feature = "opentelemetry")]
+        if crate::otel::is_recording(call) {
+            return true;
+        }
+        if crate::capture::is_capturing() {
+            return true;
+        }
+        crate::is_enabled()
+            && (crate::current_sinks().is_some()
+                || !crate::env_sinks().is_empty()
+                || levels
+                    .iter()
+                    .any(|level| level.as_log().is_none_or(|level| level <= log::max_level())))
+    }
+
+    /// Emits one entry or exit event of an instrumented function.
+    pub fn emit(
+        call: &Call,
+        level: Level,
+        kind: EventKind,
+        params: &[(&'static str, &str)],
+        ret: Option<&str>,
+        message: std::fmt::Arguments<'_>,
+    ) {
+        #[cfg(feature = "opentelemetry")]
+        crate::otel::record(call, kind, params, ret);
+        let capturing = crate::capture::is_capturing();
+        let enabled = crate::is_enabled();
+        let (sinks, env_sinks) = if enabled {
+            (crate::current_sinks(), crate::env_sinks())
+        } else {
+            (None, &[][..])
+        };
+        let sinks = sinks.as_deref().unwrap_or_default();
+        // Skip building the event when nothing would receive it
+        let to_default = enabled
+            && sinks.is_empty()
+            && level.as_log().is_none_or(|level| level <= log::max_level());
+        if !capturing && sinks.is_empty() && env_sinks.is_empty() && !to_default {
+            return;
+        }
+
+        let event = CallEvent::new(call, level, kind, params, ret, message.to_string());
+        if capturing {
+            crate::capture::record(&event);
+        }
+        if to_default {
+            match level {
+                Level::Print => PrintSink.on_event(&event),
+                _ => LogSink.on_event(&event),
+            }
+        }
+        for sink in sinks {
+            sink.on_event(&event);
+        }
+        for sink in env_sinks {
+            sink.on_event(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    static CALLSITE: Callsite = Callsite {
+        fn_name: "add",
+        module: "app",
+        file: "src/main.rs",
+        line: 1,
+    };
+
+    #[test]
+    fn test_sinks_and_enabled() {
+        let first = Arc::new(Mutex::new(Vec::new()));
+        let second = Arc::new(Mutex::new(Vec::new()));
+        let collected = Arc::clone(&first);
+        set_sink(move |event: &CallEvent| collected.lock().unwrap().push(event.clone()));
+        let collected = Arc::clone(&second);
+        register_sink(move |event: &CallEvent| {
+            collected.lock().unwrap().push(event.message.clone())
+        });
+
+        let call = Call::start(&CALLSITE);
+        __private::emit(
+            &call,
+            Level::Debug,
+            EventKind::Enter,
+            &[("x", "1")],
+            None,
+            format_args!("add [in ]: x:{}", 1),
+        );
+        set_enabled(false);
+        assert!(!is_enabled());
+        __private::emit(
+            &call,
+            Level::Debug,
+            EventKind::Exit,
+            &[],
+            None,
+            format_args!("add [out]"),
+        );
+        set_enabled(true);
+        __private::emit(
+            &call,
+            Level::Debug,
+            EventKind::Exit,
+            &[],
+            Some("1"),
+            format_args!("add [out]: return:1"),
+        );
+        clear_sinks();
+        __private::emit(
+            &call,
+            Level::Trace,
+            EventKind::Exit,
+            &[],
+            None,
+            format_args!("add [out]"),
+        );
+
+        let first = first.lock().unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].kind, EventKind::Enter);
+        assert_eq!(first[0].level, Level::Debug);
+        assert_eq!(first[0].fn_name, "add");
+        assert_eq!(first[0].params, vec![("x", "1".to_string())]);
+        assert_eq!(first[0].message, "add [in ]: x:1");
+        assert_eq!(first[1].ret.as_deref(), Some("1"));
+        assert_eq!(first[0].call_id, first[1].call_id);
+        assert_eq!(
+            *second.lock().unwrap(),
+            vec!["add [in ]: x:1", "add [out]: return:1"]
+        );
+    }
+}
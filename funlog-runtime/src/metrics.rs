@@ -0,0 +1,28 @@
+use crate::event::Call;
+use ::metrics::{counter, histogram};
+
+/// The name of the counter of finished calls.
+pub const CALLS_TOTAL: &str = "funlog_calls_total";
+
+/// The name of the histogram of call durations, in seconds.
+pub const CALL_DURATION_SECONDS: &str = "funlog_call_duration_seconds";
+
+/// Records a finished call with the installed `metrics` recorder.
+///
+/// Both metrics are labelled with `fn`, `module` and `outcome`, which is `err` for
+/// calls that returned an `Err` and `ok` for the others.
+///
+/// # Arguments
+///
+/// * `call` - The call that returned
+/// * `is_err` - Whether the call returned an `Err`
+pub(crate) fn record(call: &Call, is_err: bool) {
+    let elapsed = call.started.elapsed();
+    let labels = [
+        ("fn", call.callsite.fn_name),
+        ("module", call.callsite.module),
+        ("outcome", if is_err { "err" } else { "ok" }),
+    ];
+    counter!(CALLS_TOTAL, &labels).increment(1);
+    histogram!(CALL_DURATION_SECONDS, &labels).record(elapsed.as_secs_f64());
+}
@@ -0,0 +1,40 @@
+//! Stand-ins for the items of the `record` feature when it is off, so that the
+//! generated code fails with one error that says to turn the feature on.
+
+use crate::event::Call;
+
+/// Fails to compile, naming the attribute that needs the `record` feature. Not part of
+/// the public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __funlog_require_record {
+    ($attribute:literal) => {
+        ::core::compile_error!(::core::concat!(
+            "`",
+            $attribute,
+            "` needs the `record` feature of funlog; enable it with \
+             `funlog = { version = \"...\", features = [\"record\"] }`"
+        ));
+    };
+}
+
+/// Never records anything.
+#[derive(Debug)]
+pub struct Recording;
+
+impl Recording {
+    pub fn start(_call: &Call) -> Option<Self> {
+        None
+    }
+
+    pub fn arg<T: ?Sized>(&mut self, _value: &T) {}
+
+    pub fn finish<T: ?Sized>(self, _ret: &T) {}
+}
+
+/// Never replays anything.
+pub fn replay<A, R, F>(_manifest_dir: &str, _path: &str, _module: &str, _fn_name: &str, _f: F)
+where
+    F: FnMut(A) -> R,
+{
+}
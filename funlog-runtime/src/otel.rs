@@ -0,0 +1,168 @@
+use crate::event::{Call, EventKind};
+use opentelemetry::trace::{Span, Status, TraceContextExt, Tracer};
+use opentelemetry::{global, Context, KeyValue};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The spans of the calls that have started and not yet returned, by call id.
+static OPEN: Mutex<Option<HashMap<u64, OpenSpan>>> = Mutex::new(None);
+
+/// The number of spans in [`OPEN`], so calls can skip the lock while there are none,
+/// as with the no-op tracer provider.
+static OPEN_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+struct OpenSpan {
+    /// The parent's context with the call's span, which the spans of its callees
+    /// start from
+    context: Context,
+    params_set: bool,
+    ret: Option<String>,
+}
+
+/// Locks the open spans, or returns `None` without locking if there are none.
+fn open_spans() -> Option<std::sync::MutexGuard<'static, Option<HashMap<u64, OpenSpan>>>> {
+    (OPEN_COUNT.load(Ordering::Acquire) > 0).then(|| OPEN.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Removes the span of a call from the open spans.
+fn take(call_id: u64) -> Option<OpenSpan> {
+    let mut open = open_spans()?;
+    let span = open.as_mut()?.remove(&call_id)?;
+    OPEN_COUNT.fetch_sub(1, Ordering::Release);
+    Some(span)
+}
+
+/// Starts the span of a call with the global tracer provider.
+///
+/// The span is a child of the span of [`Call::parent`], started from the context
+/// stored with it, or of the current OpenTelemetry context for an outermost call, so
+/// funlog spans nest inside the application's own. Spans with an invalid context, as
+/// from the no-op tracer provider, are not kept.
+///
+/// # Arguments
+///
+/// * `call` - The call that started
+pub(crate) fn start(call: &Call) {
+    let callsite = call.callsite;
+    let parent = call
+        .parent
+        .and_then(|parent| Some(open_spans()?.as_ref()?.get(&parent)?.context.clone()))
+        .unwrap_or_else(Context::current);
+    let tracer = global::tracer("funlog");
+    let span = tracer
+        .span_builder(callsite.fn_name)
+        .with_attributes([
+            KeyValue::new("code.function", callsite.fn_name),
+            KeyValue::new("code.namespace", callsite.module),
+            KeyValue::new("code.filepath", callsite.file),
+            KeyValue::new("code.lineno", i64::from(callsite.line)),
+        ])
+        .start_with_context(&tracer, &parent);
+    if !span.span_context().is_valid() {
+        return;
+    }
+    let mut open = OPEN.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = open.get_or_insert_with(HashMap::new).insert(
+        call.id,
+        OpenSpan {
+            context: parent.with_span(span),
+            params_set: false,
+            ret: None,
+        },
+    );
+    if previous.is_none() {
+        OPEN_COUNT.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// Returns whether the span of a call records what is added to it, which is false
+/// for the no-op tracer used until a provider is installed.
+///
+/// # Arguments
+///
+/// * `call` - The call whose span to check
+pub(crate) fn is_recording(call: &Call) -> bool {
+    open_spans().is_some_and(|open| {
+        open.as_ref()
+            .and_then(|open| open.get(&call.id))
+            .is_some_and(|open| open.context.span().is_recording())
+    })
+}
+
+/// Adds the logged values of an entry or exit event to the span of its call.
+///
+/// The parameters become attributes named after their labels, once per call, and the
+/// return value becomes a `return` event with a `return.value` attribute.
+///
+/// # Arguments
+///
+/// * `call` - The call the event belongs to
+/// * `kind` - Whether the event is the entry or the exit
+/// * `params` - The logged parameters and fields, as `Debug` text
+/// * `ret` - The logged return value, as `Debug` text
+pub(crate) fn record(
+    call: &Call,
+    kind: EventKind,
+    params: &[(&'static str, &str)],
+    ret: Option<&str>,
+) {
+    let Some(mut open) = open_spans() else {
+        return;
+    };
+    let Some(open) = open.as_mut().and_then(|open| open.get_mut(&call.id)) else {
+        return;
+    };
+    let span = open.context.span();
+    if !open.params_set && !params.is_empty() {
+        open.params_set = true;
+        span.set_attributes(
+            params
+                .iter()
+                .map(|(label, value)| KeyValue::new(*label, value.to_string())),
+        );
+    }
+    if let (EventKind::Exit, Some(ret)) = (kind, ret) {
+        span.add_event(
+            "return",
+            vec![KeyValue::new("return.value", ret.to_string())],
+        );
+        open.ret = Some(ret.to_string());
+    }
+}
+
+/// Ends the span of a call.
+///
+/// # Arguments
+///
+/// * `call` - The call that returned
+/// * `is_err` - Whether the call returned an `Err`, which sets the span status to an
+///   error described by the logged return value, if any
+pub(crate) fn finish(call: &Call, is_err: bool) {
+    let Some(OpenSpan { context, ret, .. }) = take(call.id) else {
+        return;
+    };
+    let span = context.span();
+    if is_err {
+        span.set_status(Status::error(ret.unwrap_or_else(|| "Err".to_string())));
+    }
+    span.end();
+}
+
+/// Ends the span of a call that will not return, because its body panicked or its
+/// future was dropped, if it is still open.
+///
+/// # Arguments
+///
+/// * `call_id` - The id of the call
+/// * `panicked` - Whether the body panicked, which sets the span status to an error
+pub(crate) fn abandon(call_id: u64, panicked: bool) {
+    let Some(OpenSpan { context, .. }) = take(call_id) else {
+        return;
+    };
+    let span = context.span();
+    if panicked {
+        span.set_status(Status::error("panicked"));
+    }
+    span.end();
+}
@@ -0,0 +1,286 @@
+use crate::event::Call;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, LineWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// The cassette named by `FUNLOG_RECORD`, opened on the first recorded call.
+fn cassette() -> Option<&'static Mutex<LineWriter<File>>> {
+    static CASSETTE: OnceLock<Option<Mutex<LineWriter<File>>>> = OnceLock::new();
+    CASSETTE
+        .get_or_init(|| {
+            let path = std::env::var_os("FUNLOG_RECORD")?;
+            match OpenOptions::new().create(true).append(true).open(&path) {
+                Ok(file) => Some(Mutex::new(LineWriter::new(file))),
+                Err(e) => {
+                    eprintln!(
+                        "funlog: cannot write FUNLOG_RECORD file {}: {e}",
+                        Path::new(&path).display()
+                    );
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+/// Expands to nothing; without the `record` feature it is a compile error naming the
+/// attribute that needs it. Not part of the public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __funlog_require_record {
+    ($attribute:literal) => {};
+}
+
+/// The arguments of one call of a function instrumented with `record`, written to the
+/// cassette with the return value when the call returns.
+///
+/// Each call is one line of JSON, appended to the file named by `FUNLOG_RECORD`:
+/// `{"fn":"add","module":"app","args":[1,2],"ret":3}`, with the arguments in
+/// declaration order, `self` excluded.
+#[derive(Debug)]
+pub struct Recording {
+    fn_name: &'static str,
+    module: &'static str,
+    args: Vec<String>,
+    error: Option<serde_json::Error>,
+}
+
+impl Recording {
+    /// Starts recording a call, if `FUNLOG_RECORD` is set and output is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `call` - The call to record
+    pub fn start(call: &Call) -> Option<Self> {
+        if !crate::is_enabled() {
+            return None;
+        }
+        cassette()?;
+        Some(Self {
+            fn_name: call.callsite.fn_name,
+            module: call.callsite.module,
+            args: Vec::new(),
+            error: None,
+        })
+    }
+
+    /// Records the next argument.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The argument, before the function body runs
+    pub fn arg<T: Serialize + ?Sized>(&mut self, value: &T) {
+        match serde_json::to_string(value) {
+            Ok(json) => self.args.push(json),
+            Err(e) => self.error = self.error.take().or(Some(e)),
+        }
+    }
+
+    /// Writes the call to the cassette.
+    ///
+    /// A call whose arguments or return value cannot be serialized is reported on
+    /// standard error and left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `ret` - The return value
+    pub fn finish<T: Serialize + ?Sized>(self, ret: &T) {
+        let ret = match (self.error, serde_json::to_string(ret)) {
+            (None, Ok(ret)) => ret,
+            (Some(e), _) | (None, Err(e)) => {
+                eprintln!(
+                    "funlog: cannot record call of {}::{}: {e}",
+                    self.module, self.fn_name
+                );
+                return;
+            }
+        };
+        let mut line = String::from("{\"fn\":");
+        crate::json::push_str(&mut line, self.fn_name);
+        line.push_str(",\"module\":");
+        crate::json::push_str(&mut line, self.module);
+        line.push_str(",\"args\":[");
+        line.push_str(&self.args.join(","));
+        line.push_str("],\"ret\":");
+        line.push_str(&ret);
+        line.push_str("}\n");
+        if let Some(cassette) = cassette() {
+            let mut cassette = cassette.lock().unwrap_or_else(|e| e.into_inner());
+            if let Err(e) = cassette.write_all(line.as_bytes()) {
+                eprintln!("funlog: cannot write to FUNLOG_RECORD file: {e}");
+            }
+        }
+    }
+}
+
+/// Replays the calls of `module::fn_name` recorded in a cassette and panics, listing
+/// every difference, if any call returns something other than what was recorded.
+///
+/// A test usually sits in a module inside the one it replays, such as `tests` next to
+/// the function, so the calls of `fn_name` replayed are those recorded in `module` or,
+/// if there are none, in the nearest module that contains it. Calls of functions with
+/// the same name in other modules are skipped.
+///
+/// Return values are compared as JSON, so they need not implement `PartialEq` or
+/// `Deserialize`.
+///
+/// # Arguments
+///
+/// * `manifest_dir` - The directory that a relative `path` is relative to
+/// * `path` - The cassette
+/// * `module` - The module of the function, or a module inside it
+/// * `fn_name` - The function whose calls to replay; other lines are skipped
+/// * `f` - Calls the function with one recorded tuple of arguments
+#[track_caller]
+pub fn replay<A, R, F>(manifest_dir: &str, path: &str, module: &str, fn_name: &str, mut f: F)
+where
+    A: DeserializeOwned,
+    R: Serialize,
+    F: FnMut(A) -> R,
+{
+    let path = Path::new(manifest_dir).join(path);
+    let file = File::open(&path)
+        .unwrap_or_else(|e| panic!("cannot open cassette {}: {e}", path.display()));
+    let mut records = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+        if line.trim().is_empty() {
+            continue;
+        }
+        let location = format!("{}:{}", path.display(), index + 1);
+        let record: Value = serde_json::from_str(&line)
+            .unwrap_or_else(|e| panic!("{location}: invalid cassette line: {e}"));
+        if record["fn"] == fn_name {
+            records.push((location, record));
+        }
+    }
+    // The innermost module around `module`, itself included, with recorded calls
+    let recorded = records
+        .iter()
+        .filter_map(|(_, record)| record["module"].as_str())
+        .filter(|recorded| {
+            module == *recorded
+                || module
+                    .strip_prefix(recorded)
+                    .is_some_and(|rest| rest.starts_with("::"))
+        })
+        .max_by_key(|recorded| recorded.len())
+        .unwrap_or_else(|| {
+            panic!(
+                "no calls of {module}::{fn_name} or of {fn_name} in a module containing it \
+                 recorded in {}",
+                path.display()
+            )
+        })
+        .to_string();
+    let name = format!("{recorded}::{fn_name}");
+    let mut replayed = 0;
+    let mut failures = Vec::new();
+    for (location, record) in &records {
+        if record["module"] != recorded.as_str() {
+            continue;
+        }
+        let args = match &record["args"] {
+            // A function without arguments takes `()`, which JSON writes as `null`
+            Value::Array(args) if args.is_empty() => Value::Null,
+            args => args.clone(),
+        };
+        let args = serde_json::from_value::<A>(args.clone()).unwrap_or_else(|e| {
+            panic!("{location}: cannot deserialize the arguments {args} of {name}: {e}")
+        });
+        let ret = serde_json::to_value(f(args))
+            .unwrap_or_else(|e| panic!("{location}: cannot serialize the return value: {e}"));
+        if ret != record["ret"] {
+            failures.push(format!(
+                "{location}: {name}{} returned {ret}, recorded {}",
+                record["args"], record["ret"]
+            ));
+        }
+        replayed += 1;
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {replayed} replayed calls of {name} differ from the cassette:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cassette(name: &str, lines: &[&str]) -> String {
+        let path = std::env::temp_dir().join(format!(
+            "funlog-cassette-{name}-{}.jsonl",
+            std::process::id()
+        ));
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_replay() {
+        let path = cassette(
+            "ok",
+            &[
+                r#"{"fn":"add","module":"app","args":[1,2],"ret":3}"#,
+                r#"{"fn":"now","module":"app","args":[],"ret":7}"#,
+                "",
+                r#"{"fn":"add","module":"app","args":[-4,4],"ret":0}"#,
+                r#"{"fn":"add","module":"app::strings","args":["a","b"],"ret":"ab"}"#,
+            ],
+        );
+        let mut calls = Vec::new();
+        replay("/", &path, "app", "add", |(x, y): (i32, i32)| {
+            calls.push((x, y));
+            x + y
+        });
+        assert_eq!(calls, [(1, 2), (-4, 4)]);
+        replay("/", &path, "app::tests", "now", |(): ()| 7);
+        // The innermost module with recorded calls wins over the modules around it
+        replay(
+            "/",
+            &path,
+            "app::strings::tests",
+            "add",
+            |(x, y): (String, String)| x + &y,
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_mismatch() {
+        let path = cassette(
+            "mismatch",
+            &[
+                r#"{"fn":"add","module":"app","args":[1,2],"ret":3}"#,
+                r#"{"fn":"add","module":"app","args":[2,2],"ret":4}"#,
+            ],
+        );
+        let result = std::panic::catch_unwind(|| {
+            replay("/", &path, "app", "add", |(x, y): (i32, i32)| x * y);
+        });
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("1 of 2 replayed calls of app::add differ from the cassette:"));
+        assert!(message.ends_with(&format!("{path}:1: app::add[1,2] returned 2, recorded 3")));
+
+        for (module, fn_name) in [("app", "sub"), ("other", "add"), ("application", "add")] {
+            let result =
+                std::panic::catch_unwind(|| replay("/", &path, module, fn_name, |(): ()| 0));
+            let message = *result.unwrap_err().downcast::<String>().unwrap();
+            assert_eq!(
+                message,
+                format!(
+                    "no calls of {module}::{fn_name} or of {fn_name} in a module containing it \
+                     recorded in {path}"
+                )
+            );
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+use crate::event::CallEvent;
+
+/// A consumer of the events emitted by instrumented functions.
+///
+/// Register sinks with [`register_sink`](crate::register_sink) to replace the default
+/// output, which sends `print` events to [`PrintSink`] and the others to [`LogSink`].
+/// Register those two explicitly to keep the default output alongside your own sinks.
+///
+/// # Examples
+///
+/// ```
+/// use funlog_runtime::{CallEvent, FunlogSink};
+/// use std::sync::Mutex;
+///
+/// #[derive(Default)]
+/// struct Collect(Mutex<Vec<String>>);
+///
+/// impl FunlogSink for Collect {
+///     fn on_event(&self, event: &CallEvent) {
+///         self.0.lock().unwrap().push(event.message.clone());
+///     }
+/// }
+/// ```
+pub trait FunlogSink: Send + Sync {
+    /// Handles one entry or exit event.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event
+    fn on_event(&self, event: &CallEvent);
+
+    /// Writes out anything the sink has buffered, called by [`flush`](crate::flush).
+    ///
+    /// Sinks that write each event as it arrives need not implement it.
+    fn flush(&self) {}
+}
+
+impl<F> FunlogSink for F
+where
+    F: Fn(&CallEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &CallEvent) {
+        self(event)
+    }
+}
+
+/// Writes the message of every event to standard output.
+///
+/// This is the sink used for `print` events when no sink is registered.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PrintSink;
+
+impl FunlogSink for PrintSink {
+    fn on_event(&self, event: &CallEvent) {
+        println!("{}", event.message);
+    }
+}
+
+/// Forwards every event to the `log` crate at its level.
+///
+/// Records use the instrumented function's module as their target, so `RUST_LOG`
+/// filters apply as they would to a `log::debug!` written in that module. `print`
+/// events are logged at `Info`. This is the sink used for the other levels when no
+/// sink is registered.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogSink;
+
+impl FunlogSink for LogSink {
+    fn on_event(&self, event: &CallEvent) {
+        let level = event.level.as_log().unwrap_or(log::Level::Info);
+        if level <= log::STATIC_MAX_LEVEL && level <= log::max_level() {
+            log::logger().log(
+                &log::Record::builder()
+                    .level(level)
+                    .target(event.module)
+                    .module_path_static(Some(event.module))
+                    .file_static(Some(event.file))
+                    .line(Some(event.line))
+                    .args(format_args!("{}", event.message))
+                    .build(),
+            );
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use crate::capture::Calls;
+use crate::event::EventKind;
+use std::fs;
+use std::path::Path;
+
+/// Set to update stored snapshots instead of comparing against them.
+const UPDATE_VAR: &str = "FUNLOG_UPDATE_SNAPSHOTS";
+
+impl Calls {
+    /// Returns the log lines of the captured calls as a stable text for snapshots.
+    ///
+    /// Each entry and exit is one line with the text funlog logs for it, indented by
+    /// two spaces for every instrumented call it is nested in. The elapsed time of a
+    /// call is left out, since it changes from run to run, and so are the other values
+    /// that do: hexadecimal addresses become `0x[addr]` and `ThreadId(3)` becomes
+    /// `ThreadId([id])`. Logged values that merely look like durations, such as
+    /// `"30s"`, are kept as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use funlog_runtime::capture;
+    ///
+    /// let calls = capture(|| {});
+    /// assert_eq!(calls.trace(), "");
+    /// ```
+    pub fn trace(&self) -> String {
+        let mut out = String::new();
+        let mut open = Vec::new();
+        for event in self.events() {
+            if event.kind == EventKind::Exit {
+                if let Some(index) = open.iter().rposition(|id| *id == event.call_id) {
+                    open.truncate(index);
+                }
+            }
+            for _ in 0..open.len() {
+                out.push_str("  ");
+            }
+            out.push_str(&scrub(&event.message));
+            out.push('\n');
+            if event.kind == EventKind::Enter {
+                open.push(event.call_id);
+            }
+        }
+        out
+    }
+}
+
+/// Replaces the parts of `text` that differ between runs with placeholders.
+fn scrub(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let after_word = out
+            .chars()
+            .next_back()
+            .is_some_and(|prev| prev.is_alphanumeric() || prev == '_' || prev == '.');
+        if !after_word {
+            if let Some(len) = address_len(rest) {
+                out.push_str("0x[addr]");
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        if let Some(ids) = rest.strip_prefix("ThreadId(") {
+            let digits = ids.len() - ids.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if digits > 0 && ids[digits..].starts_with(')') {
+                out.push_str("ThreadId([id])");
+                rest = &ids[digits + 1..];
+                continue;
+            }
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Returns the length of the `0x...` address at the start of `text`, if any.
+fn address_len(text: &str) -> Option<usize> {
+    let digits = text.strip_prefix("0x")?;
+    let len = digits.len()
+        - digits
+            .trim_start_matches(|c: char| c.is_ascii_hexdigit())
+            .len();
+    let end = &digits[len..];
+    (len > 0 && !end.starts_with(|c: char| c.is_alphanumeric() || c == '_')).then_some(2 + len)
+}
+
+/// Compares `actual` with the snapshot stored at `path` and panics if they differ.
+///
+/// When the snapshot is missing or different, the actual text is written next to it
+/// with a `.new` extension for review. Setting `FUNLOG_UPDATE_SNAPSHOTS=1` writes the
+/// actual text to the snapshot instead, creating its directory if needed, and passes.
+/// Line endings are normalised before comparing.
+///
+/// # Arguments
+///
+/// * `path` - The stored snapshot
+/// * `actual` - The text to compare, usually [`Calls::trace`]
+///
+/// # Examples
+///
+/// ```no_run
+/// use funlog_runtime::{assert_snapshot, capture};
+///
+/// let calls = capture(|| {
+///     // call instrumented functions here
+/// });
+/// assert_snapshot("tests/snapshots/empty.snap", &calls.trace());
+/// ```
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+    let new_path = path.with_extension(match path.extension() {
+        Some(extension) => format!("{}.new", extension.to_string_lossy()),
+        None => "new".to_string(),
+    });
+    let update = std::env::var_os(UPDATE_VAR).is_some_and(|value| value != "0");
+    if update {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .unwrap_or_else(|e| panic!("cannot create {}: {e}", dir.display()));
+        }
+        fs::write(path, actual)
+            .unwrap_or_else(|e| panic!("cannot write snapshot {}: {e}", path.display()));
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(_) => {
+            write_new(&new_path, actual);
+            panic!(
+                "missing snapshot {}; review {} and rename it, or rerun with {UPDATE_VAR}=1\n{actual}",
+                path.display(),
+                new_path.display()
+            );
+        }
+    };
+    if expected == actual.replace("\r\n", "\n") {
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+    write_new(&new_path, actual);
+    panic!(
+        "snapshot {} does not match; rerun with {UPDATE_VAR}=1 to accept the new trace in {}\n{}",
+        path.display(),
+        new_path.display(),
+        diff(&expected, actual)
+    );
+}
+
+fn write_new(path: &Path, actual: &str) {
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = fs::write(path, actual);
+}
+
+/// Returns a line diff of two texts, with `-` for removed and `+` for added lines.
+fn diff(expected: &str, actual: &str) -> String {
+    let old = expected.lines().collect::<Vec<_>>();
+    let new = actual.lines().collect::<Vec<_>>();
+    // Longest common subsequence lengths of every pair of suffixes
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            out.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    out.join("\n")
+}
+
+/// Captures the calls made by a closure and compares their [`Calls::trace`] with the
+/// snapshot `tests/snapshots/<name>.snap` of the crate being tested.
+///
+/// See [`assert_snapshot`] for updating snapshots with `FUNLOG_UPDATE_SNAPSHOTS=1`.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[funlog(debug, retVal)]
+/// fn fib(n: u32) -> u32 {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// }
+///
+/// #[test]
+/// fn test_fib_calls() {
+///     funlog::assert_trace_snapshot!("fib", || {
+///         fib(3);
+///     });
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_trace_snapshot {
+    ($name:expr, $f:expr $(,)?) => {
+        $crate::assert_snapshot(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.snap", $name)),
+            &$crate::capture($f).trace(),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub() {
+        assert_eq!(
+            scrub("ptr:0x7ffd5e8c1a20, n:15, t:ThreadId(7)"),
+            "ptr:0x[addr], n:15, t:ThreadId([id])"
+        );
+        assert_eq!(
+            scrub("timeout:\"30s\", [2s, 1.25ms] 0xfg x0x1 ThreadId(x)"),
+            "timeout:\"30s\", [2s, 1.25ms] 0xfg x0x1 ThreadId(x)"
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb\nc\n", "a\nc\nd\n"), "  a\n- b\n  c\n+ d");
+    }
+
+    #[test]
+    fn test_assert_snapshot() {
+        let dir = std::env::temp_dir().join(format!("funlog-snapshots-{}", std::process::id()));
+        let path = dir.join("trace.snap");
+        let new_path = dir.join("trace.snap.new");
+
+        let missing = std::panic::catch_unwind(|| assert_snapshot(&path, "a\n"));
+        assert!(missing.is_err());
+        assert_eq!(fs::read_to_string(&new_path).unwrap(), "a\n");
+
+        fs::rename(&new_path, &path).unwrap();
+        assert_snapshot(&path, "a\n");
+        let changed = std::panic::catch_unwind(|| assert_snapshot(&path, "b\n"));
+        let message = *changed.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.ends_with("- a\n+ b"));
+        assert!(new_path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,272 @@
+use crate::event::Callsite;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The upper bounds of the latency histogram buckets; a last bucket holds the calls
+/// that took longer.
+pub const LATENCY_BUCKETS: [Duration; 7] = [
+    Duration::from_micros(1),
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+];
+
+/// Every `CallStats` that has recorded a call, in the order of their first call.
+static REGISTRY: Mutex<Vec<&'static CallStats>> = Mutex::new(Vec::new());
+
+/// The counters of one function instrumented with `stats`.
+///
+/// Generated code keeps one `CallStats` per function as a `static`. Recording a call
+/// only updates atomics; the first call also adds the counters to the list read by
+/// [`report`].
+#[derive(Debug)]
+pub struct CallStats {
+    callsite: &'static Callsite,
+    registered: AtomicBool,
+    calls: AtomicU64,
+    errors: AtomicU64,
+    total_nanos: AtomicU64,
+    min_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+    histogram: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+}
+
+impl CallStats {
+    /// Creates empty counters for the function at `callsite`.
+    ///
+    /// # Arguments
+    ///
+    /// * `callsite` - Where the function is defined
+    pub const fn new(callsite: &'static Callsite) -> Self {
+        Self {
+            callsite,
+            registered: AtomicBool::new(false),
+            calls: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            min_nanos: AtomicU64::new(u64::MAX),
+            max_nanos: AtomicU64::new(0),
+            histogram: [const { AtomicU64::new(0) }; LATENCY_BUCKETS.len() + 1],
+        }
+    }
+
+    /// Records one call.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed` - How long the call took
+    /// * `is_err` - Whether the call returned an `Err`
+    pub fn record(&'static self, elapsed: Duration, is_err: bool) {
+        if !self.registered.swap(true, Ordering::AcqRel) {
+            REGISTRY
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(self);
+        }
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        if is_err {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.min_nanos.fetch_min(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| elapsed < *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.histogram[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Reads the counters.
+    ///
+    /// Counters are read one at a time, so a snapshot taken while calls are running may
+    /// be off by the calls in progress.
+    pub fn snapshot(&self) -> SiteStats {
+        let calls = self.calls.load(Ordering::Relaxed);
+        let min_nanos = self.min_nanos.load(Ordering::Relaxed);
+        SiteStats {
+            fn_name: self.callsite.fn_name,
+            module: self.callsite.module,
+            file: self.callsite.file,
+            line: self.callsite.line,
+            calls,
+            errors: self.errors.load(Ordering::Relaxed),
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            min: Duration::from_nanos(if calls == 0 { 0 } else { min_nanos }),
+            max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+            histogram: std::array::from_fn(|i| self.histogram[i].load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// The statistics of one function, as read by [`report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteStats {
+    /// The name of the function
+    pub fn_name: &'static str,
+    /// The module path of the function
+    pub module: &'static str,
+    /// The source file of the function
+    pub file: &'static str,
+    /// The line of the `#[funlog]` attribute
+    pub line: u32,
+    /// The number of calls that returned
+    pub calls: u64,
+    /// The number of calls that returned an `Err`
+    pub errors: u64,
+    /// The time spent in all calls
+    pub total: Duration,
+    /// The shortest call
+    pub min: Duration,
+    /// The longest call
+    pub max: Duration,
+    /// The number of calls in each latency bucket, see [`LATENCY_BUCKETS`]
+    pub histogram: [u64; LATENCY_BUCKETS.len() + 1],
+}
+
+impl SiteStats {
+    /// Returns the mean duration of a call, zero if there were none.
+    pub fn mean(&self) -> Duration {
+        match u32::try_from(self.calls) {
+            Ok(0) => Duration::ZERO,
+            Ok(calls) => self.total / calls,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.calls as f64),
+        }
+    }
+
+    /// Returns the fraction of calls that returned an `Err`, from 0 to 1.
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+}
+
+/// Returns the statistics of every function instrumented with `stats` that has been
+/// called, sorted by total time, longest first.
+///
+/// # Examples
+///
+/// ```
+/// let report = funlog_runtime::report();
+/// println!("{report}");
+/// for stats in report.entries() {
+///     assert!(stats.min <= stats.max);
+/// }
+/// ```
+pub fn report() -> Report {
+    let mut entries = REGISTRY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|stats| stats.snapshot())
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| b.total.cmp(&a.total).then(a.fn_name.cmp(b.fn_name)));
+    Report { entries }
+}
+
+/// A snapshot of call statistics, displayed as a table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    entries: Vec<SiteStats>,
+}
+
+impl Report {
+    /// Returns the statistics of each function, longest total time first.
+    pub fn entries(&self) -> &[SiteStats] {
+        &self.entries
+    }
+
+    /// Returns the statistics of the function called `fn_name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `fn_name` - The name of the function; the first match is returned when several
+    ///   modules define one with that name
+    pub fn get(&self, fn_name: &str) -> Option<&SiteStats> {
+        self.entries.iter().find(|stats| stats.fn_name == fn_name)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self
+            .entries
+            .iter()
+            .map(|stats| format!("{}::{}", stats.module, stats.fn_name))
+            .collect::<Vec<_>>();
+        let width = names.iter().map(String::len).max().unwrap_or(0).max(8);
+        write!(
+            f,
+            "{:<width$} {:>8} {:>8} {:>7} {:>10} {:>10} {:>10} {:>10}",
+            "function", "calls", "errors", "err%", "total", "mean", "min", "max"
+        )?;
+        for (stats, name) in self.entries.iter().zip(&names) {
+            write!(
+                f,
+                "\n{:<width$} {:>8} {:>8} {:>6.1}% {:>10} {:>10} {:>10} {:>10}",
+                name,
+                stats.calls,
+                stats.errors,
+                stats.error_rate() * 100.0,
+                format!("{:.1?}", stats.total),
+                format!("{:.1?}", stats.mean()),
+                format!("{:.1?}", stats.min),
+                format!("{:.1?}", stats.max),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PARSE: Callsite = Callsite {
+        fn_name: "parse",
+        module: "app",
+        file: "src/main.rs",
+        line: 1,
+    };
+    static PARSE_STATS: CallStats = CallStats::new(&PARSE);
+
+    #[test]
+    fn test_call_stats() {
+        assert_eq!(PARSE_STATS.snapshot().min, Duration::ZERO);
+        PARSE_STATS.record(Duration::from_micros(50), false);
+        PARSE_STATS.record(Duration::from_millis(2), true);
+        PARSE_STATS.record(Duration::from_secs(3), false);
+
+        let stats = PARSE_STATS.snapshot();
+        assert_eq!(stats.calls, 3);
+        assert_eq!(stats.errors, 1);
+        assert_eq!(stats.min, Duration::from_micros(50));
+        assert_eq!(stats.max, Duration::from_secs(3));
+        assert_eq!(stats.total, Duration::from_micros(3_002_050));
+        assert_eq!(
+            stats.mean(),
+            Duration::from_micros(1_000_683) + Duration::from_nanos(333)
+        );
+        assert!((stats.error_rate() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats.histogram, [0, 0, 1, 0, 1, 0, 0, 1]);
+
+        let report = report();
+        assert_eq!(report.get("parse"), Some(&stats));
+        let table = report.to_string();
+        let mut lines = table.lines();
+        assert!(lines.next().unwrap().starts_with("function "));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("app::parse"));
+        assert!(row.contains(" 33.3% "));
+        assert!(row.ends_with("3.0s"));
+    }
+}
@@ -0,0 +1,208 @@
+use funlog::funlog;
+use std::env::set_var;
+use std::future::Future;
+use std::panic::Location;
+use std::pin::pin;
+use std::str::FromStr;
+use std::task::{Context, Poll, Waker};
+
+/// Doubles its input.
+#[must_use]
+#[inline]
+#[funlog(debug, all, retVal)]
+fn doubled(x: i32) -> i32 {
+    x * 2
+}
+
+#[deprecated(note = "use doubled")]
+#[funlog(debug, none)]
+fn old_doubled(x: i32) -> i32 {
+    x * 2
+}
+
+#[cfg(any())]
+#[funlog(debug)]
+fn never_compiled() -> DoesNotExist {}
+
+#[track_caller]
+#[funlog(debug, none)]
+fn caller_line() -> u32 {
+    Location::caller().line()
+}
+
+#[derive(Debug)]
+struct Stepper {
+    step: u32,
+}
+
+impl Stepper {
+    // Methods and functions naming `Self` run their body in the wrapper itself
+    #[track_caller]
+    #[funlog(debug, none, retVal)]
+    fn new(step: u32) -> (Self, u32) {
+        (Self { step }, Location::caller().line())
+    }
+
+    #[track_caller]
+    #[funlog(debug, none, retVal)]
+    fn caller_line(&self) -> u32 {
+        Location::caller().line() + self.step
+    }
+
+    #[track_caller]
+    #[funlog(debug, all, retVal)]
+    fn parse_step(&self, input: &str) -> Result<u32, String> {
+        let n = input.parse::<u32>().map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Ok(self.step);
+        }
+        Ok(n * self.step)
+    }
+}
+
+// `#[track_caller]` has no effect on async functions, instrumented or not
+#[allow(ungated_async_fn_track_caller)]
+#[track_caller]
+#[funlog(debug, none, retVal)]
+async fn async_caller_line() -> (u32, u32) {
+    (Location::caller().line(), line!())
+}
+
+#[funlog(debug, none, retVal)]
+fn parse_as<T: FromStr + std::fmt::Debug>(input: &str) -> Option<T> {
+    input.parse().ok()
+}
+
+#[funlog(debug, all, retVal)]
+fn first_n<const N: usize>(items: &[u8]) -> [u8; N] {
+    let mut out = [0; N];
+    out.copy_from_slice(&items[..N]);
+    out
+}
+
+#[funlog(debug, all, retVal)]
+async fn async_add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+/// # Safety
+///
+/// `ptr` must be valid for reads.
+#[funlog(debug, none, retVal)]
+unsafe fn read_ptr(ptr: *const i32) -> i32 {
+    *ptr
+}
+
+#[funlog(debug, all, retVal)]
+extern "C" fn c_callback(x: i32) -> i32 {
+    x + 1
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    match future.as_mut().poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future was not ready"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_logger::MockLogger;
+
+    #[test]
+    fn test_outer_attributes_are_kept() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(doubled(4), 8);
+        #[allow(deprecated)]
+        let old = old_doubled(2);
+        assert_eq!(old, 4);
+
+        let line = line!() + 1;
+        assert_eq!(caller_line(), line);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 6);
+            assert_eq!(entries[0].body, "doubled [in ]: x:4");
+            assert_eq!(entries[1].body, "doubled [out]: return:8");
+            assert_eq!(entries[2].body, "old_doubled [in ]");
+            assert_eq!(entries[4].body, "caller_line [in ]");
+        });
+    }
+
+    #[test]
+    fn test_track_caller_methods() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        let line = line!() + 1;
+        let (stepper, new_line) = Stepper::new(2);
+        assert_eq!(new_line, line);
+        let line = line!() + 1;
+        assert_eq!(stepper.caller_line(), line + 2);
+        assert_eq!(stepper.parse_step("3"), Ok(6));
+        assert_eq!(stepper.parse_step("0"), Ok(2));
+        assert!(stepper.parse_step("x").is_err());
+        let (caller, body) = block_on(async_caller_line());
+        assert_eq!(caller, body);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 12);
+            assert_eq!(
+                entries[3].body,
+                format!("caller_line [out]: return:{}", line + 2)
+            );
+            assert_eq!(entries[4].body, "parse_step [in ]: input:\"3\"");
+            assert_eq!(entries[5].body, "parse_step [out]: return:Ok(6)");
+            assert_eq!(entries[7].body, "parse_step [out]: return:Ok(2)");
+            assert_eq!(
+                entries[9].body,
+                "parse_step [out]: return:Err(\"invalid digit found in string\")"
+            );
+            assert_eq!(entries[10].body, "async_caller_line [in ]");
+        });
+    }
+
+    #[test]
+    fn test_generic_functions() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(parse_as::<u8>("42"), Some(42));
+        assert_eq!(first_n::<2>(&[1, 2, 3]), [1, 2]);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 4);
+            assert_eq!(entries[1].body, "parse_as [out]: return:Some(42)");
+            assert_eq!(entries[2].body, "first_n [in ]: items:[1, 2, 3]");
+            assert_eq!(entries[3].body, "first_n [out]: return:[1, 2]");
+        });
+    }
+
+    #[test]
+    fn test_qualified_functions() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(block_on(async_add(1, 2)), 3);
+        let value = 7;
+        assert_eq!(unsafe { read_ptr(&value) }, 7);
+        assert_eq!(c_callback(1), 2);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 6);
+            assert_eq!(entries[0].body, "async_add [in ]: a:1, b:2");
+            assert_eq!(entries[1].body, "async_add [out]: return:3");
+            assert_eq!(entries[3].body, "read_ptr [out]: return:7");
+            assert_eq!(entries[5].body, "c_callback [out]: return:2");
+        });
+    }
+}
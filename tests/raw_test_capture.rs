@@ -0,0 +1,73 @@
+use funlog::{assert_called, assert_not_called, funlog};
+
+#[funlog(debug, all, retVal)]
+fn add(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+#[funlog(trace, params(name), onEnd)]
+fn greet(name: &str, times: u32) -> String {
+    name.repeat(times as usize)
+}
+
+#[funlog(info, fields(n = values.len()))]
+fn total(values: &[i32]) -> i32 {
+    values.iter().fold(0, |sum, v| add(sum, *v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_calls() {
+        let calls = funlog::capture(|| {
+            assert_eq!(total(&[1, 2]), 3);
+            assert_eq!(greet("ab", 2), "abab");
+        });
+
+        assert_eq!(calls.names(), vec!["total", "add", "add", "greet"]);
+        assert_called!(calls, "total", n = 2);
+        assert_called!(calls, "add", x = 0, y = 1);
+        assert_called!(calls, "add", x = 1, y = 2);
+        assert_called!(calls, "greet", name = "ab");
+        assert_not_called!(calls, "add", x = 5);
+        assert_not_called!(calls, "sub");
+
+        let adds = calls.named("add").collect::<Vec<_>>();
+        assert_eq!(adds[1].ret.as_deref(), Some("3"));
+        assert_eq!(calls[3].param("times"), None);
+        assert_eq!(calls[3].ret, None);
+        assert!(calls.iter().all(|call| call.module == "raw_test_capture"));
+    }
+
+    #[test]
+    fn test_capture_is_per_thread() {
+        let handles = (0..4)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    let calls = funlog::capture(|| {
+                        add(i, i);
+                    });
+                    assert_eq!(calls.len(), 1);
+                    assert_called!(calls, "add", x = i, y = i);
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_capture_ignores_set_enabled() {
+        // Only sinks are turned off; the calls are still captured
+        funlog::set_enabled(false);
+        let calls = funlog::capture(|| {
+            add(2, 2);
+        });
+        funlog::set_enabled(true);
+        assert_called!(calls, "add", x = 2, y = 2);
+        assert!(funlog::capture(|| {}).is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+use funlog::funlog;
+use serde_json::Value;
+
+#[funlog(debug, all, retVal)]
+fn add(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+#[funlog(info, params(name), onEnd, retVal)]
+fn greet(name: &str) -> usize {
+    add(name.len() as i32, 1) as usize
+}
+
+/// Runs without the test harness, so the variable is set before any call or other
+/// thread: `FUNLOG_CHROME_TRACE` is read once per process.
+fn main() {
+    let path = std::env::temp_dir().join(format!("funlog-trace-{}.json", std::process::id()));
+    unsafe {
+        std::env::set_var("FUNLOG_CHROME_TRACE", &path);
+    }
+    assert_eq!(greet("bob"), 4);
+
+    // Close the array the way trace viewers do
+    let trace = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let trace = format!("{}]", trace.trim_end().trim_end_matches(','));
+    let events: Vec<Value> = serde_json::from_str(&trace).unwrap();
+
+    let phases = events
+        .iter()
+        .map(|e| (e["name"].as_str().unwrap(), e["ph"].as_str().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        phases,
+        vec![("add", "b"), ("add", "e"), ("greet", "b"), ("greet", "e")]
+    );
+    assert_eq!(events[0]["args"]["x"], "3");
+    assert_eq!(events[0]["args"]["y"], "1");
+    assert_eq!(events[1]["args"]["return"], "4");
+    assert_eq!(events[0]["id"], events[1]["id"]);
+    assert_eq!(events[2]["id"], events[3]["id"]);
+    assert_ne!(events[0]["id"], events[2]["id"]);
+    assert_eq!(events[3]["args"]["name"], "\"bob\"");
+    assert_eq!(events[3]["args"]["return"], "4");
+    assert_eq!(events[3]["cat"], "raw_test_chrome_trace");
+    assert_eq!(events[0]["pid"], std::process::id());
+    assert_eq!(events[0]["tid"], funlog::thread_id());
+
+    // The `greet` slice, written on its exit, starts when the call did and encloses
+    // the `add` slice
+    let ts = |i: usize| events[i]["ts"].as_f64().unwrap();
+    assert!(ts(2) <= ts(0) && ts(0) <= ts(1) && ts(1) <= ts(3));
+}
@@ -0,0 +1,61 @@
+use funlog::funlog;
+use std::env::set_var;
+
+#[derive(Debug)]
+struct Request {
+    id: u32,
+    payload: Vec<u8>,
+}
+
+#[funlog(debug, fields(id = req.id, n = items.len()))]
+fn handle(req: Request, items: Vec<String>) -> usize {
+    req.payload.len() + items.len()
+}
+
+#[funlog(debug, params(tag), fields(id = req.id), onEnd)]
+fn tag_request(tag: &str, req: Request) -> String {
+    format!("{tag}-{}", req.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_logger::MockLogger;
+
+    #[test]
+    fn test_fields_logging() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        let req = Request {
+            id: 7,
+            payload: vec![0; 1024],
+        };
+        assert_eq!(handle(req, vec!["a".to_string(), "b".to_string()]), 1026);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].body, "handle [in ]: id:7, n:2");
+            assert_eq!(entries[1].body, "handle [out]");
+        });
+    }
+
+    #[test]
+    fn test_fields_logging_on_end() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        let req = Request {
+            id: 3,
+            payload: Vec::new(),
+        };
+        assert_eq!(tag_request("job", req), "job-3");
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].body, "tag_request [out]: tag:\"job\", id:3");
+        });
+    }
+}
@@ -0,0 +1,123 @@
+use funlog::funlog;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+#[funlog(debug, none)]
+fn parse(input: &str) -> usize {
+    input.split(',').count()
+}
+
+#[funlog(debug, none)]
+fn load(inputs: &[&str]) -> usize {
+    inputs.iter().map(|input| parse(input)).sum()
+}
+
+#[funlog(trace, none)]
+fn run() -> usize {
+    load(&["a,b", "c"]) + parse("d")
+}
+
+/// Returns `Pending` once, so callers interleave when polled alternately
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[funlog(debug, none)]
+async fn step(n: usize) -> usize {
+    YieldNow(false).await;
+    n
+}
+
+#[funlog(debug, none)]
+async fn task(n: usize) -> usize {
+    step(n).await + step(n).await
+}
+
+/// Runs without the test harness, so the variable is set before any call or other
+/// thread: `FUNLOG_FOLDED` is read once per process.
+fn main() {
+    let path = std::env::temp_dir().join(format!("funlog-folded-{}.txt", std::process::id()));
+    unsafe {
+        std::env::set_var("FUNLOG_FOLDED", &path);
+    }
+    // The first outermost return writes the file, without a flush
+    assert_eq!(run(), 4);
+
+    let folded = std::fs::read_to_string(&path).unwrap();
+    let stacks = folded
+        .lines()
+        .map(|line| {
+            let (stack, nanos) = line.rsplit_once(' ').unwrap();
+            assert!(nanos.parse::<u128>().is_ok());
+            stack
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        stacks,
+        vec![
+            "raw_test_folded::run",
+            "raw_test_folded::run;raw_test_folded::load",
+            "raw_test_folded::run;raw_test_folded::load;raw_test_folded::parse",
+            "raw_test_folded::run;raw_test_folded::parse",
+        ]
+    );
+
+    let profile = funlog::FoldedStackSink::new();
+    funlog::register_sink(profile.clone());
+    load(&["x"]);
+    funlog::clear_sinks();
+
+    let stacks = profile.stacks();
+    let load = &stacks["raw_test_folded::load"];
+    let parse = &stacks["raw_test_folded::load;raw_test_folded::parse"];
+    assert_eq!(load.calls, 1);
+    assert_eq!(parse.calls, 1);
+    assert!(load.total_time >= parse.total_time);
+    assert_eq!(load.self_time + parse.total_time, load.total_time);
+
+    // Two async calls polled alternately on one thread keep separate stacks
+    let profile = funlog::FoldedStackSink::new();
+    funlog::register_sink(profile.clone());
+    let mut first = Box::pin(task(1));
+    let mut second = Box::pin(task(2));
+    let mut cx = Context::from_waker(Waker::noop());
+    let (mut a, mut b) = (None, None);
+    while a.is_none() || b.is_none() {
+        if let Poll::Ready(n) = first.as_mut().poll(&mut cx) {
+            a = Some(n);
+        }
+        if let Poll::Ready(n) = second.as_mut().poll(&mut cx) {
+            b = Some(n);
+        }
+    }
+    funlog::clear_sinks();
+    assert_eq!((a, b), (Some(2), Some(4)));
+
+    let stacks = profile.stacks();
+    assert_eq!(
+        stacks.keys().collect::<Vec<_>>(),
+        vec![
+            "raw_test_folded::task",
+            "raw_test_folded::task;raw_test_folded::step"
+        ]
+    );
+    assert_eq!(stacks["raw_test_folded::task"].calls, 2);
+    assert_eq!(
+        stacks["raw_test_folded::task;raw_test_folded::step"].calls,
+        4
+    );
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,175 @@
+use funlog::funlog;
+use std::env::set_var;
+
+#[derive(Debug)]
+struct Counter {
+    value: i32,
+}
+
+impl Counter {
+    #[funlog(debug, all, retVal)]
+    fn new(value: i32) -> Self {
+        Self { value }
+    }
+
+    #[funlog(debug, all, retVal)]
+    fn get(&self) -> i32 {
+        self.value
+    }
+
+    #[funlog(debug, all, retVal)]
+    fn add(&mut self, by: i32) -> i32 {
+        self.value += by;
+        self.value
+    }
+}
+
+struct Other;
+
+impl Other {
+    // Same name as `Counter::get`; the generated code must not collide
+    #[funlog(debug, none, retVal)]
+    fn get(&self) -> i32 {
+        -1
+    }
+}
+
+#[funlog(debug, all, retVal)]
+fn parse_number(input: &str) -> Result<i32, std::num::ParseIntError> {
+    let number = input.trim().parse::<i32>()?;
+    Ok(number * 2)
+}
+
+#[funlog(debug, all, retVal)]
+async fn parse_later(input: &str) -> Result<i32, std::num::ParseIntError> {
+    let number = input.parse::<i32>()?;
+    if number < 0 {
+        return Ok(0);
+    }
+    Ok(number)
+}
+
+#[funlog(debug, all, retVal)]
+fn clamp(x: i32) -> i32 {
+    if x > 10 {
+        return 10;
+    }
+    x
+}
+
+#[funlog(debug, all, retVal)]
+fn factorial(n: u64) -> u64 {
+    if n <= 1 {
+        1
+    } else {
+        n * factorial(n - 1)
+    }
+}
+
+#[funlog(debug, all)]
+fn evens(limit: u32) -> impl Iterator<Item = u32> {
+    (0..limit).filter(|n| n % 2 == 0)
+}
+
+#[funlog(debug, all, retVal)]
+fn first_word(text: &str) -> &str {
+    text.split(' ').next().unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_logger::MockLogger;
+
+    fn init() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+    }
+
+    #[test]
+    fn test_methods_with_receivers() {
+        init();
+        let mut counter = Counter::new(1);
+        assert_eq!(counter.add(2), 3);
+        assert_eq!(counter.get(), 3);
+        assert_eq!(Other.get(), -1);
+
+        MockLogger::entries(|entries| {
+            let bodies = entries.iter().map(|e| e.body.as_str()).collect::<Vec<_>>();
+            assert!(bodies.contains(&"add [in ]: by:2"));
+            assert!(bodies.contains(&"add [out]: return:3"));
+            assert!(bodies.contains(&"get [out]: return:3"));
+            assert!(bodies.contains(&"get [out]: return:-1"));
+        });
+    }
+
+    #[test]
+    fn test_question_mark_still_logs_end() {
+        init();
+        assert!(parse_number("nope").is_err());
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].body, "parse_number [in ]: input:\"nope\"");
+            assert!(entries[1]
+                .body
+                .starts_with("parse_number [out]: return:Err("));
+        });
+    }
+
+    #[test]
+    fn test_async_question_mark_and_return() {
+        use std::future::Future;
+        use std::pin::pin;
+        use std::task::{Context, Poll, Waker};
+
+        let mut future =
+            pin!(async { (parse_later("-4").await, parse_later("x").await.is_err(),) });
+        let mut cx = Context::from_waker(Waker::noop());
+        let Poll::Ready((clamped, failed)) = future.as_mut().poll(&mut cx) else {
+            panic!("future should complete immediately");
+        };
+        assert_eq!(clamped, Ok(0));
+        assert!(failed);
+    }
+
+    #[test]
+    fn test_early_return_still_logs_end() {
+        init();
+        assert_eq!(clamp(42), 10);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[1].body, "clamp [out]: return:10");
+        });
+    }
+
+    #[test]
+    fn test_recursion_logs_every_call() {
+        init();
+        assert_eq!(factorial(3), 6);
+
+        MockLogger::entries(|entries| {
+            let bodies = entries.iter().map(|e| e.body.as_str()).collect::<Vec<_>>();
+            assert_eq!(
+                bodies,
+                vec![
+                    "factorial [in ]: n:3",
+                    "factorial [in ]: n:2",
+                    "factorial [in ]: n:1",
+                    "factorial [out]: return:1",
+                    "factorial [out]: return:2",
+                    "factorial [out]: return:6",
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_impl_trait_and_borrowed_returns() {
+        assert_eq!(evens(5).collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(first_word("hello world"), "hello");
+    }
+}
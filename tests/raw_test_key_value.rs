@@ -0,0 +1,52 @@
+use funlog::funlog;
+use std::env::set_var;
+
+#[funlog(level = "info", position = "end", ret = true)]
+fn total(prices: &[u32]) -> u32 {
+    prices.iter().sum()
+}
+
+#[funlog(level = debug, params(x), position = start)]
+fn announce(x: i32, _y: i32) {}
+
+#[funlog(start(level = debug), end(level = warn), ret = true)]
+fn phased(x: i32) -> i32 {
+    x * 2
+}
+
+#[funlog(onEnd, end(level = info), ret = false)]
+fn exit_only(x: i32) -> i32 {
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_logger::MockLogger;
+
+    #[test]
+    fn test_key_value_options() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(total(&[1, 2, 3]), 6);
+        announce(7, 8);
+        assert_eq!(phased(5), 10);
+        assert_eq!(exit_only(3), 3);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 5);
+            assert_eq!(entries[0].level, log::Level::Info);
+            assert_eq!(entries[0].body, "total [out]: prices:[1, 2, 3], return:6");
+            assert_eq!(entries[1].level, log::Level::Debug);
+            assert_eq!(entries[1].body, "announce [in ]: x:7");
+            assert_eq!(entries[2].level, log::Level::Debug);
+            assert_eq!(entries[2].body, "phased [in ]: x:5");
+            assert_eq!(entries[3].level, log::Level::Warn);
+            assert_eq!(entries[3].body, "phased [out]: return:10");
+            assert_eq!(entries[4].level, log::Level::Info);
+            assert_eq!(entries[4].body, "exit_only [out]: x:3");
+        });
+    }
+}
@@ -0,0 +1,69 @@
+use funlog::funlog;
+
+#[funlog(debug, none)]
+fn parse_port(text: &str) -> Result<u16, std::num::ParseIntError> {
+    text.parse()
+}
+
+#[funlog(print, onStart, when = "false")]
+fn ping() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use metrics_util::MetricKind;
+
+    #[test]
+    fn test_call_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            assert!(parse_port("80").is_ok());
+            assert!(parse_port("http").is_err());
+            assert!(parse_port("443").is_ok());
+            // Recorded even when the guard skips the logs
+            ping();
+        });
+
+        let mut counters = Vec::new();
+        let mut histograms = Vec::new();
+        for (key, _, _, value) in snapshotter.snapshot().into_vec() {
+            let (kind, key) = key.into_parts();
+            let mut labels = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect::<Vec<_>>();
+            labels.sort();
+            let labels = labels.join(",");
+            match (kind, value) {
+                (MetricKind::Counter, DebugValue::Counter(count)) => {
+                    assert_eq!(key.name(), "funlog_calls_total");
+                    counters.push((labels, count));
+                }
+                (MetricKind::Histogram, DebugValue::Histogram(values)) => {
+                    assert_eq!(key.name(), "funlog_call_duration_seconds");
+                    assert!(values.iter().all(|seconds| seconds.0 >= 0.0));
+                    histograms.push((labels, values.len() as u64));
+                }
+                other => panic!("unexpected metric {other:?}"),
+            }
+        }
+        counters.sort();
+        histograms.sort();
+
+        let expected = vec![
+            (
+                "fn=parse_port,module=raw_test_metrics,outcome=err".to_string(),
+                1,
+            ),
+            (
+                "fn=parse_port,module=raw_test_metrics,outcome=ok".to_string(),
+                2,
+            ),
+            ("fn=ping,module=raw_test_metrics,outcome=ok".to_string(), 1),
+        ];
+        assert_eq!(counters, expected);
+        assert_eq!(histograms, expected);
+    }
+}
@@ -0,0 +1,225 @@
+use funlog::funlog;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+#[funlog(debug, retVal)]
+fn checkout(cart_id: u32, items: usize) -> Result<u32, String> {
+    let total = price(items);
+    if cart_id == 0 {
+        return Err("empty cart".to_string());
+    }
+    Ok(total)
+}
+
+#[funlog(trace, onEnd)]
+fn price(items: usize) -> u32 {
+    items as u32 * 5
+}
+
+/// Returns `Pending` once, so callers interleave when polled alternately
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[funlog(debug, retVal)]
+async fn fetch(id: u32) -> u32 {
+    YieldNow(false).await;
+    id * 10
+}
+
+#[funlog(debug, retVal)]
+async fn handle(id: u32) -> u32 {
+    fetch(id).await + fetch(id + 1).await
+}
+
+#[funlog(debug)]
+fn explode(reason: &str) {
+    panic!("{reason}");
+}
+
+#[funlog(debug)]
+fn settle(n: u32) -> u32 {
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::Status;
+    use opentelemetry::{Key, Value};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+    use std::sync::OnceLock;
+
+    fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a Value> {
+        span.attributes
+            .iter()
+            .find(|kv| kv.key == Key::from(key.to_string()))
+            .map(|kv| &kv.value)
+    }
+
+    /// Installs the global tracer provider once for every test, returning its exporter.
+    fn exporter() -> &'static InMemorySpanExporter {
+        static EXPORTER: OnceLock<InMemorySpanExporter> = OnceLock::new();
+        EXPORTER.get_or_init(|| {
+            let exporter = InMemorySpanExporter::default();
+            let provider = SdkTracerProvider::builder()
+                .with_simple_exporter(exporter.clone())
+                .build();
+            opentelemetry::global::set_tracer_provider(provider);
+            exporter
+        })
+    }
+
+    /// Returns the finished spans of the functions named in `names`, in end order.
+    fn finished(names: &[&str]) -> Vec<SpanData> {
+        exporter()
+            .get_finished_spans()
+            .unwrap()
+            .into_iter()
+            .filter(|span| names.contains(&span.name.as_ref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_spans() {
+        exporter();
+        assert_eq!(checkout(7, 2), Ok(10));
+        assert!(checkout(0, 1).is_err());
+
+        let spans = finished(&["price", "checkout"]);
+        let names = spans.iter().map(|s| s.name.as_ref()).collect::<Vec<_>>();
+        assert_eq!(names, ["price", "checkout", "price", "checkout"]);
+        let (price, ok, err) = (&spans[0], &spans[1], &spans[3]);
+
+        // Nested calls are child spans in the same trace
+        assert_eq!(price.parent_span_id, ok.span_context.span_id());
+        assert_eq!(price.span_context.trace_id(), ok.span_context.trace_id());
+        assert!(!price.parent_span_is_remote);
+        assert_ne!(ok.span_context.trace_id(), err.span_context.trace_id());
+
+        assert_eq!(
+            attribute(ok, "code.function"),
+            Some(&Value::from("checkout"))
+        );
+        assert_eq!(
+            attribute(ok, "code.namespace"),
+            Some(&Value::from("raw_test_opentelemetry"))
+        );
+        assert!(matches!(
+            attribute(ok, "code.filepath"),
+            Some(Value::String(path)) if path.as_str().ends_with("raw_test_opentelemetry.rs")
+        ));
+        assert_eq!(attribute(ok, "code.lineno"), Some(&Value::I64(6)));
+        assert_eq!(attribute(ok, "cart_id"), Some(&Value::from("7")));
+        assert_eq!(attribute(ok, "items"), Some(&Value::from("2")));
+        assert_eq!(attribute(price, "items"), Some(&Value::from("2")));
+
+        // The return value is an event, and an `Err` sets the status
+        assert_eq!(ok.status, Status::Unset);
+        let event = ok.events.events.first().unwrap();
+        assert_eq!(event.name, "return");
+        assert_eq!(
+            event.attributes,
+            [opentelemetry::KeyValue::new("return.value", "Ok(10)")]
+        );
+        assert_eq!(err.status, Status::error("Err(\"empty cart\")"));
+        assert!(ok.start_time <= price.start_time && price.end_time <= ok.end_time);
+    }
+
+    #[test]
+    fn test_interleaved_async_spans() {
+        exporter();
+        let mut first = Box::pin(handle(1));
+        let mut second = Box::pin(handle(5));
+        let mut cx = Context::from_waker(Waker::noop());
+        let (mut a, mut b) = (None, None);
+        while a.is_none() || b.is_none() {
+            if let Poll::Ready(n) = first.as_mut().poll(&mut cx) {
+                a = Some(n);
+            }
+            if let Poll::Ready(n) = second.as_mut().poll(&mut cx) {
+                b = Some(n);
+            }
+        }
+        assert_eq!((a, b), (Some(30), Some(110)));
+
+        // Each `fetch` is a child of the `handle` call that awaited it, whatever order
+        // the calls were polled and returned in
+        let spans = finished(&["handle", "fetch"]);
+        let handles = spans
+            .iter()
+            .filter(|s| s.name == "handle")
+            .collect::<Vec<_>>();
+        assert_eq!(handles.len(), 2);
+        for handle in &handles {
+            let return_value = &handle.events.events[0].attributes[0].value;
+            let fetches = spans
+                .iter()
+                .filter(|s| s.parent_span_id == handle.span_context.span_id())
+                .collect::<Vec<_>>();
+            assert_eq!(fetches.len(), 2);
+            for fetch in &fetches {
+                assert_eq!(fetch.name, "fetch");
+                assert_eq!(
+                    fetch.span_context.trace_id(),
+                    handle.span_context.trace_id()
+                );
+            }
+            let ids = fetches
+                .iter()
+                .map(|fetch| attribute(fetch, "id").unwrap().to_string())
+                .collect::<Vec<_>>();
+            let expected = if *return_value == Value::from("30") {
+                ["1", "2"]
+            } else {
+                ["5", "6"]
+            };
+            assert_eq!(ids, expected);
+        }
+        assert_ne!(
+            handles[0].span_context.trace_id(),
+            handles[1].span_context.trace_id()
+        );
+
+        // A future dropped before it completes ends its spans
+        let mut dropped = Box::pin(handle(9));
+        assert!(dropped.as_mut().poll(&mut cx).is_pending());
+        drop(dropped);
+        let spans = finished(&["handle", "fetch"]);
+        assert_eq!(spans.len(), 8);
+        assert_eq!(spans[6].name, "fetch");
+        assert_eq!(spans[7].name, "handle");
+        assert_eq!(spans[6].parent_span_id, spans[7].span_context.span_id());
+    }
+
+    #[test]
+    fn test_panicking_call_ends_span() {
+        exporter();
+        let result = std::panic::catch_unwind(|| explode("boom"));
+        assert!(result.is_err());
+        // The next call is an outermost call, not a child of the one that panicked
+        assert_eq!(settle(3), 3);
+
+        let spans = finished(&["explode", "settle"]);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].status, Status::error("panicked"));
+        assert_ne!(spans[1].parent_span_id, spans[0].span_context.span_id());
+        assert_ne!(
+            spans[1].span_context.trace_id(),
+            spans[0].span_context.trace_id()
+        );
+    }
+}
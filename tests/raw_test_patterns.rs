@@ -0,0 +1,123 @@
+use funlog::funlog;
+use std::env::set_var;
+
+#[derive(Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Marker;
+
+#[funlog(debug, all, retVal)]
+fn add_pair((a, b): (i32, i32)) -> i32 {
+    a + b
+}
+
+#[funlog(debug, params(x), retVal)]
+fn shift(Point { x, y }: Point, _: Marker, mut by: i32) -> i32 {
+    by += 1;
+    x + y + by
+}
+
+// Names bound by patterns are values in guards and fields, like plain parameters
+#[funlog(debug, all, retVal, when = "a > 0 && b != a")]
+fn positive_pair((a, b): (i32, i32)) -> i32 {
+    a * b
+}
+
+#[funlog(debug, none, retVal, when = "x > 0 && y == 2", fields(sum = x + y))]
+fn on_right(Point { x, y }: Point) -> bool {
+    x > y
+}
+
+#[funlog(debug, all, when = "first + last > 10 && label == \"big\"")]
+fn ends((first, .., last): (u32, u8, u8, u32), &label: &&str) -> u32 {
+    let _ = label;
+    first + last
+}
+
+#[funlog(debug, all, onEnd)]
+fn count_down(mut n: u32) -> u32 {
+    while n > 0 {
+        n -= 1;
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_logger::MockLogger;
+
+    #[test]
+    fn test_tuple_pattern() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(add_pair((2, 3)), 5);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].body, "add_pair [in ]: a:2, b:3");
+            assert_eq!(entries[1].body, "add_pair [out]: return:5");
+        });
+    }
+
+    #[test]
+    fn test_struct_and_wildcard_patterns() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(shift(Point { x: 1, y: 2 }, Marker, 3), 7);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].body, "shift [in ]: x:1");
+            assert_eq!(entries[1].body, "shift [out]: return:7");
+        });
+    }
+
+    #[test]
+    fn test_when_on_patterns() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(positive_pair((2, 3)), 6);
+        assert_eq!(positive_pair((-2, 3)), -6);
+        assert_eq!(positive_pair((2, 2)), 4);
+        assert!(on_right(Point { x: 3, y: 2 }));
+        assert!(!on_right(Point { x: -3, y: 2 }));
+        assert_eq!(ends((9, 0, 0, 4), &"big"), 13);
+        assert_eq!(ends((1, 0, 0, 4), &"big"), 5);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 6);
+            assert_eq!(entries[0].body, "positive_pair [in ]: a:2, b:3");
+            assert_eq!(entries[1].body, "positive_pair [out]: return:6");
+            assert_eq!(entries[2].body, "on_right [in ]: sum:5");
+            assert_eq!(entries[3].body, "on_right [out]: return:true");
+            assert_eq!(
+                entries[4].body,
+                "ends [in ]: first:9, last:4, label:\"big\""
+            );
+        });
+    }
+
+    #[test]
+    fn test_mut_parameter() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(count_down(3), 0);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].body, "count_down [out]: n:3");
+        });
+    }
+}
@@ -0,0 +1,62 @@
+use funlog::funlog;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    name: String,
+    cents: u32,
+}
+
+#[funlog(debug, record)]
+fn total(items: &[Item], discount: u32) -> Result<u32, String> {
+    let sum = items.iter().map(|item| item.cents).sum::<u32>();
+    sum.checked_sub(discount)
+        .ok_or_else(|| format!("discount {discount} exceeds {sum}"))
+}
+
+#[funlog(trace, record, when = "false")]
+fn next_id() -> u64 {
+    42
+}
+
+#[funlog(trace)]
+fn not_recorded(x: u8) -> u8 {
+    x
+}
+
+/// Runs without the test harness, so the variable is set before any call or other
+/// thread: `FUNLOG_RECORD` is read once per process.
+fn main() {
+    let path = std::env::temp_dir().join(format!("funlog-record-{}.jsonl", std::process::id()));
+    unsafe {
+        std::env::set_var("FUNLOG_RECORD", &path);
+    }
+
+    let items = vec![
+        Item {
+            name: "tea".to_string(),
+            cents: 250,
+        },
+        Item {
+            name: "cake".to_string(),
+            cents: 400,
+        },
+    ];
+    assert_eq!(total(&items, 50), Ok(600));
+    assert!(total(&items[..1], 300).is_err());
+    // Recorded even when the guard skips the logs
+    assert_eq!(next_id(), 42);
+    not_recorded(1);
+
+    let cassette = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let lines = cassette.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        [
+            r#"{"fn":"total","module":"raw_test_record","args":[[{"name":"tea","cents":250},{"name":"cake","cents":400}],50],"ret":{"Ok":600}}"#,
+            r#"{"fn":"total","module":"raw_test_record","args":[[{"name":"tea","cents":250}],300],"ret":{"Err":"discount 300 exceeds 250"}}"#,
+            r#"{"fn":"next_id","module":"raw_test_record","args":[],"ret":42}"#,
+        ]
+    );
+}
@@ -0,0 +1,53 @@
+use funlog::funlog;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    name: String,
+    cents: u32,
+}
+
+#[funlog(debug)]
+fn total(items: &[Item], discount: u32) -> Result<u32, String> {
+    let sum = items.iter().map(|item| item.cents).sum::<u32>();
+    sum.checked_sub(discount)
+        .ok_or_else(|| format!("discount {discount} exceeds {sum}"))
+}
+
+// The cassette was recorded from `raw_test_record.rs`
+#[funlog::replay_test("tests/cassettes/total.jsonl", module = "raw_test_record")]
+fn replay_total(items: Vec<Item>, discount: u32) -> Result<u32, String> {
+    total(&items, discount)
+}
+
+#[funlog::replay_test(
+    "tests/cassettes/total.jsonl",
+    module = "raw_test_record",
+    name = "next_id"
+)]
+fn replay_ids() -> u64 {
+    42
+}
+
+#[funlog::replay_test(
+    "tests/cassettes/total.jsonl",
+    module = "raw_test_record",
+    name = "total"
+)]
+#[should_panic(
+    expected = "1 of 3 replayed calls of raw_test_record::total differ from the cassette"
+)]
+fn replay_total_regression(items: Vec<Item>, discount: u32) -> Result<u32, String> {
+    // A discount bug that the second recorded call catches
+    Ok(items
+        .iter()
+        .map(|item| item.cents)
+        .sum::<u32>()
+        .saturating_sub(discount))
+}
+
+#[funlog::replay_test("tests/cassettes/total.jsonl", name = "total")]
+#[should_panic(expected = "no calls of raw_test_replay::total or of total in a module")]
+fn replay_total_in_another_module(items: Vec<Item>, discount: u32) -> Result<u32, String> {
+    total(&items, discount)
+}
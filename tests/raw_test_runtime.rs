@@ -0,0 +1,141 @@
+use funlog::{funlog, CallEvent, EventKind, Level};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[funlog(debug, all, retVal)]
+fn add(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+#[funlog(start(level = trace), end(level = info), none)]
+fn tick() {}
+
+#[funlog(info, all, fields(n = values.len()), retVal)]
+fn scale(values: Vec<i32>, factor: i32) -> Vec<i32> {
+    values.into_iter().map(|v| v * factor).collect()
+}
+
+static FORMATTED: AtomicUsize = AtomicUsize::new(0);
+
+/// A value that counts how often it is formatted
+struct Counted;
+
+impl fmt::Debug for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        FORMATTED.fetch_add(1, Ordering::Relaxed);
+        write!(f, "Counted")
+    }
+}
+
+#[funlog(debug, all)]
+fn inspect(_value: &Counted) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sinks are global, so tests that register them must not overlap
+    static SINKS: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_sink_receives_events() {
+        let _guard = SINKS.lock().unwrap();
+        let events = Arc::new(Mutex::new(Vec::<CallEvent>::new()));
+        let collected = Arc::clone(&events);
+        funlog::set_sink(move |event: &CallEvent| collected.lock().unwrap().push(event.clone()));
+
+        assert_eq!(add(1, 2), 3);
+        tick();
+
+        // Nothing is emitted while output is disabled
+        funlog::set_enabled(false);
+        assert_eq!(add(5, 5), 10);
+        funlog::set_enabled(true);
+        funlog::clear_sinks();
+
+        let events = events.lock().unwrap();
+        let summary = events
+            .iter()
+            .map(|e| (e.kind, e.level, e.fn_name, e.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (EventKind::Enter, Level::Debug, "add", "add [in ]: x:1, y:2"),
+                (EventKind::Exit, Level::Debug, "add", "add [out]: return:3"),
+                (EventKind::Enter, Level::Trace, "tick", "tick [in ]"),
+                (EventKind::Exit, Level::Info, "tick", "tick [out]"),
+            ]
+        );
+        assert_eq!(events[0].module, "raw_test_runtime");
+        assert_eq!(events[0].file, "tests/raw_test_runtime.rs");
+        assert_eq!(events[0].line, 6);
+    }
+
+    #[test]
+    fn test_event_fields() {
+        let _guard = SINKS.lock().unwrap();
+        let events = Arc::new(Mutex::new(Vec::<CallEvent>::new()));
+        let messages = Arc::new(Mutex::new(Vec::<String>::new()));
+        let collected = Arc::clone(&events);
+        funlog::register_sink(move |event: &CallEvent| {
+            if event.fn_name == "scale" {
+                collected.lock().unwrap().push(event.clone())
+            }
+        });
+        let collected = Arc::clone(&messages);
+        funlog::register_sink(move |event: &CallEvent| {
+            if event.fn_name == "scale" {
+                collected.lock().unwrap().push(event.to_string())
+            }
+        });
+
+        assert_eq!(scale(vec![1, 2], 3), vec![3, 6]);
+        assert_eq!(scale(vec![], 2), Vec::<i32>::new());
+        funlog::clear_sinks();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 4);
+        let (enter, exit) = (&events[0], &events[1]);
+        assert_eq!(
+            enter.params,
+            vec![
+                ("values", "[1, 2]".to_string()),
+                ("factor", "3".to_string()),
+                ("n", "2".to_string())
+            ]
+        );
+        assert_eq!(enter.ret, None);
+        assert_eq!(enter.elapsed, None);
+        assert!(exit.params.is_empty());
+        assert_eq!(exit.ret.as_deref(), Some("[3, 6]"));
+        assert!(exit.elapsed.is_some());
+        assert_eq!(enter.call_id, exit.call_id);
+        assert_ne!(enter.call_id, events[2].call_id);
+        assert_eq!(enter.thread, funlog::thread_id());
+        assert_eq!(
+            messages.lock().unwrap()[..2],
+            [
+                "scale [in ]: values:[1, 2], factor:3, n:2",
+                "scale [out]: return:[3, 6]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_values_not_formatted_without_receiver() {
+        let _guard = SINKS.lock().unwrap();
+        funlog::set_enabled(false);
+        inspect(&Counted);
+        funlog::set_enabled(true);
+        // No sink is registered and no logger accepts `debug`
+        inspect(&Counted);
+        assert_eq!(FORMATTED.load(Ordering::Relaxed), 0);
+
+        funlog::set_sink(|_: &CallEvent| {});
+        inspect(&Counted);
+        funlog::clear_sinks();
+        assert_eq!(FORMATTED.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,50 @@
+use funlog::funlog;
+
+#[funlog(debug, none, stats)]
+fn parse_port(text: &str) -> Result<u16, std::num::ParseIntError> {
+    text.parse()
+}
+
+#[funlog(trace, none, stats, when = "n > 100")]
+fn square(n: u64) -> u64 {
+    n * n
+}
+
+#[funlog(trace, none)]
+fn untracked() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_report() {
+        assert!(parse_port("80").is_ok());
+        assert!(parse_port("http").is_err());
+        assert!(parse_port("8080").is_ok());
+        // The `when` guard only affects logging
+        for n in 0..5 {
+            square(n);
+        }
+        untracked();
+
+        let report = funlog::report();
+        let parse = report.get("parse_port").unwrap();
+        assert_eq!(parse.calls, 3);
+        assert_eq!(parse.errors, 1);
+        assert_eq!(parse.module, "raw_test_stats");
+        assert_eq!(parse.line, 3);
+        assert_eq!(parse.histogram.iter().sum::<u64>(), 3);
+        assert!(parse.min <= parse.mean() && parse.mean() <= parse.max);
+
+        let square = report.get("square").unwrap();
+        assert_eq!(square.calls, 5);
+        assert_eq!(square.errors, 0);
+        assert!(report.get("untracked").is_none());
+
+        let table = report.to_string();
+        assert!(table.starts_with("function "));
+        assert!(table.contains("raw_test_stats::parse_port"));
+        assert!(table.contains("raw_test_stats::square"));
+    }
+}
@@ -0,0 +1,56 @@
+use funlog::funlog;
+use std::env::set_var;
+
+#[funlog(debug, all, when = "amount > 10_000")]
+fn transfer(amount: u64) -> u64 {
+    amount
+}
+
+#[funlog(debug, none, retVal, when_ret = "ret.is_err()")]
+fn parse(input: &str) -> Result<i32, String> {
+    input.parse::<i32>().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock_logger::MockLogger;
+
+    #[test]
+    fn test_when_guard() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(transfer(500), 500);
+        assert_eq!(transfer(20_000), 20_000);
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].level, log::Level::Debug);
+            assert_eq!(entries[0].body, "transfer [in ]: amount:20000");
+            assert_eq!(entries[1].level, log::Level::Debug);
+            assert_eq!(entries[1].body, "transfer [out]");
+        });
+    }
+
+    #[test]
+    fn test_when_ret_guard() {
+        unsafe {
+            set_var("RUST_LOG", "debug");
+        }
+        mock_logger::init();
+        assert_eq!(parse("42"), Ok(42));
+        assert!(parse("forty-two").is_err());
+
+        MockLogger::entries(|entries| {
+            assert_eq!(entries.len(), 3);
+            assert_eq!(entries[0].body, "parse [in ]");
+            assert_eq!(entries[1].body, "parse [in ]");
+            assert_eq!(
+                entries[2].body,
+                "parse [out]: return:Err(\"invalid digit found in string\")"
+            );
+        });
+    }
+}
@@ -0,0 +1,10 @@
+//! Compile-fail tests for the errors reported by `#[funlog]` and `#[replay_test]`.
+//!
+//! Each `tests/ui/*.rs` file must fail to compile with exactly the diagnostics in the
+//! `.stderr` file next to it. Run with `TRYBUILD=overwrite` to accept new output.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,13 @@
+use funlog::funlog;
+
+#[funlog(retVal, ret = false)]
+fn square(x: u32) -> u32 {
+    x * x
+}
+
+#[funlog(stats, stats = false)]
+fn cube(x: u32) -> u32 {
+    x * x * x
+}
+
+fn main() {}
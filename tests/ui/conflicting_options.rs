@@ -0,0 +1,22 @@
+use funlog::funlog;
+
+#[funlog(debug, info)]
+fn two_levels() {}
+
+#[funlog(trace, debug, warn)]
+fn three_levels() {}
+
+#[funlog(all, none)]
+fn all_and_none(param: i32) {
+    let _ = param;
+}
+
+#[funlog(none, params(param))]
+fn none_and_params(param: i32) {
+    let _ = param;
+}
+
+#[funlog(onStart, onEnd)]
+fn two_positions() {}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use funlog::funlog;
+
+#[funlog(debgu)]
+fn log_level() {}
+
+#[funlog(onStrat)]
+fn position() {}
+
+#[funlog(retVla)]
+fn return_value() {}
+
+#[funlog(DEBUG)]
+fn upper_case() {}
+
+#[funlog(param(a))]
+fn singular_params(a: i32) {
+    let _ = a;
+}
+
+#[funlog(completely_unknown_attribute)]
+fn unknown() {}
+
+fn main() {}
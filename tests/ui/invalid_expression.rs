@@ -0,0 +1,13 @@
+use funlog::funlog;
+
+#[funlog(debug, when = "amount >")]
+fn incomplete(amount: u64) {
+    let _ = amount;
+}
+
+#[funlog(debug, when_ret = "ret.is_err(")]
+fn unclosed(text: &str) -> Result<u8, std::num::ParseIntError> {
+    text.parse()
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use funlog::funlog;
+
+#[funlog(params(nonexistent_param))]
+fn single(valid_param: i32) {
+    let _ = valid_param;
+}
+
+#[funlog(params(valid_param, wrong_param))]
+fn partial(valid_param: i32, another_param: &str) {
+    let _ = (valid_param, another_param);
+}
+
+#[funlog(params(some_param))]
+fn without_params() {}
+
+fn main() {}
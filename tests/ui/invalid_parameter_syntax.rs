@@ -0,0 +1,12 @@
+use funlog::funlog;
+
+#[funlog(stats = "yes")]
+fn not_a_bool() {}
+
+#[funlog(level = "loud")]
+fn unknown_level() {}
+
+#[funlog(level(debug))]
+fn level_as_list() {}
+
+fn main() {}
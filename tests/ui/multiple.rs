@@ -0,0 +1,8 @@
+use funlog::funlog;
+
+#[funlog(debgu, info, params(z), warn)]
+fn add(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+fn main() {}
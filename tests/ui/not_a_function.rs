@@ -0,0 +1,8 @@
+use funlog::funlog;
+
+#[funlog(debug)]
+struct Config {
+    verbose: bool,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use funlog::funlog;
+
+struct Secret(u64);
+
+#[funlog(debug)]
+fn unlock(secret: Secret, attempt: u32) -> bool {
+    secret.0 == u64::from(attempt)
+}
+
+fn main() {}
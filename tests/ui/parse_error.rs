@@ -0,0 +1,13 @@
+use funlog::funlog;
+
+#[funlog(params(a b))]
+fn missing_comma(a: i32, b: i32) {
+    let _ = (a, b);
+}
+
+#[funlog(fields(1 = a))]
+fn field_without_name(a: i32) {
+    let _ = a;
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use funlog::replay_test;
+
+#[replay_test("tests/cassettes/total.jsonl")]
+async fn replay_async(items: Vec<u32>) -> u32 {
+    items.iter().sum()
+}
+
+#[replay_test("tests/cassettes/total.jsonl")]
+fn replay_generic<T: Into<u32>>(item: T) -> u32 {
+    item.into()
+}
+
+#[replay_test("tests/cassettes/total.jsonl", fn_name = "total")]
+fn replay_unknown_option(items: Vec<u32>) -> u32 {
+    items.iter().sum()
+}
+
+fn main() {}
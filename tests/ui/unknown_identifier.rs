@@ -0,0 +1,13 @@
+use funlog::funlog;
+
+#[funlog(debug, when = "amout > 100")]
+fn misspelled(amount: u64) {
+    let _ = amount;
+}
+
+#[funlog(debug, fields(total = count * price))]
+fn field_expression(count: u32) {
+    let _ = count;
+}
+
+fn main() {}
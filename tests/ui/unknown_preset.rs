@@ -0,0 +1,6 @@
+use funlog::funlog;
+
+#[funlog(preset = audit)]
+fn undefined() {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use funlog::funlog;
+
+#[funlog(debug)]
+const fn constant(x: u32) -> u32 {
+    x
+}
+
+fn main() {}